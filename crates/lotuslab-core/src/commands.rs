@@ -2,13 +2,34 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use specta::specta;
-use tauri::{State, async_runtime::Mutex, command};
+use std::path::PathBuf;
 
-use lotuslab_types::{Folder, FolderChildren, FolderId, NewFolder};
+use serde::{Deserialize, Serialize};
+use specta::{Type, specta};
+use tauri::async_runtime::{Mutex, spawn_blocking};
+use tauri::{AppHandle, State, command};
+use tauri_specta::Event;
+
+use lotuslab_types::{
+    AutoTagReport, BackupInfo, BackupScope, BackupSettings, BuyListFormat, CardCoreDetail,
+    CardCoreId, CardRuling, CardUsage, CatalogUpdate, CatalogUpdateId, CatalogUpdateSummary,
+    CollectionEntry, CollectionEntryId, CsvColumns, CsvImportReport, CsvPreset, CustomFormat,
+    CustomFormatId, DckFormat, DeckStats, DeckValidation, DrawOdds, DrawQuestion, Folder,
+    FolderChildren, FolderId, FormatRef, FormatRules, GoldfishReport, GoldfishSettings,
+    HaveNeedReport, ListId, ManaBaseReport, NewCollectionEntry, NewFolder, NewTagRule, PreconSet,
+    Project, ProjectId, ProjectPoints, SampleHand, TagRule, TagRuleId, UpdateCollectionEntry,
+    UpdateTagRule,
+};
 
 use crate::DbState;
 
+/// Sent after a command changed the cards in a list. Project views re-run
+/// their validation, stats and points when one of their lists changes.
+#[derive(Clone, Debug, Serialize, Deserialize, Type, Event)]
+pub(crate) struct ListChanged {
+    pub list_id: ListId,
+}
+
 // ----- Folder Commands -----
 
 #[command]
@@ -72,3 +93,518 @@ pub(crate) async fn delete_folder(
     let store = &state.lock().await.store;
     lotuslab_services::delete_folder(store, &id).await
 }
+
+// ----- Card Commands -----
+
+#[command]
+#[specta]
+pub(crate) async fn search_cards(
+    state: State<'_, Mutex<DbState>>,
+    query: String,
+    limit: Option<u32>,
+) -> Result<Vec<CardCoreDetail>, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::search_cards(store, &query, limit).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn get_card_usage(
+    state: State<'_, Mutex<DbState>>,
+    card_core_id: CardCoreId,
+) -> Result<CardUsage, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::get_card_usage(store, &card_core_id).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn get_card_rulings(
+    state: State<'_, Mutex<DbState>>,
+    id: CardCoreId,
+) -> Result<Vec<CardRuling>, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::get_card_rulings(store, &id).await
+}
+
+// ----- Format Commands -----
+
+#[command]
+#[specta]
+pub(crate) async fn list_formats() -> Result<Vec<FormatRules>, String> {
+    Ok(lotuslab_services::list_formats())
+}
+
+/// Cheap enough to call again whenever one of the project's lists changes.
+#[command]
+#[specta]
+pub(crate) async fn validate_project(
+    state: State<'_, Mutex<DbState>>,
+    project_id: ProjectId,
+    format: FormatRef,
+) -> Result<DeckValidation, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::validate_project(store, &project_id, &format).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn get_project_points(
+    state: State<'_, Mutex<DbState>>,
+    project_id: ProjectId,
+    format: FormatRef,
+) -> Result<ProjectPoints, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::project_points(store, &project_id, &format).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn list_custom_formats(
+    state: State<'_, Mutex<DbState>>,
+) -> Result<Vec<CustomFormat>, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::list_custom_formats(store).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn create_custom_format(
+    state: State<'_, Mutex<DbState>>,
+    rules: FormatRules,
+) -> Result<CustomFormat, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::create_custom_format(store, rules).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn update_custom_format(
+    state: State<'_, Mutex<DbState>>,
+    id: CustomFormatId,
+    rules: FormatRules,
+) -> Result<CustomFormat, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::update_custom_format(store, &id, rules).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn delete_custom_format(
+    state: State<'_, Mutex<DbState>>,
+    id: CustomFormatId,
+) -> Result<(), String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::delete_custom_format(store, &id).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn import_custom_format(
+    state: State<'_, Mutex<DbState>>,
+    path: PathBuf,
+) -> Result<CustomFormat, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::import_custom_format(store, &path).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn export_custom_format(
+    state: State<'_, Mutex<DbState>>,
+    id: CustomFormatId,
+    path: PathBuf,
+) -> Result<(), String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::export_custom_format(store, &id, &path).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn import_points_csv(
+    state: State<'_, Mutex<DbState>>,
+    id: CustomFormatId,
+    path: PathBuf,
+    cap: Option<u32>,
+) -> Result<CustomFormat, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::import_points_csv(store, &id, &path, cap).await
+}
+
+// ----- Stats Commands -----
+
+#[command]
+#[specta]
+pub(crate) async fn get_deck_stats(
+    state: State<'_, Mutex<DbState>>,
+    list_id: ListId,
+) -> Result<DeckStats, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::deck_stats(store, &list_id).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn get_draw_odds(
+    state: State<'_, Mutex<DbState>>,
+    list_id: ListId,
+    question: DrawQuestion,
+) -> Result<DrawOdds, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::draw_odds(store, &list_id, &question).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn get_mana_base(
+    state: State<'_, Mutex<DbState>>,
+    list_id: ListId,
+) -> Result<ManaBaseReport, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::mana_base(store, &list_id).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn run_goldfish(
+    state: State<'_, Mutex<DbState>>,
+    list_id: ListId,
+    settings: GoldfishSettings,
+) -> Result<GoldfishReport, String> {
+    // Let go of the store before the games, they take a while.
+    let items = {
+        let store = &state.lock().await.store;
+        lotuslab_services::goldfish_deck(store, &list_id).await?
+    };
+    spawn_blocking(move || lotuslab_services::goldfish(&items, &settings))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[command]
+#[specta]
+pub(crate) async fn draw_sample_hand(
+    state: State<'_, Mutex<DbState>>,
+    list_id: ListId,
+    seed: Option<u32>,
+    mulligans: u32,
+) -> Result<SampleHand, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::sample_hand(store, &list_id, seed, mulligans).await
+}
+
+// ----- Tag Commands -----
+
+#[command]
+#[specta]
+pub(crate) async fn list_tag_rules(
+    state: State<'_, Mutex<DbState>>,
+) -> Result<Vec<TagRule>, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::list_tag_rules(store).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn create_tag_rule(
+    state: State<'_, Mutex<DbState>>,
+    new: NewTagRule,
+) -> Result<TagRule, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::create_tag_rule(store, new).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn update_tag_rule(
+    state: State<'_, Mutex<DbState>>,
+    id: TagRuleId,
+    patch: UpdateTagRule,
+) -> Result<TagRule, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::update_tag_rule(store, &id, patch).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn delete_tag_rule(
+    state: State<'_, Mutex<DbState>>,
+    id: TagRuleId,
+) -> Result<(), String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::delete_tag_rule(store, &id).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn add_default_tag_rules(
+    state: State<'_, Mutex<DbState>>,
+) -> Result<Vec<TagRule>, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::add_default_tag_rules(store).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn run_tag_rules(
+    state: State<'_, Mutex<DbState>>,
+    list_id: ListId,
+) -> Result<AutoTagReport, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::run_tag_rules(store, &list_id).await
+}
+
+// ----- Collection Commands -----
+
+#[command]
+#[specta]
+pub(crate) async fn list_collection(
+    state: State<'_, Mutex<DbState>>,
+) -> Result<Vec<CollectionEntry>, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::list_collection(store).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn create_collection_entry(
+    state: State<'_, Mutex<DbState>>,
+    new: NewCollectionEntry,
+) -> Result<CollectionEntry, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::create_collection_entry(store, new).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn update_collection_entry(
+    state: State<'_, Mutex<DbState>>,
+    id: CollectionEntryId,
+    patch: UpdateCollectionEntry,
+) -> Result<CollectionEntry, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::update_collection_entry(store, &id, patch).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn delete_collection_entry(
+    state: State<'_, Mutex<DbState>>,
+    id: CollectionEntryId,
+) -> Result<(), String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::delete_collection_entry(store, &id).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn get_have_need(
+    state: State<'_, Mutex<DbState>>,
+    project_id: ProjectId,
+) -> Result<HaveNeedReport, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::get_have_need(store, &project_id).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn export_buy_list(
+    state: State<'_, Mutex<DbState>>,
+    project_id: ProjectId,
+    format: BuyListFormat,
+) -> Result<String, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::export_buy_list(store, &project_id, format).await
+}
+
+// ----- Catalog Commands -----
+
+#[command]
+#[specta]
+pub(crate) async fn update_card_catalog(
+    state: State<'_, Mutex<DbState>>,
+    path: PathBuf,
+    allow_missing_checksum: bool,
+) -> Result<CatalogUpdateSummary, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::update_card_catalog(store, &path, allow_missing_checksum).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn list_catalog_updates(
+    state: State<'_, Mutex<DbState>>,
+) -> Result<Vec<CatalogUpdateSummary>, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::list_catalog_updates(store).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn get_catalog_update(
+    state: State<'_, Mutex<DbState>>,
+    id: CatalogUpdateId,
+) -> Result<CatalogUpdate, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::get_catalog_update(store, &id).await
+}
+
+// ----- Import Commands -----
+
+#[command]
+#[specta]
+pub(crate) async fn get_csv_preset_columns(preset: CsvPreset) -> Result<CsvColumns, String> {
+    Ok(lotuslab_services::get_csv_preset_columns(preset))
+}
+
+#[command]
+#[specta]
+pub(crate) async fn import_csv_into_list(
+    app: AppHandle,
+    state: State<'_, Mutex<DbState>>,
+    list_id: ListId,
+    input: String,
+    columns: CsvColumns,
+) -> Result<CsvImportReport, String> {
+    let store = &state.lock().await.store;
+    let report = lotuslab_services::import_csv_into_list(store, &list_id, &input, &columns).await?;
+    if let Err(e) = (ListChanged { list_id }).emit(&app) {
+        log::warn!("failed to announce list change: {}", e);
+    }
+    Ok(report)
+}
+
+#[command]
+#[specta]
+pub(crate) async fn import_csv_into_collection(
+    state: State<'_, Mutex<DbState>>,
+    input: String,
+    columns: CsvColumns,
+) -> Result<CsvImportReport, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::import_csv_into_collection(store, &input, &columns).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn list_precons(decks_dir: PathBuf) -> Result<Vec<PreconSet>, String> {
+    lotuslab_services::list_precons(&decks_dir)
+}
+
+#[command]
+#[specta]
+pub(crate) async fn import_precon(
+    state: State<'_, Mutex<DbState>>,
+    decks_dir: PathBuf,
+    file_name: String,
+    folder: Option<FolderId>,
+) -> Result<Project, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::import_precon(store, &decks_dir, &file_name, folder).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn import_project_bundle(
+    state: State<'_, Mutex<DbState>>,
+    path: PathBuf,
+    folder: Option<FolderId>,
+) -> Result<Project, String> {
+    let state = state.lock().await;
+    let images_dir = state.app_data_dir.join("images");
+    lotuslab_services::import_project_bundle(&state.store, &path, folder, &images_dir).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn import_dck(
+    state: State<'_, Mutex<DbState>>,
+    path: PathBuf,
+    format: DckFormat,
+    folder: Option<FolderId>,
+) -> Result<Project, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::import_dck(store, &path, format, folder).await
+}
+
+// ----- Export Commands -----
+
+#[command]
+#[specta]
+pub(crate) async fn export_project_tts(
+    state: State<'_, Mutex<DbState>>,
+    project_id: ProjectId,
+) -> Result<String, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::export_project_tts(store, &project_id).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn export_project_dck(
+    state: State<'_, Mutex<DbState>>,
+    project_id: ProjectId,
+    format: DckFormat,
+) -> Result<String, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::export_project_dck(store, &project_id, format).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn export_project_bundle(
+    state: State<'_, Mutex<DbState>>,
+    project_id: ProjectId,
+    path: PathBuf,
+) -> Result<(), String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::export_project_bundle(store, &project_id, &path).await
+}
+
+// ----- Backup Commands -----
+
+#[command]
+#[specta]
+pub(crate) async fn backup_database(
+    state: State<'_, Mutex<DbState>>,
+    path: PathBuf,
+    scope: BackupScope,
+) -> Result<BackupInfo, String> {
+    let store = &state.lock().await.store;
+    lotuslab_services::backup_database(store, &path, scope).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn restore_database(
+    state: State<'_, Mutex<DbState>>,
+    path: PathBuf,
+) -> Result<BackupInfo, String> {
+    let state = state.lock().await;
+    lotuslab_services::restore_database(&state.store, &path, &state.backups_dir()).await
+}
+
+#[command]
+#[specta]
+pub(crate) async fn list_backups(
+    state: State<'_, Mutex<DbState>>,
+) -> Result<Vec<BackupInfo>, String> {
+    lotuslab_services::list_backups(&state.lock().await.backups_dir())
+}
+
+#[command]
+#[specta]
+pub(crate) async fn get_backup_settings(
+    state: State<'_, Mutex<DbState>>,
+) -> Result<BackupSettings, String> {
+    lotuslab_services::get_backup_settings(&state.lock().await.backups_dir())
+}
+
+#[command]
+#[specta]
+pub(crate) async fn set_backup_settings(
+    state: State<'_, Mutex<DbState>>,
+    settings: BackupSettings,
+) -> Result<(), String> {
+    lotuslab_services::set_backup_settings(&state.lock().await.backups_dir(), &settings)
+}
@@ -3,35 +3,96 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::Error;
 use log::LevelFilter;
 use lotuslab_db::{Store, open_store};
 use specta_typescript::Typescript;
 use tauri::{
-    Manager,
+    AppHandle, Manager,
     async_runtime::{Mutex, block_on},
     generate_context,
 };
-use tauri_specta::{Builder, collect_commands};
+use tauri_specta::{Builder, collect_commands, collect_events};
 
 mod commands;
 use commands::*;
 
 pub(crate) struct DbState {
     store: Store,
+    app_data_dir: PathBuf,
 }
 
+impl DbState {
+    fn backups_dir(&self) -> PathBuf {
+        self.app_data_dir.join("backups")
+    }
+}
+
+/// How often to check whether an automatic backup is due.
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let builder = Builder::<tauri::Wry>::new().commands(collect_commands![
-        get_folder_metadata,
-        get_folder_children,
-        new_folder,
-        rename_folder,
-        move_folder,
-        delete_folder
-    ]);
+    let builder = Builder::<tauri::Wry>::new()
+        .commands(collect_commands![
+            get_folder_metadata,
+            get_folder_children,
+            new_folder,
+            rename_folder,
+            move_folder,
+            delete_folder,
+            search_cards,
+            get_card_usage,
+            get_card_rulings,
+            list_formats,
+            validate_project,
+            get_project_points,
+            list_custom_formats,
+            create_custom_format,
+            update_custom_format,
+            delete_custom_format,
+            import_custom_format,
+            export_custom_format,
+            import_points_csv,
+            get_deck_stats,
+            get_draw_odds,
+            get_mana_base,
+            run_goldfish,
+            draw_sample_hand,
+            list_tag_rules,
+            create_tag_rule,
+            update_tag_rule,
+            delete_tag_rule,
+            add_default_tag_rules,
+            run_tag_rules,
+            list_collection,
+            create_collection_entry,
+            update_collection_entry,
+            delete_collection_entry,
+            get_have_need,
+            export_buy_list,
+            update_card_catalog,
+            list_catalog_updates,
+            get_catalog_update,
+            get_csv_preset_columns,
+            import_csv_into_list,
+            import_csv_into_collection,
+            list_precons,
+            import_precon,
+            import_project_bundle,
+            import_dck,
+            export_project_tts,
+            export_project_dck,
+            export_project_bundle,
+            backup_database,
+            restore_database,
+            list_backups,
+            get_backup_settings,
+            set_backup_settings
+        ])
+        .events(collect_events![ListChanged]);
 
     #[cfg(debug_assertions)] // <- Only export on non-release builds
     builder
@@ -46,12 +107,18 @@ pub fn run() {
         )
         .invoke_handler(builder.invoke_handler())
         .setup(move |app| {
+            builder.mount_events(app);
             let app_dir = app
                 .path()
                 .app_data_dir()
                 .expect("failed to get app data directory");
-            let store = block_on(setup_db(app_dir)).expect("failed to initialize database store");
-            app.manage(Mutex::new(DbState { store }));
+            let store =
+                block_on(setup_db(app_dir.clone())).expect("failed to initialize database store");
+            app.manage(Mutex::new(DbState {
+                store,
+                app_data_dir: app_dir,
+            }));
+            tauri::async_runtime::spawn(scheduled_backups(app.handle().clone()));
 
             Ok(())
         })
@@ -63,3 +130,19 @@ async fn setup_db(app_data_dir: PathBuf) -> Result<Store, Error> {
     let db_path = app_data_dir.join("db");
     open_store(db_path).await.map_err(Error::msg)
 }
+
+async fn scheduled_backups(app: AppHandle) {
+    loop {
+        {
+            let state = app.state::<Mutex<DbState>>();
+            let state = state.lock().await;
+            match lotuslab_services::run_scheduled_backup(&state.store, &state.backups_dir()).await
+            {
+                Ok(Some(backup)) => log::info!("wrote automatic backup {}", backup.path),
+                Ok(None) => {}
+                Err(e) => log::error!("automatic backup failed: {}", e),
+            }
+        }
+        tokio::time::sleep(BACKUP_CHECK_INTERVAL).await;
+    }
+}
@@ -0,0 +1,350 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Logical backups of the database.
+//!
+//! A backup is a SurrealQL export of the user database with a one line
+//! header comment in front of it:
+//!
+//! ```text
+//! -- lotuslab backup schema_version=2 scope=user created_at=2025-06-01T12:00:00Z
+//! ```
+//!
+//! Backups that include card data append an export of the catalog database
+//! after a `-- lotuslab catalog` marker line. Both are plain comments, so each
+//! part can still be fed to `surreal import` by hand.
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use lotuslab_types::{BackupScope, RepoError};
+
+use surrealdb::{Surreal, engine::local::Db};
+
+use crate::migrate::set_schema_version;
+use crate::{CATALOG_SCHEMA, SCHEMA, SCHEMA_VERSION, Store};
+
+const BACKUP_MAGIC: &str = "-- lotuslab backup";
+const CATALOG_MARKER: &str = "-- lotuslab catalog";
+
+/// Tables in the user database.
+const USER_TABLES: &[&str] = &[
+    "folder",
+    "project",
+    "list",
+    "list_contains",
+    "tag",
+    "tag_rule",
+    "custom_format",
+    "catalog_update",
+    "collection_entry",
+];
+/// Tables in the catalog database.
+const CATALOG_TABLES: &[&str] = &["card_core", "card_printing", "card_ruling"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackupHeader {
+    pub schema_version: u32,
+    pub scope: BackupScope,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BackupHeader {
+    fn to_line(&self) -> String {
+        format!(
+            "{} schema_version={} scope={} created_at={}\n",
+            BACKUP_MAGIC,
+            self.schema_version,
+            self.scope.as_str(),
+            self.created_at.to_rfc3339()
+        )
+    }
+
+    fn parse(line: &str) -> Result<Self, RepoError> {
+        let not_a_backup = || RepoError::InvalidInput("not a lotuslab backup".into());
+        let fields = line
+            .trim()
+            .strip_prefix(BACKUP_MAGIC)
+            .ok_or_else(not_a_backup)?;
+
+        let mut schema_version = None;
+        let mut scope = None;
+        let mut created_at = None;
+        for field in fields.split_whitespace() {
+            match field.split_once('=') {
+                Some(("schema_version", v)) => schema_version = v.parse::<u32>().ok(),
+                Some(("scope", v)) => scope = BackupScope::parse(v),
+                Some(("created_at", v)) => {
+                    created_at = DateTime::parse_from_rfc3339(v)
+                        .ok()
+                        .map(|d| d.with_timezone(&Utc))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(BackupHeader {
+            schema_version: schema_version.ok_or_else(not_a_backup)?,
+            scope: scope.ok_or_else(not_a_backup)?,
+            created_at: created_at.ok_or_else(not_a_backup)?,
+        })
+    }
+}
+
+/// Turns a definition of the export into one that replaces what's there.
+/// Exports spell out definitions the database adds on its own, like the
+/// `in` and `out` of relations or the `.*` of array fields, which a plain
+/// `DEFINE` then rejects as already existing.
+fn overwrite_definition(line: &str) -> Cow<'_, str> {
+    for kind in ["TABLE", "FIELD", "INDEX", "EVENT"] {
+        let define = format!("DEFINE {} ", kind);
+        if let Some(rest) = line.strip_prefix(&define)
+            && !rest.starts_with("OVERWRITE ")
+        {
+            return Cow::Owned(format!("DEFINE {} OVERWRITE {}", kind, rest));
+        }
+    }
+    Cow::Borrowed(line)
+}
+
+/// Reads the header of a backup file without loading the rest of it.
+pub fn read_backup_header(path: impl AsRef<Path>) -> Result<BackupHeader, RepoError> {
+    let file = File::open(path).map_err(|e| RepoError::DbError(e.into()))?;
+    let mut line = String::new();
+    BufReader::new(file)
+        .read_line(&mut line)
+        .map_err(|e| RepoError::DbError(e.into()))?;
+
+    BackupHeader::parse(&line)
+}
+
+async fn export_tables(db: &Surreal<Db>, path: &Path, tables: &[&str]) -> Result<(), RepoError> {
+    db.export(path)
+        .with_config()
+        .users(false)
+        .accesses(false)
+        .params(false)
+        .functions(false)
+        .analyzers(false)
+        .versions(false)
+        .tables(tables.to_vec())
+        .await
+        .map_err(|e| RepoError::DbError(e.into()))
+}
+
+async fn replace_tables(
+    db: &Surreal<Db>,
+    path: &Path,
+    tables: &[&str],
+    schema: &str,
+) -> Result<(), RepoError> {
+    let remove = tables
+        .iter()
+        .map(|table| format!("REMOVE TABLE IF EXISTS {};", table))
+        .collect::<String>();
+    db.query(remove)
+        .await
+        .map_err(|e| RepoError::DbError(e.into()))?
+        .check()
+        .map_err(|e| RepoError::DbError(e.into()))?;
+
+    db.import(path)
+        .await
+        .map_err(|e| RepoError::DbError(e.into()))?;
+
+    // The export only has what existed at backup time, bring back the root
+    // folder and anything else the schema sets up.
+    db.query(schema)
+        .await
+        .map_err(|e| RepoError::DbError(e.into()))?;
+
+    Ok(())
+}
+
+impl Store {
+    /// Writes a consistent export of the databases in `scope` to `path`.
+    ///
+    /// Exports go to sibling `.partial` files first so a failed or
+    /// interrupted backup never leaves a truncated file behind under the
+    /// real name.
+    pub async fn backup(
+        &self,
+        path: impl AsRef<Path>,
+        scope: BackupScope,
+    ) -> Result<BackupHeader, RepoError> {
+        let path = path.as_ref();
+        let user_part = path.with_extension("user.partial");
+        let catalog_part = path.with_extension("catalog.partial");
+        let with_cards = scope == BackupScope::UserAndCards;
+
+        let cleanup = || {
+            let _ = std::fs::remove_file(&user_part);
+            let _ = std::fs::remove_file(&catalog_part);
+        };
+
+        let exported = async {
+            export_tables(&self.db, &user_part, USER_TABLES).await?;
+            if with_cards {
+                export_tables(&self.catalog, &catalog_part, CATALOG_TABLES).await?;
+            }
+            Ok::<_, RepoError>(())
+        }
+        .await;
+        if let Err(e) = exported {
+            cleanup();
+            return Err(e);
+        }
+
+        let header = BackupHeader {
+            schema_version: SCHEMA_VERSION,
+            scope,
+            created_at: Utc::now(),
+        };
+
+        let write = || -> std::io::Result<()> {
+            let mut out = BufWriter::new(File::create(path)?);
+            out.write_all(header.to_line().as_bytes())?;
+            std::io::copy(&mut File::open(&user_part)?, &mut out)?;
+            if with_cards {
+                writeln!(out, "\n{}", CATALOG_MARKER)?;
+                std::io::copy(&mut File::open(&catalog_part)?, &mut out)?;
+            }
+            out.into_inner()?.sync_all()
+        };
+        let written = write();
+        cleanup();
+        written.map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(header)
+    }
+
+    /// Replaces the data covered by the backup at `path` with its contents.
+    ///
+    /// Restoring a user data backup leaves the catalog alone. Backups made
+    /// by older versions are migrated after loading them, backups from a
+    /// newer version are rejected before anything is touched.
+    pub async fn restore(&self, path: impl AsRef<Path>) -> Result<BackupHeader, RepoError> {
+        let path = path.as_ref();
+        let header = read_backup_header(path)?;
+
+        if header.schema_version > SCHEMA_VERSION {
+            return Err(RepoError::InvalidInput(format!(
+                "backup has schema version {}, newer than the {} this version of LotusLab uses",
+                header.schema_version, SCHEMA_VERSION
+            )));
+        }
+
+        // Split the file back into one export per database.
+        let stem = std::env::temp_dir().join(format!(
+            "lotuslab-restore-{}-{}",
+            std::process::id(),
+            header.created_at.timestamp()
+        ));
+        let user_part = stem.with_extension("user.surql");
+        let catalog_part = stem.with_extension("catalog.surql");
+        let cleanup = || {
+            let _ = std::fs::remove_file(&user_part);
+            let _ = std::fs::remove_file(&catalog_part);
+        };
+
+        let split = || -> std::io::Result<bool> {
+            let mut user = BufWriter::new(File::create(&user_part)?);
+            let mut catalog: Option<BufWriter<File>> = None;
+            for line in BufReader::new(File::open(path)?).lines() {
+                let line = line?;
+                if catalog.is_none() && line.trim() == CATALOG_MARKER {
+                    catalog = Some(BufWriter::new(File::create(&catalog_part)?));
+                    continue;
+                }
+                let out = catalog.as_mut().unwrap_or(&mut user);
+                writeln!(out, "{}", overwrite_definition(&line))?;
+            }
+            user.flush()?;
+            match catalog {
+                Some(mut catalog) => catalog.flush().map(|_| true),
+                None => Ok(false),
+            }
+        };
+        let has_catalog = match split() {
+            Ok(has_catalog) => has_catalog,
+            Err(e) => {
+                cleanup();
+                return Err(RepoError::DbError(e.into()));
+            }
+        };
+        if (header.scope == BackupScope::UserAndCards) != has_catalog {
+            cleanup();
+            return Err(RepoError::InvalidInput(
+                "backup is incomplete, card data doesn't match its header".into(),
+            ));
+        }
+
+        let restored = async {
+            // The version isn't part of the export, set it to the backup's
+            // so the migrations it predates run.
+            replace_tables(&self.db, &user_part, USER_TABLES, SCHEMA).await?;
+            set_schema_version(&self.db, header.schema_version, "").await?;
+            if has_catalog {
+                replace_tables(&self.catalog, &catalog_part, CATALOG_TABLES, CATALOG_SCHEMA)
+                    .await?;
+                set_schema_version(&self.catalog, header.schema_version, "").await?;
+            }
+            self.migrate().await
+        }
+        .await;
+        cleanup();
+        restored?;
+
+        Ok(header)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_header_round_trip() {
+        let header = BackupHeader {
+            schema_version: 3,
+            scope: BackupScope::UserAndCards,
+            created_at: DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+                .expect("valid timestamp")
+                .with_timezone(&Utc),
+        };
+
+        assert_eq!(BackupHeader::parse(&header.to_line()).ok(), Some(header));
+    }
+
+    #[test]
+    fn test_overwrite_definition() {
+        assert_eq!(
+            overwrite_definition("DEFINE FIELD in ON list_contains TYPE record<list>;"),
+            "DEFINE FIELD OVERWRITE in ON list_contains TYPE record<list>;"
+        );
+        assert_eq!(
+            overwrite_definition("DEFINE INDEX OVERWRITE name ON tag FIELDS name;"),
+            "DEFINE INDEX OVERWRITE name ON tag FIELDS name;"
+        );
+        assert_eq!(
+            overwrite_definition("INSERT [ { name: 'DEFINE FIELD x' } ];"),
+            "INSERT [ { name: 'DEFINE FIELD x' } ];"
+        );
+    }
+
+    #[test]
+    fn test_backup_header_rejects_other_files() {
+        assert!(matches!(
+            BackupHeader::parse("-- ------------------------------"),
+            Err(RepoError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            BackupHeader::parse("-- lotuslab backup scope=user"),
+            Err(RepoError::InvalidInput(_))
+        ));
+    }
+}
@@ -6,13 +6,20 @@ use std::path::Path;
 
 use lotuslab_types::RepoError;
 
+mod backup;
+mod migrate;
 mod repos;
 mod store;
 mod tests;
 
+pub use backup::{BackupHeader, read_backup_header};
 pub use store::Store;
 
 const SCHEMA: &str = include_str!("schema.surql");
+const CATALOG_SCHEMA: &str = include_str!("catalog.surql");
+/// Bump whenever `schema.surql` or `catalog.surql` change in a way older
+/// backups don't match, and give any data changes a step in `migrate.rs`.
+pub const SCHEMA_VERSION: u32 = 9;
 
 pub async fn open_store(path: impl AsRef<Path>) -> Result<Store, RepoError> {
     let store = Store::new_rocksdb(path)
@@ -25,5 +32,12 @@ pub async fn open_store(path: impl AsRef<Path>) -> Result<Store, RepoError> {
         .await
         .map_err(|e| RepoError::DbError(e.into()))?;
 
-    return Ok(store);
+    store
+        .catalog
+        .query(CATALOG_SCHEMA)
+        .await
+        .map_err(|e| RepoError::DbError(e.into()))?;
+    store.migrate().await?;
+
+    Ok(store)
 }
@@ -0,0 +1,96 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Brings data written by older versions up to the current schema.
+//!
+//! `schema.surql` and `catalog.surql` only define tables and fields, they run
+//! on every start and must not touch data. Changes to existing records go
+//! here instead, keyed by the [SCHEMA_VERSION] that introduced them. Each
+//! database remembers the version it was last migrated to in
+//! `schema_meta:version`, so every step runs once. Databases from before
+//! that record existed start at 0 and run every step, which is why steps
+//! only touch records that still need it.
+
+use lotuslab_types::RepoError;
+use surrealdb::{Surreal, engine::local::Db};
+
+use crate::{SCHEMA_VERSION, Store};
+
+/// Data migrations of the user database, in order, each with the schema
+/// version it belongs to.
+const MIGRATIONS: &[(u32, &str)] = &[
+    (
+        7,
+        // list items from before tag rules
+        "UPDATE list_contains SET auto_tags = [], dismissed_tags = [] \
+             WHERE auto_tags IS NONE;",
+    ),
+    (
+        9,
+        // lists used to be told apart by name
+        "UPDATE list SET role = 'sideboard' \
+             WHERE role IS NONE AND string::lowercase(string::trim(name)) = 'sideboard'; \
+         UPDATE list SET role = 'maybeboard' \
+             WHERE role IS NONE AND string::lowercase(string::trim(name)) = 'maybeboard'; \
+         UPDATE list SET role = 'main' WHERE role IS NONE;",
+    ),
+];
+
+/// Like [MIGRATIONS], for the card catalog.
+const CATALOG_MIGRATIONS: &[(u32, &str)] = &[];
+
+impl Store {
+    /// Runs the migrations of both databases newer than the version each
+    /// was last migrated to.
+    pub(crate) async fn migrate(&self) -> Result<(), RepoError> {
+        migrate(&self.db, MIGRATIONS).await?;
+        self.migrate_catalog().await
+    }
+
+    pub(crate) async fn migrate_catalog(&self) -> Result<(), RepoError> {
+        migrate(&self.catalog, CATALOG_MIGRATIONS).await
+    }
+}
+
+/// Each step is committed together with its version, so an interrupted
+/// migration picks up where it stopped.
+async fn migrate(db: &Surreal<Db>, migrations: &[(u32, &str)]) -> Result<(), RepoError> {
+    let migrated = db
+        .query("SELECT VALUE version FROM schema_meta:version;")
+        .await
+        .map_err(|e| RepoError::DbError(e.into()))?
+        .take::<Option<i64>>(0)
+        .map_err(|e| RepoError::DbError(e.into()))?
+        .unwrap_or_default();
+
+    for (version, migration) in migrations {
+        if i64::from(*version) > migrated {
+            set_schema_version(db, *version, migration).await?;
+        }
+    }
+
+    if migrated < i64::from(SCHEMA_VERSION) {
+        set_schema_version(db, SCHEMA_VERSION, "").await?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_schema_version(
+    db: &Surreal<Db>,
+    version: u32,
+    migration: &str,
+) -> Result<(), RepoError> {
+    db.query(format!(
+        "BEGIN; {} UPSERT schema_meta:version SET version = $version; COMMIT;",
+        migration
+    ))
+    .bind(("version", version))
+    .await
+    .map_err(|e| RepoError::DbError(e.into()))?
+    .check()
+    .map_err(|e| RepoError::DbError(e.into()))?;
+
+    Ok(())
+}
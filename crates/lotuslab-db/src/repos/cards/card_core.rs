@@ -1,3 +1,233 @@
 // SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, RecordIdKey, SurrealValue, ToSql};
+
+use crate::Store;
+use crate::repos::record_id_string;
+use lotuslab_types::{
+    CardCoreDetail, CardCoreId, CardCoreReadRepo, Color, Legality, ManaCost, ManaSymbol,
+    OracleText, RepoError, TypeLine,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, SurrealValue)]
+#[serde(transparent)]
+pub(crate) struct SurrealCardCoreId(RecordId);
+
+impl SurrealCardCoreId {
+    /// *Infallible* constructor for building an ID in code.
+    /// Panics if key is not kind `RecordIdKey::String`
+    pub(crate) fn from_key_unchecked(key: impl Into<RecordIdKey>) -> Self {
+        let key = key.into();
+        debug_assert!(matches!(key, RecordIdKey::String(_)));
+
+        SurrealCardCoreId(RecordId::new("card_core", key))
+    }
+
+    /// *Fallible* constructor for parsing from a [RecordId]-ish at runtime.
+    /// Checks that the id has table type `card_core`.
+    pub(crate) fn from_record_id(id: impl Into<RecordId>) -> Result<Self, RepoError> {
+        let record_id = id.into();
+
+        match record_id.is_table_type(&["card_core".to_string()]) {
+            false => Err(RepoError::InvalidInput("not a card core id".into())),
+            true => Ok(SurrealCardCoreId::from_key_unchecked(record_id.key)),
+        }
+    }
+
+    /// Convert to a [RecordId] for passing into db queries.
+    pub fn into_record(self) -> RecordId {
+        self.0
+    }
+
+    /// Convert to a string id of form `"card_core:id"`.
+    pub fn to_sql(&self) -> String {
+        self.0.to_sql()
+    }
+}
+
+impl TryFrom<RecordId> for SurrealCardCoreId {
+    type Error = RepoError;
+
+    fn try_from(id: RecordId) -> Result<Self, RepoError> {
+        SurrealCardCoreId::from_record_id(id)
+    }
+}
+
+impl TryFrom<CardCoreId> for SurrealCardCoreId {
+    type Error = RepoError;
+
+    fn try_from(id: CardCoreId) -> Result<Self, RepoError> {
+        let record_id = RecordId::parse_simple(id.as_ref())
+            .map_err(|e| RepoError::InvalidInput(e.to_string()))?;
+        SurrealCardCoreId::try_from(record_id)
+    }
+}
+
+impl From<SurrealCardCoreId> for CardCoreId {
+    fn from(value: SurrealCardCoreId) -> CardCoreId {
+        CardCoreId(record_id_string(&value.0))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealCardCore {
+    pub(crate) id: SurrealCardCoreId,
+    pub(crate) name: String,
+    pub(crate) mana_cost: Option<String>,
+    pub(crate) back_mana_costs: Vec<String>,
+    /// [Color::symbol] of every indicated color.
+    pub(crate) color_indicator: Vec<String>,
+    pub(crate) type_line: String,
+    pub(crate) oracle_text: Option<String>,
+    /// Format name to [Legality::as_str].
+    pub(crate) legalities: BTreeMap<String, String>,
+    /// Derived from the card's text for search, all lowercase. Every type on
+    /// any face.
+    pub(crate) types: Vec<String>,
+    pub(crate) keywords: Vec<String>,
+    /// Mana symbols the card can produce, `c` for colorless.
+    pub(crate) produces: Vec<String>,
+    pub(crate) mana_value: u32,
+}
+
+impl SurrealCardCore {
+    /// Fills the derived search fields from the card's text. Stored cards
+    /// only pick up changes to this when a catalog update rewrites them.
+    fn derive_search_fields(&mut self) {
+        let faces = TypeLine::faces(&self.type_line);
+        let oracle = OracleText::parse(self.oracle_text.as_deref().unwrap_or_default());
+
+        let types: BTreeSet<String> = faces.iter().flat_map(|face| face.words()).collect();
+        let keywords: BTreeSet<String> = oracle.keywords().map(str::to_lowercase).collect();
+        let mut colors = oracle.produced_colors();
+        colors.extend(faces.iter().flat_map(|face| face.basic_land_colors()));
+        let mut produces: Vec<String> = colors
+            .into_iter()
+            .map(|color| color.symbol().to_ascii_lowercase().to_string())
+            .collect();
+        if oracle.produces_colorless() {
+            produces.push("c".into());
+        }
+
+        self.types = types.into_iter().collect();
+        self.keywords = keywords.into_iter().collect();
+        self.produces = produces;
+        self.mana_value = ManaSymbol::scan(self.mana_cost.as_deref().unwrap_or_default())
+            .collect::<ManaCost>()
+            .mana_value();
+    }
+}
+
+impl From<SurrealCardCore> for CardCoreDetail {
+    fn from(value: SurrealCardCore) -> CardCoreDetail {
+        CardCoreDetail {
+            id: value.id.into(),
+            name: value.name,
+            mana_cost: value.mana_cost,
+            back_mana_costs: value.back_mana_costs,
+            color_indicator: value
+                .color_indicator
+                .iter()
+                .filter_map(|symbol| symbol.chars().next().and_then(Color::from_symbol))
+                .collect(),
+            type_line: value.type_line,
+            oracle_text: value.oracle_text,
+            legalities: value
+                .legalities
+                .into_iter()
+                .filter_map(|(format, legality)| Some((format, Legality::parse(&legality)?)))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<CardCoreDetail> for SurrealCardCore {
+    type Error = RepoError;
+
+    fn try_from(card: CardCoreDetail) -> Result<Self, RepoError> {
+        let mut card = SurrealCardCore {
+            id: card.id.try_into()?,
+            name: card.name,
+            mana_cost: card.mana_cost,
+            back_mana_costs: card.back_mana_costs,
+            color_indicator: card
+                .color_indicator
+                .iter()
+                .map(|color| color.symbol().to_string())
+                .collect(),
+            type_line: card.type_line,
+            oracle_text: card.oracle_text,
+            legalities: card
+                .legalities
+                .into_iter()
+                .map(|(format, legality)| (format, legality.as_str().to_string()))
+                .collect(),
+            types: Vec::new(),
+            keywords: Vec::new(),
+            produces: Vec::new(),
+            mana_value: 0,
+        };
+        card.derive_search_fields();
+        Ok(card)
+    }
+}
+
+impl Store {
+    /// Fetches many cards from the catalog at once, for joining card details
+    /// onto user data. Ids missing from the catalog are left out.
+    pub(crate) async fn card_cores_by_id(
+        &self,
+        ids: Vec<SurrealCardCoreId>,
+    ) -> Result<HashMap<SurrealCardCoreId, SurrealCardCore>, RepoError> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let ids: Vec<RecordId> = ids.into_iter().map(|id| id.into_record()).collect();
+        let cards = self
+            .catalog
+            .query("SELECT * FROM $ids;")
+            .bind(("ids", ids))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealCardCore>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(cards.into_iter().map(|c| (c.id.clone(), c)).collect())
+    }
+}
+
+#[async_trait]
+impl CardCoreReadRepo for Store {
+    async fn get_card_core(&self, id: &CardCoreId) -> Result<CardCoreDetail, RepoError> {
+        let surreal_id = SurrealCardCoreId::try_from(id.clone())?;
+        let card = self
+            .catalog
+            .select::<Option<SurrealCardCore>>(surreal_id.into_record())
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(card.into())
+    }
+
+    async fn find_card_core_by_name(&self, name: &str) -> Result<CardCoreDetail, RepoError> {
+        let card = self
+            .catalog
+            .query("SELECT * FROM card_core WHERE name = $name LIMIT 1;")
+            .bind(("name", name.to_string()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Option<SurrealCardCore>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(card.into())
+    }
+}
@@ -1,3 +1,201 @@
 // SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, RecordIdKey, SurrealValue, ToSql};
+
+use crate::repos::record_id_string;
+use crate::{Store, repos::cards::card_core::SurrealCardCoreId};
+use lotuslab_types::{
+    CardCoreId, CardPrintingDetail, CardPrintingId, CardPrintingReadRepo, Rarity, RepoError,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, SurrealValue)]
+#[serde(transparent)]
+pub(crate) struct SurrealCardPrintingId(RecordId);
+
+impl SurrealCardPrintingId {
+    /// *Infallible* constructor for building an ID in code.
+    /// Panics if key is not kind `RecordIdKey::String`
+    pub(crate) fn from_key_unchecked(key: impl Into<RecordIdKey>) -> Self {
+        let key = key.into();
+        debug_assert!(matches!(key, RecordIdKey::String(_)));
+
+        SurrealCardPrintingId(RecordId::new("card_printing", key))
+    }
+
+    /// *Fallible* constructor for parsing from a [RecordId]-ish at runtime.
+    /// Checks that the id has table type `card_printing`.
+    pub(crate) fn from_record_id(id: impl Into<RecordId>) -> Result<Self, RepoError> {
+        let record_id = id.into();
+
+        match record_id.is_table_type(&["card_printing".to_string()]) {
+            false => Err(RepoError::InvalidInput("not a card printing id".into())),
+            true => Ok(SurrealCardPrintingId::from_key_unchecked(record_id.key)),
+        }
+    }
+
+    /// Convert to a [RecordId] for passing into db queries.
+    pub fn into_record(self) -> RecordId {
+        self.0
+    }
+
+    /// Convert to a string id of form `"card_printing:id"`.
+    pub fn to_sql(&self) -> String {
+        self.0.to_sql()
+    }
+}
+
+impl TryFrom<RecordId> for SurrealCardPrintingId {
+    type Error = RepoError;
+
+    fn try_from(id: RecordId) -> Result<Self, RepoError> {
+        SurrealCardPrintingId::from_record_id(id)
+    }
+}
+
+impl TryFrom<CardPrintingId> for SurrealCardPrintingId {
+    type Error = RepoError;
+
+    fn try_from(id: CardPrintingId) -> Result<Self, RepoError> {
+        let record_id = RecordId::parse_simple(id.as_ref())
+            .map_err(|e| RepoError::InvalidInput(e.to_string()))?;
+        SurrealCardPrintingId::try_from(record_id)
+    }
+}
+
+impl From<SurrealCardPrintingId> for CardPrintingId {
+    fn from(value: SurrealCardPrintingId) -> CardPrintingId {
+        CardPrintingId(record_id_string(&value.0))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealCardPrinting {
+    pub(crate) id: SurrealCardPrintingId,
+    pub(crate) card_core: SurrealCardCoreId,
+    pub(crate) set_code: String,
+    pub(crate) collector_number: String,
+    pub(crate) scryfall_id: Option<String>,
+    pub(crate) rarity: Option<String>,
+    pub(crate) price_usd: Option<u32>,
+}
+
+impl From<SurrealCardPrinting> for CardPrintingDetail {
+    fn from(value: SurrealCardPrinting) -> CardPrintingDetail {
+        CardPrintingDetail {
+            id: value.id.into(),
+            card_core: value.card_core.into(),
+            set_code: value.set_code,
+            collector_number: value.collector_number,
+            scryfall_id: value.scryfall_id,
+            rarity: value.rarity.as_deref().and_then(Rarity::parse),
+            price_usd: value.price_usd,
+        }
+    }
+}
+
+impl TryFrom<CardPrintingDetail> for SurrealCardPrinting {
+    type Error = RepoError;
+
+    fn try_from(printing: CardPrintingDetail) -> Result<Self, RepoError> {
+        Ok(SurrealCardPrinting {
+            id: printing.id.try_into()?,
+            card_core: printing.card_core.try_into()?,
+            set_code: printing.set_code,
+            collector_number: printing.collector_number,
+            scryfall_id: printing.scryfall_id,
+            rarity: printing.rarity.map(|r| r.as_str().to_string()),
+            price_usd: printing.price_usd,
+        })
+    }
+}
+
+impl Store {
+    /// Fetches many printings from the catalog at once. Ids missing from the
+    /// catalog are left out.
+    pub(crate) async fn card_printings_by_id(
+        &self,
+        ids: Vec<SurrealCardPrintingId>,
+    ) -> Result<HashMap<SurrealCardPrintingId, SurrealCardPrinting>, RepoError> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let ids: Vec<RecordId> = ids.into_iter().map(|id| id.into_record()).collect();
+        let printings = self
+            .catalog
+            .query("SELECT * FROM $ids;")
+            .bind(("ids", ids))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealCardPrinting>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(printings.into_iter().map(|p| (p.id.clone(), p)).collect())
+    }
+}
+
+#[async_trait]
+impl CardPrintingReadRepo for Store {
+    async fn get_card_printing(
+        &self,
+        id: &CardPrintingId,
+    ) -> Result<CardPrintingDetail, RepoError> {
+        let surreal_id = SurrealCardPrintingId::try_from(id.clone())?;
+        let printing = self
+            .catalog
+            .select::<Option<SurrealCardPrinting>>(surreal_id.into_record())
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(printing.into())
+    }
+
+    async fn find_card_printing(
+        &self,
+        set_code: &str,
+        collector_number: &str,
+    ) -> Result<CardPrintingDetail, RepoError> {
+        let printing = self
+            .catalog
+            .query(
+                "SELECT * FROM card_printing \
+                 WHERE set_code = $set_code AND collector_number = $collector_number LIMIT 1;",
+            )
+            .bind(("set_code", set_code.to_ascii_uppercase()))
+            .bind(("collector_number", collector_number.to_string()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Option<SurrealCardPrinting>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(printing.into())
+    }
+
+    async fn card_printings_for_cards(
+        &self,
+        ids: &[CardCoreId],
+    ) -> Result<Vec<CardPrintingDetail>, RepoError> {
+        let cards = ids
+            .iter()
+            .map(|id| SurrealCardCoreId::try_from(id.clone()).map(|id| id.into_record()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let printings = self
+            .catalog
+            .query("SELECT * FROM card_printing WHERE card_core IN $cards;")
+            .bind(("cards", cards))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealCardPrinting>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(printings.into_iter().map(|p| p.into()).collect())
+    }
+}
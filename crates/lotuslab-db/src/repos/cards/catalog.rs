@@ -0,0 +1,256 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, SurrealValue};
+
+use crate::Store;
+use crate::repos::cards::{
+    card_core::{SurrealCardCore, SurrealCardCoreId},
+    card_printing::{SurrealCardPrinting, SurrealCardPrintingId},
+    ruling::SurrealCardRuling,
+};
+use lotuslab_types::{CatalogChanges, CatalogData, CatalogReadRepo, CatalogWriteRepo, RepoError};
+
+/// Records written per transaction. Catalog updates can touch tens of
+/// thousands of records, which is too much for a single query.
+const BATCH_SIZE: usize = 1000;
+
+#[async_trait]
+impl CatalogReadRepo for Store {
+    async fn catalog_snapshot(&self) -> Result<CatalogData, RepoError> {
+        let mut response = self
+            .catalog
+            .query(
+                "SELECT * FROM card_core; SELECT * FROM card_printing; \
+                 SELECT card_core, date, text, source FROM card_ruling;",
+            )
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        let cards = response
+            .take::<Vec<SurrealCardCore>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+        let printings = response
+            .take::<Vec<SurrealCardPrinting>>(1)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+        let rulings = response
+            .take::<Vec<SurrealCardRuling>>(2)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(CatalogData {
+            cards: cards.into_iter().map(|c| c.into()).collect(),
+            printings: printings.into_iter().map(|p| p.into()).collect(),
+            rulings: Some(SurrealCardRuling::group(rulings)?),
+        })
+    }
+}
+
+#[async_trait]
+impl CatalogWriteRepo for Store {
+    /// Printings are removed before and cards after everything else, so no
+    /// printing or ruling is ever left pointing at a missing card. References
+    /// to re-keyed printings move first, so they survive an interrupted
+    /// update.
+    async fn apply_catalog_changes(&self, changes: CatalogChanges) -> Result<(), RepoError> {
+        let remap_printings = changes
+            .remap_printings
+            .into_iter()
+            .map(|remap| {
+                Ok((
+                    SurrealCardPrintingId::try_from(remap.from)?.into_record(),
+                    SurrealCardPrintingId::try_from(remap.to)?.into_record(),
+                ))
+            })
+            .collect::<Result<HashMap<_, _>, RepoError>>()?;
+        let delete_printings = changes
+            .delete_printings
+            .into_iter()
+            .map(|id| SurrealCardPrintingId::try_from(id).map(|id| id.into_record()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let upsert_cards = changes
+            .upsert_cards
+            .into_iter()
+            .map(SurrealCardCore::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let upsert_printings = changes
+            .upsert_printings
+            .into_iter()
+            .map(SurrealCardPrinting::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let replace_rulings = changes
+            .replace_rulings
+            .into_iter()
+            .map(|rulings| {
+                let card = SurrealCardCoreId::try_from(rulings.card_core.clone())?;
+                Ok((
+                    card.into_record(),
+                    SurrealCardRuling::from_rulings(rulings)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, RepoError>>()?;
+        let delete_cards = changes
+            .delete_cards
+            .into_iter()
+            .map(|id| SurrealCardCoreId::try_from(id).map(|id| id.into_record()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !remap_printings.is_empty() {
+            self.remap_printing_references(&remap_printings).await?;
+        }
+        if !delete_printings.is_empty() {
+            self.clear_printing_references(&delete_printings).await?;
+        }
+        for batch in delete_printings.chunks(BATCH_SIZE) {
+            self.delete_batch(batch).await?;
+        }
+        for batch in upsert_cards.chunks(BATCH_SIZE) {
+            self.catalog
+                .query("BEGIN; FOR $card IN $cards { UPSERT $card.id CONTENT $card; }; COMMIT;")
+                .bind(("cards", batch.to_vec()))
+                .await
+                .map_err(|e| RepoError::DbError(e.into()))?
+                .check()
+                .map_err(|e| RepoError::DbError(e.into()))?;
+        }
+        for batch in upsert_printings.chunks(BATCH_SIZE) {
+            self.catalog
+                .query(
+                    "BEGIN; FOR $printing IN $printings { \
+                     UPSERT $printing.id CONTENT $printing; }; COMMIT;",
+                )
+                .bind(("printings", batch.to_vec()))
+                .await
+                .map_err(|e| RepoError::DbError(e.into()))?
+                .check()
+                .map_err(|e| RepoError::DbError(e.into()))?;
+        }
+        for batch in replace_rulings.chunks(BATCH_SIZE) {
+            let (cards, rulings): (Vec<RecordId>, Vec<Vec<SurrealCardRuling>>) =
+                batch.iter().cloned().unzip();
+            self.catalog
+                .query(
+                    "BEGIN; DELETE card_ruling WHERE card_core IN $cards; \
+                     FOR $ruling IN $rulings { CREATE card_ruling CONTENT $ruling; }; COMMIT;",
+                )
+                .bind(("cards", cards))
+                .bind(("rulings", rulings.concat()))
+                .await
+                .map_err(|e| RepoError::DbError(e.into()))?
+                .check()
+                .map_err(|e| RepoError::DbError(e.into()))?;
+        }
+        for batch in delete_cards.chunks(BATCH_SIZE) {
+            self.delete_batch(batch).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A user record pointing at a printing.
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+struct PrintingReference {
+    id: RecordId,
+    printing: RecordId,
+}
+
+impl Store {
+    /// Points list items and collection entries at the new ids of re-keyed
+    /// printings. User data is small next to the catalog, so every reference
+    /// is fetched and matched here rather than searched once per printing.
+    async fn remap_printing_references(
+        &self,
+        remaps: &HashMap<RecordId, RecordId>,
+    ) -> Result<(), RepoError> {
+        let mut response = self
+            .db
+            .query(
+                "SELECT id, selected_printing AS printing FROM list_contains \
+                 WHERE selected_printing != NONE; \
+                 SELECT id, card_printing AS printing FROM collection_entry \
+                 WHERE card_printing != NONE;",
+            )
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?;
+        let remap = |references: Vec<PrintingReference>| -> Vec<PrintingReference> {
+            references
+                .into_iter()
+                .filter_map(|r| {
+                    Some(PrintingReference {
+                        printing: remaps.get(&r.printing)?.clone(),
+                        id: r.id,
+                    })
+                })
+                .collect()
+        };
+        let items = remap(
+            response
+                .take::<Vec<PrintingReference>>(0)
+                .map_err(|e| RepoError::DbError(e.into()))?,
+        );
+        let entries = remap(
+            response
+                .take::<Vec<PrintingReference>>(1)
+                .map_err(|e| RepoError::DbError(e.into()))?,
+        );
+        if items.is_empty() && entries.is_empty() {
+            return Ok(());
+        }
+
+        self.db
+            .query(
+                "BEGIN; \
+                 FOR $item IN $items { UPDATE $item.id SET selected_printing = $item.printing; }; \
+                 FOR $entry IN $entries { UPDATE $entry.id SET card_printing = $entry.printing; }; \
+                 COMMIT;",
+            )
+            .bind(("items", items))
+            .bind(("entries", entries))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .check()
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(())
+    }
+
+    /// Lets go of printings about to be deleted. Re-keyed printings were
+    /// remapped already, what still points at `printings` has no
+    /// replacement: list items go back to no picked printing and collection
+    /// entries keep counting for their card without one.
+    async fn clear_printing_references(&self, printings: &[RecordId]) -> Result<(), RepoError> {
+        self.db
+            .query(
+                "BEGIN; \
+                 UPDATE list_contains SET selected_printing = NONE \
+                     WHERE selected_printing IN $printings; \
+                 UPDATE collection_entry SET card_printing = NONE \
+                     WHERE card_printing IN $printings; \
+                 COMMIT;",
+            )
+            .bind(("printings", printings.to_vec()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .check()
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(())
+    }
+
+    async fn delete_batch(&self, ids: &[RecordId]) -> Result<(), RepoError> {
+        self.catalog
+            .query("BEGIN; FOR $id IN $ids { DELETE $id; }; COMMIT;")
+            .bind(("ids", ids.to_vec()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .check()
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(())
+    }
+}
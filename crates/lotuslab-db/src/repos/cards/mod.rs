@@ -2,9 +2,12 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-mod artist;
-mod card_core;
-mod card_printing;
-mod set;
-mod token_core;
-mod token_printing;
+pub(crate) mod artist;
+pub(crate) mod card_core;
+pub(crate) mod card_printing;
+pub(crate) mod catalog;
+pub(crate) mod ruling;
+pub(crate) mod search;
+pub(crate) mod set;
+pub(crate) mod token_core;
+pub(crate) mod token_printing;
@@ -0,0 +1,98 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use surrealdb::types::SurrealValue;
+
+use crate::Store;
+use crate::repos::cards::card_core::SurrealCardCoreId;
+use lotuslab_types::{
+    CardCoreId, CardRuling, CardRulingReadRepo, CardRulings, RepoError, RulingSource,
+};
+
+/// Rulings have no identity of their own, they're replaced per card whenever
+/// the catalog is updated.
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealCardRuling {
+    pub(crate) card_core: SurrealCardCoreId,
+    pub(crate) date: String,
+    pub(crate) text: String,
+    /// [RulingSource::as_str].
+    pub(crate) source: String,
+}
+
+impl TryFrom<SurrealCardRuling> for CardRuling {
+    type Error = RepoError;
+
+    fn try_from(value: SurrealCardRuling) -> Result<Self, RepoError> {
+        let source = RulingSource::parse(&value.source).ok_or_else(|| {
+            RepoError::InvalidInput(format!("unknown ruling source '{}'", value.source))
+        })?;
+
+        Ok(CardRuling {
+            date: value.date,
+            text: value.text,
+            source,
+        })
+    }
+}
+
+impl SurrealCardRuling {
+    pub(crate) fn from_rulings(rulings: CardRulings) -> Result<Vec<Self>, RepoError> {
+        let card_core = SurrealCardCoreId::try_from(rulings.card_core)?;
+
+        Ok(rulings
+            .rulings
+            .into_iter()
+            .map(|ruling| SurrealCardRuling {
+                card_core: card_core.clone(),
+                date: ruling.date,
+                text: ruling.text,
+                source: ruling.source.as_str().to_string(),
+            })
+            .collect())
+    }
+
+    /// Groups rulings by card, the inverse of [SurrealCardRuling::from_rulings].
+    pub(crate) fn group(rulings: Vec<Self>) -> Result<Vec<CardRulings>, RepoError> {
+        let mut by_card: HashMap<SurrealCardCoreId, Vec<CardRuling>> = HashMap::new();
+        for ruling in rulings {
+            by_card
+                .entry(ruling.card_core.clone())
+                .or_default()
+                .push(ruling.try_into()?);
+        }
+
+        Ok(by_card
+            .into_iter()
+            .map(|(card_core, rulings)| CardRulings {
+                card_core: card_core.into(),
+                rulings,
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl CardRulingReadRepo for Store {
+    async fn get_card_rulings(&self, id: &CardCoreId) -> Result<Vec<CardRuling>, RepoError> {
+        let surreal_id = SurrealCardCoreId::try_from(id.clone())?;
+        let rulings = self
+            .catalog
+            .query(
+                "SELECT card_core, date, text, source FROM card_ruling \
+                 WHERE card_core = $card ORDER BY date, text;",
+            )
+            .bind(("card", surreal_id.into_record()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealCardRuling>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        rulings.into_iter().map(CardRuling::try_from).collect()
+    }
+}
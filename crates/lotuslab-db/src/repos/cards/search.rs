@@ -0,0 +1,171 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use async_trait::async_trait;
+use surrealdb::types::RecordId;
+
+use crate::Store;
+use crate::repos::cards::card_core::{SurrealCardCore, SurrealCardCoreId};
+use lotuslab_types::{
+    CardCoreDetail, CardCoreId, CardFilter, CardQuery, CardSearchRepo, RepoError,
+};
+
+/// Compiles a query into a SurrealQL condition on `card_core`. User text
+/// never ends up in the condition itself, only as `$params[n]`.
+fn compile(query: &CardQuery, params: &mut Vec<String>) -> String {
+    match query {
+        CardQuery::And(queries) => join(queries, " AND ", params),
+        CardQuery::Or(queries) => join(queries, " OR ", params),
+        CardQuery::Not(query) => format!("!({})", compile(query, params)),
+        CardQuery::Filter(filter) => match filter {
+            CardFilter::Name(value) => format!(
+                "string::contains(string::lowercase(name), {})",
+                param(params, value.to_lowercase())
+            ),
+            CardFilter::Type(value) => {
+                let words: Vec<String> = value
+                    .to_lowercase()
+                    .split_whitespace()
+                    .map(|word| format!("{} IN types", param(params, word.to_string())))
+                    .collect();
+                format!("({})", words.join(" AND "))
+            }
+            CardFilter::Oracle(value) => format!(
+                "string::contains(string::lowercase(oracle_text ?? ''), {})",
+                param(params, value.to_lowercase())
+            ),
+            CardFilter::Keyword(value) => {
+                format!("{} IN keywords", param(params, value.to_lowercase()))
+            }
+            CardFilter::Produces(produces) => {
+                let mut symbols: Vec<String> = produces
+                    .colors
+                    .iter()
+                    .map(|color| color.symbol().to_ascii_lowercase().to_string())
+                    .collect();
+                if produces.colorless {
+                    symbols.push("c".into());
+                }
+                let symbols: Vec<String> = symbols
+                    .into_iter()
+                    .map(|symbol| format!("{} IN produces", param(params, symbol)))
+                    .collect();
+                format!("({})", symbols.join(" AND "))
+            }
+            CardFilter::ManaValue(comparison, value) => {
+                format!("mana_value {} {}", comparison.as_str(), value)
+            }
+            CardFilter::Ruling(value) => format!(
+                "id IN (SELECT VALUE card_core FROM card_ruling WHERE text @@ {})",
+                param(params, value.clone())
+            ),
+        },
+    }
+}
+
+fn param(params: &mut Vec<String>, value: String) -> String {
+    params.push(value);
+    format!("$params[{}]", params.len() - 1)
+}
+
+fn join(queries: &[CardQuery], separator: &str, params: &mut Vec<String>) -> String {
+    let parts: Vec<String> = queries.iter().map(|q| compile(q, params)).collect();
+    format!("({})", parts.join(separator))
+}
+
+#[async_trait]
+impl CardSearchRepo for Store {
+    async fn search_cards(
+        &self,
+        query: &CardQuery,
+        limit: u32,
+    ) -> Result<Vec<CardCoreDetail>, RepoError> {
+        let mut params = Vec::new();
+        let condition = compile(query, &mut params);
+
+        let cards = self
+            .catalog
+            .query(format!(
+                "SELECT * FROM card_core WHERE {} ORDER BY name LIMIT $limit;",
+                condition
+            ))
+            .bind(("params", params))
+            .bind(("limit", limit))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealCardCore>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(cards.into_iter().map(|c| c.into()).collect())
+    }
+
+    async fn filter_cards(
+        &self,
+        query: &CardQuery,
+        ids: &[CardCoreId],
+    ) -> Result<Vec<CardCoreId>, RepoError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids = ids
+            .iter()
+            .map(|id| Ok(SurrealCardCoreId::try_from(id.clone())?.into_record()))
+            .collect::<Result<Vec<RecordId>, RepoError>>()?;
+        let mut params = Vec::new();
+        let condition = compile(query, &mut params);
+
+        let matches = self
+            .catalog
+            .query(format!(
+                "SELECT VALUE id FROM card_core WHERE id IN $ids AND {};",
+                condition
+            ))
+            .bind(("ids", ids))
+            .bind(("params", params))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealCardCoreId>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(matches.into_iter().map(|id| id.into()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_card_query() {
+        let query = CardQuery::parse(r#"t:Creature -(o:"Draw a card" or rul:copy)"#)
+            .expect("query should parse");
+        let mut params = Vec::new();
+
+        assert_eq!(
+            compile(&query, &mut params),
+            "(($params[0] IN types) AND \
+             !((string::contains(string::lowercase(oracle_text ?? ''), $params[1]) OR \
+             id IN (SELECT VALUE card_core FROM card_ruling WHERE text @@ $params[2]))))"
+        );
+        assert_eq!(params, vec!["creature", "draw a card", "copy"]);
+
+        let query = CardQuery::parse(r#"t:"legendary elf" kw:Flying produces:gc"#)
+            .expect("query should parse");
+        let mut params = Vec::new();
+
+        assert_eq!(
+            compile(&query, &mut params),
+            "(($params[0] IN types AND $params[1] IN types) AND $params[2] IN keywords AND \
+             ($params[3] IN produces AND $params[4] IN produces))"
+        );
+        assert_eq!(params, vec!["legendary", "elf", "flying", "g", "c"]);
+
+        let query = CardQuery::parse("mv<=3 -mv:0").expect("query should parse");
+        assert_eq!(
+            compile(&query, &mut Vec::new()),
+            "(mana_value <= 3 AND !(mana_value = 0))"
+        );
+    }
+}
@@ -4,3 +4,18 @@
 
 pub(crate) mod cards;
 pub(crate) mod user;
+
+use surrealdb::types::{RecordId, RecordIdKey, ToSql};
+
+/// The `table:key` form of a record id handed out as a typed id.
+///
+/// [ToSql] escapes keys that aren't plain identifiers, e.g. the UUIDs the
+/// catalog is keyed by come out as `card_core:⟨…⟩`, which
+/// [RecordId::parse_simple] would read back as a different key. String keys
+/// are left as they are here so ids survive the round trip.
+pub(crate) fn record_id_string(id: &RecordId) -> String {
+    match &id.key {
+        RecordIdKey::String(key) => format!("{}:{}", id.table, key),
+        key => format!("{}:{}", id.table, key.to_sql()),
+    }
+}
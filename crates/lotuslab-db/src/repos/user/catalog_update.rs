@@ -0,0 +1,202 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, RecordIdKey, SurrealValue};
+
+use crate::Store;
+use crate::repos::record_id_string;
+use lotuslab_types::{
+    CatalogChangeReport, CatalogUpdate, CatalogUpdateId, CatalogUpdateRepo, CatalogUpdateSummary,
+    RepoError,
+};
+
+/// Fields for listing updates without loading their reports.
+const SUMMARY_FIELDS: &str = "id, applied_at, source_version, new_cards, oracle_errata, \
+     legality_changes, removed_printings";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, SurrealValue)]
+#[serde(transparent)]
+pub(crate) struct SurrealCatalogUpdateId(RecordId);
+
+impl SurrealCatalogUpdateId {
+    /// *Infallible* constructor for building an ID in code.
+    /// Panics if key is not kind `RecordIdKey::String`
+    pub(crate) fn from_key_unchecked(key: impl Into<RecordIdKey>) -> Self {
+        let key = key.into();
+        debug_assert!(matches!(key, RecordIdKey::String(_)));
+
+        SurrealCatalogUpdateId(RecordId::new("catalog_update", key))
+    }
+
+    /// *Fallible* constructor for parsing from a [RecordId]-ish at runtime.
+    /// Checks that the id has table type `catalog_update`.
+    pub(crate) fn from_record_id(id: impl Into<RecordId>) -> Result<Self, RepoError> {
+        let record_id = id.into();
+
+        match record_id.is_table_type(&["catalog_update".to_string()]) {
+            false => Err(RepoError::InvalidInput("not a catalog update id".into())),
+            true => Ok(SurrealCatalogUpdateId::from_key_unchecked(record_id.key)),
+        }
+    }
+
+    /// Convert to a [RecordId] for passing into db queries.
+    pub fn into_record(self) -> RecordId {
+        self.0
+    }
+}
+
+impl TryFrom<RecordId> for SurrealCatalogUpdateId {
+    type Error = RepoError;
+
+    fn try_from(id: RecordId) -> Result<Self, RepoError> {
+        SurrealCatalogUpdateId::from_record_id(id)
+    }
+}
+
+impl TryFrom<CatalogUpdateId> for SurrealCatalogUpdateId {
+    type Error = RepoError;
+
+    fn try_from(id: CatalogUpdateId) -> Result<Self, RepoError> {
+        let record_id = RecordId::parse_simple(id.as_ref())
+            .map_err(|e| RepoError::InvalidInput(e.to_string()))?;
+        SurrealCatalogUpdateId::try_from(record_id)
+    }
+}
+
+impl From<SurrealCatalogUpdateId> for CatalogUpdateId {
+    fn from(value: SurrealCatalogUpdateId) -> CatalogUpdateId {
+        CatalogUpdateId(record_id_string(&value.0))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealCatalogUpdateSummary {
+    pub(crate) id: SurrealCatalogUpdateId,
+    pub(crate) applied_at: DateTime<Utc>,
+    pub(crate) source_version: Option<String>,
+    pub(crate) new_cards: u32,
+    pub(crate) oracle_errata: u32,
+    pub(crate) legality_changes: u32,
+    pub(crate) removed_printings: u32,
+}
+
+impl From<SurrealCatalogUpdateSummary> for CatalogUpdateSummary {
+    fn from(value: SurrealCatalogUpdateSummary) -> CatalogUpdateSummary {
+        CatalogUpdateSummary {
+            id: value.id.into(),
+            applied_at: value.applied_at.to_rfc3339(),
+            source_version: value.source_version,
+            new_cards: value.new_cards,
+            oracle_errata: value.oracle_errata,
+            legality_changes: value.legality_changes,
+            removed_printings: value.removed_printings,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealCatalogUpdate {
+    pub(crate) id: SurrealCatalogUpdateId,
+    pub(crate) applied_at: DateTime<Utc>,
+    pub(crate) source_version: Option<String>,
+    pub(crate) new_cards: u32,
+    pub(crate) oracle_errata: u32,
+    pub(crate) legality_changes: u32,
+    pub(crate) removed_printings: u32,
+    /// The [CatalogChangeReport] as JSON. It's only ever read back whole, so
+    /// it isn't worth mirroring every nested type as a table field.
+    pub(crate) report: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealNewCatalogUpdate {
+    pub(crate) source_version: Option<String>,
+    pub(crate) new_cards: u32,
+    pub(crate) oracle_errata: u32,
+    pub(crate) legality_changes: u32,
+    pub(crate) removed_printings: u32,
+    pub(crate) report: String,
+}
+
+#[async_trait]
+impl CatalogUpdateRepo for Store {
+    async fn record_catalog_update(
+        &self,
+        source_version: Option<String>,
+        report: CatalogChangeReport,
+    ) -> Result<CatalogUpdateSummary, RepoError> {
+        let content = SurrealNewCatalogUpdate {
+            source_version,
+            new_cards: report.new_cards.len() as u32,
+            oracle_errata: report.oracle_errata.len() as u32,
+            legality_changes: report.legality_changes.len() as u32,
+            removed_printings: report.removed_printings.len() as u32,
+            report: serde_json::to_string(&report).map_err(|e| RepoError::DbError(e.into()))?,
+        };
+
+        let created = self
+            .db
+            .create::<Option<SurrealCatalogUpdate>>("catalog_update")
+            .content(content)
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::ToDo)?;
+
+        Ok(SurrealCatalogUpdateSummary {
+            id: created.id,
+            applied_at: created.applied_at,
+            source_version: created.source_version,
+            new_cards: created.new_cards,
+            oracle_errata: created.oracle_errata,
+            legality_changes: created.legality_changes,
+            removed_printings: created.removed_printings,
+        }
+        .into())
+    }
+
+    async fn list_catalog_updates(&self) -> Result<Vec<CatalogUpdateSummary>, RepoError> {
+        let updates = self
+            .db
+            .query(format!(
+                "SELECT {} FROM catalog_update ORDER BY applied_at DESC;",
+                SUMMARY_FIELDS
+            ))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealCatalogUpdateSummary>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(updates.into_iter().map(|u| u.into()).collect())
+    }
+
+    async fn get_catalog_update(&self, id: &CatalogUpdateId) -> Result<CatalogUpdate, RepoError> {
+        let surreal_id = SurrealCatalogUpdateId::try_from(id.clone())?;
+        let update = self
+            .db
+            .select::<Option<SurrealCatalogUpdate>>(surreal_id.into_record())
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        let report: CatalogChangeReport =
+            serde_json::from_str(&update.report).map_err(|e| RepoError::DbError(e.into()))?;
+        let summary = SurrealCatalogUpdateSummary {
+            id: update.id,
+            applied_at: update.applied_at,
+            source_version: update.source_version,
+            new_cards: update.new_cards,
+            oracle_errata: update.oracle_errata,
+            legality_changes: update.legality_changes,
+            removed_printings: update.removed_printings,
+        };
+
+        Ok(CatalogUpdate {
+            summary: summary.into(),
+            report,
+        })
+    }
+}
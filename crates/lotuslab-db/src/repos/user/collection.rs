@@ -0,0 +1,440 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, RecordIdKey, SurrealValue, ToSql};
+
+use crate::Store;
+use crate::repos::cards::{card_core::SurrealCardCoreId, card_printing::SurrealCardPrintingId};
+use crate::repos::record_id_string;
+use lotuslab_types::{
+    CardCondition, CardCoreId, CardPrintingId, CardPrintingReadRepo, CollectionEntry,
+    CollectionEntryId, CollectionReadRepo, CollectionWriteRepo, Finish, NewCollectionEntry, Patch,
+    RepoError, UpdateCollectionEntry,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, SurrealValue)]
+#[serde(transparent)]
+pub(crate) struct SurrealCollectionEntryId(RecordId);
+
+impl SurrealCollectionEntryId {
+    /// *Infallible* constructor for building an ID in code.
+    /// Panics if key is not kind `RecordIdKey::String`
+    pub(crate) fn from_key_unchecked(key: impl Into<RecordIdKey>) -> Self {
+        let key = key.into();
+        debug_assert!(matches!(key, RecordIdKey::String(_)));
+
+        SurrealCollectionEntryId(RecordId::new("collection_entry", key))
+    }
+
+    /// *Fallible* constructor for parsing from a [RecordId]-ish at runtime.
+    /// Checks that the id has table type `collection_entry`.
+    pub(crate) fn from_record_id(id: impl Into<RecordId>) -> Result<Self, RepoError> {
+        let record_id = id.into();
+
+        match record_id.is_table_type(&["collection_entry".to_string()]) {
+            false => Err(RepoError::InvalidInput("not a collection entry id".into())),
+            true => Ok(SurrealCollectionEntryId::from_key_unchecked(record_id.key)),
+        }
+    }
+
+    /// Convert to a [RecordId] for passing into db queries.
+    pub fn into_record(self) -> RecordId {
+        self.0
+    }
+
+    /// Convert to a string id of form `"collection_entry:id"`.
+    pub fn to_sql(&self) -> String {
+        self.0.to_sql()
+    }
+}
+
+impl TryFrom<RecordId> for SurrealCollectionEntryId {
+    type Error = RepoError;
+
+    fn try_from(id: RecordId) -> Result<Self, RepoError> {
+        SurrealCollectionEntryId::from_record_id(id)
+    }
+}
+
+impl TryFrom<CollectionEntryId> for SurrealCollectionEntryId {
+    type Error = RepoError;
+
+    fn try_from(id: CollectionEntryId) -> Result<Self, RepoError> {
+        let record_id = RecordId::parse_simple(id.as_ref())
+            .map_err(|e| RepoError::InvalidInput(e.to_string()))?;
+        SurrealCollectionEntryId::try_from(record_id)
+    }
+}
+
+impl From<SurrealCollectionEntryId> for CollectionEntryId {
+    fn from(value: SurrealCollectionEntryId) -> CollectionEntryId {
+        CollectionEntryId(record_id_string(&value.0))
+    }
+}
+
+/// `finish` and `condition` are stored as their `as_str` names, the schema
+/// only accepts known ones.
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealCollectionEntry {
+    pub(crate) id: SurrealCollectionEntryId,
+    pub(crate) card_core: SurrealCardCoreId,
+    pub(crate) card_printing: Option<SurrealCardPrintingId>,
+    pub(crate) quantity: i32,
+    pub(crate) finish: String,
+    pub(crate) condition: String,
+    pub(crate) language: String,
+    pub(crate) purchase_price: Option<u32>,
+    pub(crate) acquired_on: Option<String>,
+    pub(crate) location: Option<String>,
+}
+
+impl From<SurrealCollectionEntry> for CollectionEntry {
+    fn from(value: SurrealCollectionEntry) -> CollectionEntry {
+        CollectionEntry {
+            id: value.id.into(),
+            card_core_id: value.card_core.into(),
+            card_printing_id: value.card_printing.map(Into::into),
+            quantity: value.quantity,
+            finish: Finish::parse(&value.finish).unwrap_or_default(),
+            condition: CardCondition::parse(&value.condition).unwrap_or_default(),
+            language: value.language,
+            purchase_price: value.purchase_price,
+            acquired_on: value.acquired_on,
+            location: value.location,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealNewCollectionEntry {
+    pub(crate) card_core: SurrealCardCoreId,
+    pub(crate) card_printing: SurrealCardPrintingId,
+    pub(crate) quantity: i32,
+    pub(crate) finish: String,
+    pub(crate) condition: String,
+    pub(crate) language: String,
+    pub(crate) purchase_price: Option<u32>,
+    pub(crate) acquired_on: Option<String>,
+    pub(crate) location: Option<String>,
+}
+
+impl Store {
+    /// The printing and its card, checked against the catalog so entries
+    /// never point at printings that don't exist.
+    async fn collection_printing(
+        &self,
+        id: CardPrintingId,
+    ) -> Result<(SurrealCardCoreId, SurrealCardPrintingId), RepoError> {
+        let printing = match self.get_card_printing(&id).await {
+            Ok(printing) => printing,
+            Err(RepoError::NotFound) => {
+                return Err(RepoError::InvalidInput(format!("no printing {}", id)));
+            }
+            Err(e) => return Err(e),
+        };
+
+        Ok((printing.card_core.try_into()?, printing.id.try_into()?))
+    }
+}
+
+impl SurrealNewCollectionEntry {
+    fn checked(
+        card_core: SurrealCardCoreId,
+        card_printing: SurrealCardPrintingId,
+        new: NewCollectionEntry,
+    ) -> Result<Self, RepoError> {
+        Ok(SurrealNewCollectionEntry {
+            card_core,
+            card_printing,
+            quantity: checked_quantity(new.quantity.unwrap_or(1))?,
+            finish: new.finish.as_str().to_string(),
+            condition: new.condition.as_str().to_string(),
+            language: checked_language(new.language.unwrap_or_else(|| "en".to_string()))?,
+            purchase_price: new.purchase_price,
+            acquired_on: new.acquired_on.map(checked_acquired_on).transpose()?,
+            location: new.location,
+        })
+    }
+}
+
+fn checked_quantity(quantity: i32) -> Result<i32, RepoError> {
+    match quantity > 0 {
+        true => Ok(quantity),
+        false => Err(RepoError::InvalidInput("quantity must be positive".into())),
+    }
+}
+
+fn checked_language(language: String) -> Result<String, RepoError> {
+    let language = language.trim().to_ascii_lowercase();
+    match language.is_empty() {
+        true => Err(RepoError::InvalidInput("language can't be empty".into())),
+        false => Ok(language),
+    }
+}
+
+/// Dates are kept as `YYYY-MM-DD` strings, which also sort in date order.
+fn checked_acquired_on(date: String) -> Result<String, RepoError> {
+    let date = date.trim();
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .map_err(|_| RepoError::InvalidInput(format!("'{}' is not a YYYY-MM-DD date", date)))
+}
+
+#[async_trait]
+impl CollectionReadRepo for Store {
+    async fn get_collection_entry(
+        &self,
+        id: &CollectionEntryId,
+    ) -> Result<CollectionEntry, RepoError> {
+        let surreal_id = SurrealCollectionEntryId::try_from(id.clone())?;
+        let entry = self
+            .db
+            .select::<Option<SurrealCollectionEntry>>(surreal_id.into_record())
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(entry.into())
+    }
+
+    async fn list_collection_entries(&self) -> Result<Vec<CollectionEntry>, RepoError> {
+        let entries = self
+            .db
+            .query("SELECT * FROM collection_entry ORDER BY card_core, card_printing;")
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealCollectionEntry>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(entries.into_iter().map(|entry| entry.into()).collect())
+    }
+
+    async fn collection_entries_for_cards(
+        &self,
+        ids: &[CardCoreId],
+    ) -> Result<Vec<CollectionEntry>, RepoError> {
+        let cards = ids
+            .iter()
+            .map(|id| SurrealCardCoreId::try_from(id.clone()).map(|id| id.into_record()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let entries = self
+            .db
+            .query("SELECT * FROM collection_entry WHERE card_core IN $cards;")
+            .bind(("cards", cards))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealCollectionEntry>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(entries.into_iter().map(|entry| entry.into()).collect())
+    }
+}
+
+#[async_trait]
+impl CollectionWriteRepo for Store {
+    async fn create_collection_entry(
+        &self,
+        new: NewCollectionEntry,
+    ) -> Result<CollectionEntry, RepoError> {
+        let (card_core, card_printing) = self
+            .collection_printing(new.card_printing_id.clone())
+            .await?;
+        let content = SurrealNewCollectionEntry::checked(card_core, card_printing, new)?;
+
+        let created = self
+            .db
+            .create::<Option<SurrealCollectionEntry>>("collection_entry")
+            .content(content)
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::ToDo)?;
+
+        Ok(created.into())
+    }
+
+    async fn create_collection_entries(
+        &self,
+        new: Vec<NewCollectionEntry>,
+    ) -> Result<(), RepoError> {
+        if new.is_empty() {
+            return Ok(());
+        }
+
+        let ids = new
+            .iter()
+            .map(|entry| SurrealCardPrintingId::try_from(entry.card_printing_id.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let printings = self.card_printings_by_id(ids.clone()).await?;
+        let entries = new
+            .into_iter()
+            .zip(ids)
+            .map(|(entry, id)| match printings.get(&id) {
+                Some(printing) => {
+                    SurrealNewCollectionEntry::checked(printing.card_core.clone(), id, entry)
+                }
+                None => Err(RepoError::InvalidInput(format!(
+                    "no printing {}",
+                    entry.card_printing_id
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.db
+            .query(
+                "BEGIN; FOR $entry IN $entries { \
+                 CREATE collection_entry CONTENT $entry; }; COMMIT;",
+            )
+            .bind(("entries", entries))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .check()
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(())
+    }
+
+    async fn update_collection_entry(
+        &self,
+        id: &CollectionEntryId,
+        patch: UpdateCollectionEntry,
+    ) -> Result<CollectionEntry, RepoError> {
+        let mut content = Vec::new();
+
+        match patch.card_printing_id {
+            Patch::Set(printing) => {
+                let (card_core, card_printing) = self.collection_printing(printing).await?;
+                content.push(format!("card_core = {}", card_core.to_sql()));
+                content.push(format!("card_printing = {}", card_printing.to_sql()));
+            }
+            Patch::Clear => {
+                return Err(RepoError::InvalidInput(
+                    "collection entry needs a printing".into(),
+                ));
+            }
+            Patch::Ignore => {}
+        }
+
+        match patch.quantity {
+            Patch::Set(quantity) => {
+                content.push(format!("quantity = {}", checked_quantity(quantity)?))
+            }
+            Patch::Clear => {
+                return Err(RepoError::InvalidInput("quantity must be positive".into()));
+            }
+            Patch::Ignore => {}
+        }
+
+        match patch.finish {
+            Patch::Set(finish) => content.push(format!("finish = '{}'", finish.as_str())),
+            Patch::Clear => content.push(format!("finish = '{}'", Finish::default().as_str())),
+            Patch::Ignore => {}
+        }
+
+        match patch.condition {
+            Patch::Set(condition) => content.push(format!("condition = '{}'", condition.as_str())),
+            Patch::Clear => content.push(format!(
+                "condition = '{}'",
+                CardCondition::default().as_str()
+            )),
+            Patch::Ignore => {}
+        }
+
+        match patch.purchase_price {
+            Patch::Set(price) => content.push(format!("purchase_price = {}", price)),
+            Patch::Clear => content.push("purchase_price = NONE".to_string()),
+            Patch::Ignore => {}
+        }
+
+        let language = match patch.language {
+            Patch::Set(language) => {
+                content.push("language = $language".to_string());
+                Some(checked_language(language)?)
+            }
+            Patch::Clear => {
+                return Err(RepoError::InvalidInput("language can't be empty".into()));
+            }
+            Patch::Ignore => None,
+        };
+
+        let acquired_on = match patch.acquired_on {
+            Patch::Set(date) => {
+                content.push("acquired_on = $acquired_on".to_string());
+                Some(checked_acquired_on(date)?)
+            }
+            Patch::Clear => {
+                content.push("acquired_on = NONE".to_string());
+                None
+            }
+            Patch::Ignore => None,
+        };
+
+        // Free text is bound as a parameter rather than formatted into the query.
+        let location = match patch.location {
+            Patch::Set(location) => {
+                content.push("location = $location".to_string());
+                Some(location)
+            }
+            Patch::Clear => {
+                content.push("location = NONE".to_string());
+                None
+            }
+            Patch::Ignore => None,
+        };
+
+        if content.is_empty() {
+            return Err(RepoError::NoOp);
+        }
+
+        let surreal_id = SurrealCollectionEntryId::try_from(id.clone())?;
+        let query = format!("UPDATE {} SET {}", surreal_id.to_sql(), content.join(", "));
+        let updated = self
+            .db
+            .query(query)
+            .bind(("language", language))
+            .bind(("acquired_on", acquired_on))
+            .bind(("location", location))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Option<SurrealCollectionEntry>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(updated.into())
+    }
+
+    async fn delete_collection_entry(&self, id: &CollectionEntryId) -> Result<(), RepoError> {
+        let surreal_id = SurrealCollectionEntryId::try_from(id.clone())?;
+        self.db
+            .delete::<Option<SurrealCollectionEntry>>(surreal_id.into_record())
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_acquired_on() {
+        assert_eq!(
+            checked_acquired_on(" 2024-03-09 ".into()).ok(),
+            Some("2024-03-09".to_string())
+        );
+        assert!(matches!(
+            checked_acquired_on("2024-02-30".into()),
+            Err(RepoError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            checked_acquired_on("09/03/2024".into()),
+            Err(RepoError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,268 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, RecordIdKey, SurrealValue, ToSql};
+
+use crate::Store;
+use crate::repos::record_id_string;
+use lotuslab_types::{
+    CustomFormat, CustomFormatId, CustomFormatReadRepo, CustomFormatWriteRepo, FormatRules,
+    PointsTable, Rarity, RepoError,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, SurrealValue)]
+#[serde(transparent)]
+pub(crate) struct SurrealCustomFormatId(RecordId);
+
+impl SurrealCustomFormatId {
+    /// *Infallible* constructor for building an ID in code.
+    /// Panics if key is not kind `RecordIdKey::String`
+    pub(crate) fn from_key_unchecked(key: impl Into<RecordIdKey>) -> Self {
+        let key = key.into();
+        debug_assert!(matches!(key, RecordIdKey::String(_)));
+
+        SurrealCustomFormatId(RecordId::new("custom_format", key))
+    }
+
+    /// *Fallible* constructor for parsing from a [RecordId]-ish at runtime.
+    /// Checks that the id has table type `custom_format`.
+    pub(crate) fn from_record_id(id: impl Into<RecordId>) -> Result<Self, RepoError> {
+        let record_id = id.into();
+
+        match record_id.is_table_type(&["custom_format".to_string()]) {
+            false => Err(RepoError::InvalidInput("not a custom format id".into())),
+            true => Ok(SurrealCustomFormatId::from_key_unchecked(record_id.key)),
+        }
+    }
+
+    /// Convert to a [RecordId] for passing into db queries.
+    pub fn into_record(self) -> RecordId {
+        self.0
+    }
+
+    /// Convert to a string id of form `"custom_format:id"`.
+    pub fn to_sql(&self) -> String {
+        self.0.to_sql()
+    }
+}
+
+impl TryFrom<RecordId> for SurrealCustomFormatId {
+    type Error = RepoError;
+
+    fn try_from(id: RecordId) -> Result<Self, RepoError> {
+        SurrealCustomFormatId::from_record_id(id)
+    }
+}
+
+impl TryFrom<CustomFormatId> for SurrealCustomFormatId {
+    type Error = RepoError;
+
+    fn try_from(id: CustomFormatId) -> Result<Self, RepoError> {
+        let record_id = RecordId::parse_simple(id.as_ref())
+            .map_err(|e| RepoError::InvalidInput(e.to_string()))?;
+        SurrealCustomFormatId::try_from(record_id)
+    }
+}
+
+impl From<SurrealCustomFormatId> for CustomFormatId {
+    fn from(value: SurrealCustomFormatId) -> CustomFormatId {
+        CustomFormatId(record_id_string(&value.0))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealCustomFormat {
+    pub(crate) id: SurrealCustomFormatId,
+    pub(crate) name: String,
+    pub(crate) rules: SurrealFormatRules,
+}
+
+impl From<SurrealCustomFormat> for CustomFormat {
+    fn from(format: SurrealCustomFormat) -> Self {
+        CustomFormat {
+            id: format.id.into(),
+            rules: format.rules.into_rules(format.name),
+        }
+    }
+}
+
+/// [FormatRules] without the name, which is stored next to them.
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealFormatRules {
+    pub(crate) legality_key: Option<String>,
+    pub(crate) min_deck_size: Option<u32>,
+    pub(crate) max_deck_size: Option<u32>,
+    pub(crate) max_copies: u32,
+    pub(crate) max_sideboard_size: Option<u32>,
+    pub(crate) commander: bool,
+    pub(crate) banned: Vec<String>,
+    pub(crate) restricted: Vec<String>,
+    pub(crate) allowed_sets: Option<Vec<String>>,
+    pub(crate) allowed_rarities: Option<Vec<String>>,
+    pub(crate) points: Option<SurrealPointsTable>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealPointsTable {
+    pub(crate) cap: u32,
+    pub(crate) points: BTreeMap<String, u32>,
+}
+
+impl SurrealFormatRules {
+    fn into_rules(self, name: String) -> FormatRules {
+        FormatRules {
+            name,
+            legality_key: self.legality_key,
+            min_deck_size: self.min_deck_size,
+            max_deck_size: self.max_deck_size,
+            max_copies: self.max_copies,
+            max_sideboard_size: self.max_sideboard_size,
+            commander: self.commander,
+            banned: self.banned,
+            restricted: self.restricted,
+            allowed_sets: self.allowed_sets,
+            allowed_rarities: self.allowed_rarities.map(|rarities| {
+                rarities
+                    .iter()
+                    .filter_map(|rarity| Rarity::parse(rarity))
+                    .collect()
+            }),
+            points: self.points.map(|table| PointsTable {
+                cap: table.cap,
+                points: table.points,
+            }),
+        }
+    }
+}
+
+impl From<FormatRules> for SurrealFormatRules {
+    fn from(rules: FormatRules) -> Self {
+        SurrealFormatRules {
+            legality_key: rules.legality_key,
+            min_deck_size: rules.min_deck_size,
+            max_deck_size: rules.max_deck_size,
+            max_copies: rules.max_copies,
+            max_sideboard_size: rules.max_sideboard_size,
+            commander: rules.commander,
+            banned: rules.banned,
+            restricted: rules.restricted,
+            allowed_sets: rules.allowed_sets,
+            allowed_rarities: rules.allowed_rarities.map(|rarities| {
+                rarities
+                    .iter()
+                    .map(|rarity| rarity.as_str().to_string())
+                    .collect()
+            }),
+            points: rules.points.map(|table| SurrealPointsTable {
+                cap: table.cap,
+                points: table.points,
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealNewCustomFormat {
+    pub(crate) name: String,
+    pub(crate) rules: SurrealFormatRules,
+}
+
+impl TryFrom<FormatRules> for SurrealNewCustomFormat {
+    type Error = RepoError;
+
+    fn try_from(rules: FormatRules) -> Result<Self, RepoError> {
+        let name = rules.name.trim().to_string();
+        if name.is_empty() {
+            return Err(RepoError::InvalidInput(
+                "format name must not be empty".into(),
+            ));
+        }
+
+        Ok(SurrealNewCustomFormat {
+            name,
+            rules: rules.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl CustomFormatReadRepo for Store {
+    async fn get_custom_format(&self, id: &CustomFormatId) -> Result<CustomFormat, RepoError> {
+        let surreal_id = SurrealCustomFormatId::try_from(id.clone())?;
+        let format = self
+            .db
+            .select::<Option<SurrealCustomFormat>>(surreal_id.into_record())
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(format.into())
+    }
+
+    async fn list_custom_formats(&self) -> Result<Vec<CustomFormat>, RepoError> {
+        let formats = self
+            .db
+            .query("SELECT * FROM custom_format ORDER BY name;")
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealCustomFormat>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(formats.into_iter().map(CustomFormat::from).collect())
+    }
+}
+
+#[async_trait]
+impl CustomFormatWriteRepo for Store {
+    async fn create_custom_format(&self, rules: FormatRules) -> Result<CustomFormat, RepoError> {
+        let created = self
+            .db
+            .create::<Option<SurrealCustomFormat>>("custom_format")
+            .content(SurrealNewCustomFormat::try_from(rules)?)
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::ToDo)?;
+
+        Ok(created.into())
+    }
+
+    async fn update_custom_format(
+        &self,
+        id: &CustomFormatId,
+        rules: FormatRules,
+    ) -> Result<CustomFormat, RepoError> {
+        let surreal_id = SurrealCustomFormatId::try_from(id.clone())?;
+        let content = SurrealNewCustomFormat::try_from(rules)?;
+        let updated = self
+            .db
+            .query(format!(
+                "UPDATE {} SET name = $name, rules = $rules",
+                surreal_id.to_sql()
+            ))
+            .bind(("name", content.name))
+            .bind(("rules", content.rules))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Option<SurrealCustomFormat>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(updated.into())
+    }
+
+    async fn delete_custom_format(&self, id: &CustomFormatId) -> Result<(), RepoError> {
+        let surreal_id = SurrealCustomFormatId::try_from(id.clone())?;
+        self.db
+            .delete::<Option<SurrealCustomFormat>>(surreal_id.into_record())
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(())
+    }
+}
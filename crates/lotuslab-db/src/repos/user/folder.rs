@@ -5,6 +5,7 @@
 use serde::{Deserialize, Serialize};
 use surrealdb::types::{RecordId, RecordIdKey, SurrealValue, ToSql};
 
+use crate::repos::record_id_string;
 use crate::{Store, repos::user::project::SurrealProject};
 use lotuslab_types::{
     Folder, FolderChildren, FolderId, FolderReadRepo, FolderWriteRepo, NewFolder, Patch,
@@ -65,9 +66,9 @@ impl TryFrom<FolderId> for SurrealFolderId {
     }
 }
 
-impl Into<FolderId> for SurrealFolderId {
-    fn into(self) -> FolderId {
-        FolderId(self.to_sql())
+impl From<SurrealFolderId> for FolderId {
+    fn from(value: SurrealFolderId) -> FolderId {
+        FolderId(record_id_string(&value.0))
     }
 }
 
@@ -78,12 +79,12 @@ pub(crate) struct SurrealFolder {
     pub(crate) parent: Option<SurrealFolderId>,
 }
 
-impl Into<Folder> for SurrealFolder {
-    fn into(self) -> Folder {
+impl From<SurrealFolder> for Folder {
+    fn from(value: SurrealFolder) -> Folder {
         Folder {
-            id: self.id.into(),
-            name: self.name,
-            parent: self.parent.map(|p| p.into()),
+            id: value.id.into(),
+            name: value.name,
+            parent: value.parent.map(|p| p.into()),
         }
     }
 }
@@ -114,15 +115,15 @@ pub(crate) struct SurrealFolderChildren {
     pub(crate) projects: Vec<SurrealProject>,
 }
 
-impl Into<FolderChildren> for SurrealFolderChildren {
-    fn into(self) -> FolderChildren {
+impl From<SurrealFolderChildren> for FolderChildren {
+    fn from(value: SurrealFolderChildren) -> FolderChildren {
         FolderChildren {
-            folders: self
+            folders: value
                 .folders
                 .into_iter()
                 .map(|folder| folder.into())
                 .collect::<Vec<Folder>>(),
-            projects: self
+            projects: value
                 .projects
                 .into_iter()
                 .map(|project| project.into())
@@ -162,6 +163,18 @@ impl FolderReadRepo for Store {
 
         Ok(children.into())
     }
+
+    async fn list_folders(&self) -> Result<Vec<Folder>, RepoError> {
+        let folders = self
+            .db
+            .query("SELECT * FROM folder ORDER BY name;")
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealFolder>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(folders.into_iter().map(|folder| folder.into()).collect())
+    }
 }
 
 #[async_trait::async_trait]
@@ -9,9 +9,13 @@ use std::convert::TryFrom;
 use surrealdb::types::{RecordId, RecordIdKey, SurrealValue, ToSql};
 
 use crate::Store;
+use crate::repos::record_id_string;
 use crate::repos::user::project::SurrealProjectId;
 
-use lotuslab_types::{List, ListId, ListReadRepo, ListWriteRepo, NewList, PatchList, RepoError};
+use lotuslab_types::{
+    List, ListId, ListReadRepo, ListRole, ListWriteRepo, NewList, Patch, PatchList, ProjectId,
+    RepoError,
+};
 
 /// Strongly-typed SurrealDB RecordId for the `list` table
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, SurrealValue)]
@@ -66,25 +70,28 @@ impl TryFrom<ListId> for SurrealListId {
     }
 }
 
-impl Into<ListId> for SurrealListId {
-    fn into(self) -> ListId {
-        ListId(self.to_sql())
+impl From<SurrealListId> for ListId {
+    fn from(value: SurrealListId) -> ListId {
+        ListId(record_id_string(&value.0))
     }
 }
 
+/// `role` is stored as its `as_str` name, the schema only accepts those.
 #[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
 pub(crate) struct SurrealList {
     pub(crate) id: SurrealListId,
     pub(crate) name: String,
     pub(crate) project: SurrealProjectId,
+    pub(crate) role: String,
 }
 
-impl Into<List> for SurrealList {
-    fn into(self) -> List {
+impl From<SurrealList> for List {
+    fn from(value: SurrealList) -> List {
         List {
-            id: self.id.into(),
-            name: self.name,
-            project: self.project.into(),
+            id: value.id.into(),
+            name: value.name,
+            project: value.project.into(),
+            role: ListRole::parse(&value.role).unwrap_or_default(),
         }
     }
 }
@@ -93,6 +100,7 @@ impl Into<List> for SurrealList {
 pub(crate) struct SurrealNewList {
     pub(crate) name: String,
     pub(crate) project: SurrealProjectId,
+    pub(crate) role: String,
 }
 
 impl TryFrom<NewList> for SurrealNewList {
@@ -102,6 +110,7 @@ impl TryFrom<NewList> for SurrealNewList {
         Ok(SurrealNewList {
             name: new_list.name,
             project: new_list.project.try_into()?,
+            role: new_list.role.as_str().to_string(),
         })
     }
 }
@@ -119,6 +128,20 @@ impl ListReadRepo for Store {
 
         Ok(list.into())
     }
+
+    async fn lists_for_project(&self, project_id: &ProjectId) -> Result<Vec<List>, RepoError> {
+        let surreal_id = SurrealProjectId::try_from(project_id.clone())?;
+        let lists = self
+            .db
+            .query("SELECT * FROM list WHERE project = $project;")
+            .bind(("project", surreal_id.into_record()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealList>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(lists.into_iter().map(|list| list.into()).collect())
+    }
 }
 
 #[async_trait]
@@ -138,15 +161,21 @@ impl ListWriteRepo for Store {
     async fn update_list(&self, id: &ListId, patch: PatchList) -> Result<List, RepoError> {
         let mut content = Vec::new();
 
-        if let lotuslab_types::Patch::Set(name) = &patch.name {
+        if let Patch::Set(name) = &patch.name {
             content.push(format!("name = {}", name));
         }
 
-        if let lotuslab_types::Patch::Set(proj) = &patch.project {
+        if let Patch::Set(proj) = &patch.project {
             let surreal_id = SurrealProjectId::try_from(proj.clone())?;
             content.push(format!("project = {}", surreal_id.to_sql()));
         }
 
+        match patch.role {
+            Patch::Set(role) => content.push(format!("role = '{}'", role.as_str())),
+            Patch::Clear => content.push(format!("role = '{}'", ListRole::default().as_str())),
+            Patch::Ignore => {}
+        }
+
         if content.is_empty() {
             return Err(RepoError::NoOp);
         }
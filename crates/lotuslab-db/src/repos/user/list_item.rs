@@ -1,3 +1,557 @@
 // SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, RecordIdKey, SurrealValue, ToSql};
+
+use crate::Store;
+use crate::repos::cards::{
+    card_core::{SurrealCardCore, SurrealCardCoreId},
+    card_printing::{SurrealCardPrinting, SurrealCardPrintingId},
+};
+use crate::repos::record_id_string;
+use crate::repos::user::{
+    folder::SurrealFolderId, list::SurrealListId, project::SurrealProjectId, tag::SurrealTagId,
+};
+use lotuslab_types::{
+    CardCoreId, CardUsageRow, ListId, ListItemDetail, ListItemId, ListItemReadRepo,
+    ListItemSummary, ListItemWriteRepo, ListRole, NewListItem, Patch, RepoError, TagId,
+    UpdateListItem,
+};
+
+/// List items are `list_contains` edges from a `list` to a `card_core`. The
+/// edge's `in` and `out` are aliased to `list` and `card_core` when selected.
+pub(crate) const LIST_ITEM_FIELDS: &str = "id, in AS list, out AS card_core, selected_printing, \
+     quantity, notes, custom_image_uri, tags, auto_tags, dismissed_tags, created_at, updated_at";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, SurrealValue)]
+#[serde(transparent)]
+pub(crate) struct SurrealListItemId(RecordId);
+
+impl SurrealListItemId {
+    /// *Infallible* constructor for building an ID in code.
+    /// Panics if key is not kind `RecordIdKey::String`
+    pub(crate) fn from_key_unchecked(key: impl Into<RecordIdKey>) -> Self {
+        let key = key.into();
+        debug_assert!(matches!(key, RecordIdKey::String(_)));
+
+        SurrealListItemId(RecordId::new("list_contains", key))
+    }
+
+    /// *Fallible* constructor for parsing from a [RecordId]-ish at runtime.
+    /// Checks that the id has table type `list_contains`.
+    pub(crate) fn from_record_id(id: impl Into<RecordId>) -> Result<Self, RepoError> {
+        let record_id = id.into();
+
+        match record_id.is_table_type(&["list_contains".to_string()]) {
+            false => Err(RepoError::InvalidInput("not a list item id".into())),
+            true => Ok(SurrealListItemId::from_key_unchecked(record_id.key)),
+        }
+    }
+
+    /// Convert to a [RecordId] for passing into db queries.
+    pub fn into_record(self) -> RecordId {
+        self.0
+    }
+
+    /// Convert to a string id of form `"list_contains:id"`.
+    pub fn to_sql(&self) -> String {
+        self.0.to_sql()
+    }
+}
+
+impl TryFrom<RecordId> for SurrealListItemId {
+    type Error = RepoError;
+
+    fn try_from(id: RecordId) -> Result<Self, RepoError> {
+        SurrealListItemId::from_record_id(id)
+    }
+}
+
+impl TryFrom<ListItemId> for SurrealListItemId {
+    type Error = RepoError;
+
+    fn try_from(id: ListItemId) -> Result<Self, RepoError> {
+        let record_id = RecordId::parse_simple(id.as_ref())
+            .map_err(|e| RepoError::InvalidInput(e.to_string()))?;
+        SurrealListItemId::try_from(record_id)
+    }
+}
+
+impl From<SurrealListItemId> for ListItemId {
+    fn from(value: SurrealListItemId) -> ListItemId {
+        ListItemId(record_id_string(&value.0))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealListItem {
+    pub(crate) id: SurrealListItemId,
+    pub(crate) list: SurrealListId,
+    pub(crate) card_core: SurrealCardCoreId,
+    pub(crate) selected_printing: Option<SurrealCardPrintingId>,
+    pub(crate) quantity: i32,
+    pub(crate) notes: Option<String>,
+    pub(crate) custom_image_uri: Option<String>,
+    pub(crate) tags: Vec<SurrealTagId>,
+    pub(crate) auto_tags: Vec<SurrealTagId>,
+    pub(crate) dismissed_tags: Vec<SurrealTagId>,
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) updated_at: DateTime<Utc>,
+}
+
+impl From<SurrealListItem> for ListItemSummary {
+    fn from(value: SurrealListItem) -> ListItemSummary {
+        ListItemSummary {
+            id: value.id.into(),
+            list_id: value.list.into(),
+            card_core_id: value.card_core.into(),
+            selected_printing: value.selected_printing.map(|p| p.into()),
+            quantity: value.quantity,
+            notes: value.notes,
+            custom_image_uri: value.custom_image_uri,
+            tags: value.tags.into_iter().map(|t| t.into()).collect(),
+            auto_tags: value.auto_tags.into_iter().map(|t| t.into()).collect(),
+            dismissed_tags: value.dismissed_tags.into_iter().map(|t| t.into()).collect(),
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+impl SurrealListItem {
+    fn into_detail(
+        self,
+        card_core: SurrealCardCore,
+        card_printing: Option<SurrealCardPrinting>,
+    ) -> ListItemDetail {
+        ListItemDetail {
+            id: self.id.into(),
+            list_id: self.list.into(),
+            card_core_id: self.card_core.into(),
+            selected_printing: self.selected_printing.map(|p| p.into()),
+            quantity: self.quantity,
+            notes: self.notes,
+            custom_image_uri: self.custom_image_uri,
+            tags: self.tags.into_iter().map(|t| t.into()).collect(),
+            auto_tags: self.auto_tags.into_iter().map(|t| t.into()).collect(),
+            dismissed_tags: self.dismissed_tags.into_iter().map(|t| t.into()).collect(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            card_core_details: card_core.into(),
+            card_printing_details: card_printing.map(|p| p.into()),
+        }
+    }
+}
+
+/// A list item with the list and project it's in, fetched along the
+/// `list_contains` edge in one go.
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealCardUsageRow {
+    pub(crate) id: SurrealListItemId,
+    pub(crate) card_core: SurrealCardCoreId,
+    pub(crate) list: SurrealListId,
+    pub(crate) list_name: String,
+    pub(crate) list_role: String,
+    pub(crate) project: SurrealProjectId,
+    pub(crate) project_name: String,
+    pub(crate) folder: SurrealFolderId,
+    pub(crate) quantity: i32,
+    pub(crate) selected_printing: Option<SurrealCardPrintingId>,
+}
+
+/// A [NewListItem] with its ids checked, for creating items in bulk.
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+struct SurrealNewListItem {
+    list: RecordId,
+    card_core: RecordId,
+    selected_printing: Option<RecordId>,
+    quantity: i32,
+    notes: Option<String>,
+    tags: Vec<RecordId>,
+}
+
+impl TryFrom<NewListItem> for SurrealNewListItem {
+    type Error = RepoError;
+
+    fn try_from(new: NewListItem) -> Result<Self, RepoError> {
+        Ok(SurrealNewListItem {
+            list: SurrealListId::try_from(new.list_id)?.into_record(),
+            card_core: SurrealCardCoreId::try_from(new.card_core_id)?.into_record(),
+            selected_printing: new
+                .selected_printing
+                .map(|p| SurrealCardPrintingId::try_from(p).map(|p| p.into_record()))
+                .transpose()?,
+            quantity: new.quantity.unwrap_or(1),
+            notes: new.notes,
+            tags: new
+                .tags
+                .into_iter()
+                .map(|t| SurrealTagId::try_from(t).map(|t| t.into_record()))
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl Store {
+    /// Joins card details from the catalog onto list items. The two live in
+    /// separate databases, so this is two queries rather than a graph fetch.
+    ///
+    /// Items whose card is missing from the catalog, e.g. after a catalog
+    /// update dropped it, are left out with a warning rather than failing
+    /// the whole list.
+    pub(crate) async fn list_item_details(
+        &self,
+        items: Vec<SurrealListItem>,
+    ) -> Result<Vec<ListItemDetail>, RepoError> {
+        let cards = self
+            .card_cores_by_id(items.iter().map(|i| i.card_core.clone()).collect())
+            .await?;
+        let printings = self
+            .card_printings_by_id(
+                items
+                    .iter()
+                    .filter_map(|i| i.selected_printing.clone())
+                    .collect(),
+            )
+            .await?;
+
+        Ok(items
+            .into_iter()
+            .filter_map(|item| {
+                // Items can share a card, so the card is cloned, not removed.
+                let Some(card) = cards.get(&item.card_core).cloned() else {
+                    log::warn!(
+                        "{} is missing from the card catalog, skipping {}",
+                        item.card_core.to_sql(),
+                        item.id.to_sql()
+                    );
+                    return None;
+                };
+                let printing = item
+                    .selected_printing
+                    .as_ref()
+                    .and_then(|p| printings.get(p).cloned());
+                Some(item.into_detail(card, printing))
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ListItemReadRepo for Store {
+    async fn get_list_item_summary(&self, id: &ListItemId) -> Result<ListItemSummary, RepoError> {
+        let surreal_id = SurrealListItemId::try_from(id.clone())?;
+        let item = self
+            .db
+            .query(format!("SELECT {} FROM $id;", LIST_ITEM_FIELDS))
+            .bind(("id", surreal_id.into_record()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Option<SurrealListItem>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(item.into())
+    }
+
+    async fn get_list_item_detail(&self, id: &ListItemId) -> Result<ListItemDetail, RepoError> {
+        let surreal_id = SurrealListItemId::try_from(id.clone())?;
+        let item = self
+            .db
+            .query(format!("SELECT {} FROM $id;", LIST_ITEM_FIELDS))
+            .bind(("id", surreal_id.into_record()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Option<SurrealListItem>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        let card = item.card_core.to_sql();
+        self.list_item_details(vec![item])
+            .await?
+            .pop()
+            .ok_or_else(|| {
+                RepoError::InvalidInput(format!("{} is missing from the card catalog", card))
+            })
+    }
+
+    async fn list_items_for_list(
+        &self,
+        list_id: &ListId,
+    ) -> Result<Vec<ListItemSummary>, RepoError> {
+        let surreal_id = SurrealListId::try_from(list_id.clone())?;
+        let items = self
+            .db
+            .query(format!(
+                "SELECT {} FROM list_contains WHERE in = $list ORDER BY created_at;",
+                LIST_ITEM_FIELDS
+            ))
+            .bind(("list", surreal_id.into_record()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealListItem>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(items.into_iter().map(|item| item.into()).collect())
+    }
+
+    async fn list_item_details_for_list(
+        &self,
+        list_id: &ListId,
+    ) -> Result<Vec<ListItemDetail>, RepoError> {
+        let surreal_id = SurrealListId::try_from(list_id.clone())?;
+        let items = self
+            .db
+            .query(format!(
+                "SELECT {} FROM list_contains WHERE in = $list ORDER BY created_at;",
+                LIST_ITEM_FIELDS
+            ))
+            .bind(("list", surreal_id.into_record()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealListItem>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        self.list_item_details(items).await
+    }
+
+    async fn card_usage_rows(
+        &self,
+        card_core_ids: &[CardCoreId],
+    ) -> Result<Vec<CardUsageRow>, RepoError> {
+        let cards = card_core_ids
+            .iter()
+            .map(|id| SurrealCardCoreId::try_from(id.clone()).map(|id| id.into_record()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let rows = self
+            .db
+            .query(
+                "SELECT id, out AS card_core, in AS list, in.name AS list_name, \
+                 in.role AS list_role, in.project AS project, in.project.name AS project_name, \
+                 in.project.folder AS folder, quantity, selected_printing FROM list_contains \
+                 WHERE out IN $cards ORDER BY created_at;",
+            )
+            .bind(("cards", cards))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealCardUsageRow>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        let printings = self
+            .card_printings_by_id(
+                rows.iter()
+                    .filter_map(|r| r.selected_printing.clone())
+                    .collect(),
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| CardUsageRow {
+                printing: row
+                    .selected_printing
+                    .as_ref()
+                    .and_then(|p| printings.get(p).cloned())
+                    .map(|p| p.into()),
+                list_item_id: row.id.into(),
+                card_core_id: row.card_core.into(),
+                list_id: row.list.into(),
+                list_name: row.list_name,
+                list_role: ListRole::parse(&row.list_role).unwrap_or_default(),
+                project_id: row.project.into(),
+                project_name: row.project_name,
+                folder_id: row.folder.into(),
+                quantity: row.quantity,
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ListItemWriteRepo for Store {
+    async fn create_list_item(&self, new: NewListItem) -> Result<ListItemDetail, RepoError> {
+        let list = SurrealListId::try_from(new.list_id)?;
+        let card = SurrealCardCoreId::try_from(new.card_core_id)?;
+        let selected_printing = new
+            .selected_printing
+            .map(|p| SurrealCardPrintingId::try_from(p).map(|p| p.into_record()))
+            .transpose()?;
+        let tags = new
+            .tags
+            .into_iter()
+            .map(|t| SurrealTagId::try_from(t).map(|t| t.into_record()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let created = self
+            .db
+            .query(
+                "LET $item = RELATE ONLY $list->list_contains->$card SET \
+                 selected_printing = $selected_printing, quantity = $quantity, notes = $notes, \
+                 tags = $tags; \
+                 RETURN $item.id;",
+            )
+            .bind(("list", list.into_record()))
+            .bind(("card", card.into_record()))
+            .bind(("selected_printing", selected_printing))
+            .bind(("quantity", new.quantity.unwrap_or(1)))
+            .bind(("notes", new.notes))
+            .bind(("tags", tags))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Option<SurrealListItemId>>(1)
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::ToDo)?;
+
+        self.get_list_item_detail(&created.into()).await
+    }
+
+    async fn create_list_items(&self, new: Vec<NewListItem>) -> Result<(), RepoError> {
+        let items = new
+            .into_iter()
+            .map(SurrealNewListItem::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        self.db
+            .query(
+                "BEGIN; FOR $item IN $items { \
+                 LET $list = $item.list; LET $card = $item.card_core; \
+                 RELATE $list->list_contains->$card SET \
+                 selected_printing = $item.selected_printing, quantity = $item.quantity, \
+                 notes = $item.notes, tags = $item.tags; }; COMMIT;",
+            )
+            .bind(("items", items))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .check()
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(())
+    }
+
+    async fn update_list_item(
+        &self,
+        id: &ListItemId,
+        patch: UpdateListItem,
+    ) -> Result<ListItemDetail, RepoError> {
+        let mut content = Vec::new();
+
+        match &patch.selected_printing {
+            Patch::Set(printing) => {
+                let surreal_id = SurrealCardPrintingId::try_from(printing.clone())?;
+                content.push(format!("selected_printing = {}", surreal_id.to_sql()));
+            }
+            Patch::Clear => content.push("selected_printing = NONE".to_string()),
+            Patch::Ignore => {}
+        }
+
+        match &patch.quantity {
+            Patch::Set(quantity) if *quantity > 0 => {
+                content.push(format!("quantity = {}", quantity))
+            }
+            Patch::Set(_) | Patch::Clear => {
+                return Err(RepoError::InvalidInput("quantity must be positive".into()));
+            }
+            Patch::Ignore => {}
+        }
+
+        let tags = match &patch.tags {
+            Patch::Set(tags) => Some(
+                tags.iter()
+                    .map(|t| SurrealTagId::try_from(t.clone()).map(|t| t.to_sql()))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            Patch::Clear => Some(Vec::new()),
+            Patch::Ignore => None,
+        };
+        if let Some(tags) = tags {
+            // Dismissals are worked out from the old auto tags, so they go
+            // first.
+            let tags = format!("[{}]", tags.join(", "));
+            content.push(format!(
+                "dismissed_tags = array::complement(array::union(dismissed_tags, \
+                 array::complement(auto_tags, {0})), {0})",
+                tags
+            ));
+            content.push(format!("auto_tags = array::intersect(auto_tags, {})", tags));
+            content.push(format!("tags = {}", tags));
+        }
+
+        // Free text is bound as a parameter rather than formatted into the query.
+        let mut text = |field: &str, patch: Patch<String>| match patch {
+            Patch::Set(value) => {
+                content.push(format!("{0} = ${0}", field));
+                Some(value)
+            }
+            Patch::Clear => {
+                content.push(format!("{} = NONE", field));
+                None
+            }
+            Patch::Ignore => None,
+        };
+        let notes = text("notes", patch.notes);
+        let custom_image_uri = text("custom_image_uri", patch.custom_image_uri);
+
+        if content.is_empty() {
+            return Err(RepoError::NoOp);
+        }
+
+        let surreal_id = SurrealListItemId::try_from(id.clone())?;
+        let query = format!("UPDATE {} SET {}", surreal_id.to_sql(), content.join(", "));
+        self.db
+            .query(query)
+            .bind(("notes", notes))
+            .bind(("custom_image_uri", custom_image_uri))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .check()
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        self.get_list_item_detail(id).await
+    }
+
+    async fn set_auto_tags(&self, id: &ListItemId, auto_tags: Vec<TagId>) -> Result<(), RepoError> {
+        let surreal_id = SurrealListItemId::try_from(id.clone())?;
+        let auto_tags = auto_tags
+            .into_iter()
+            .map(|t| SurrealTagId::try_from(t).map(|t| t.into_record()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The old auto tags come off `tags` before the new ones go on.
+        self.db
+            .query(
+                "UPDATE $id SET tags = array::union(array::complement(tags, auto_tags), $auto), \
+                 auto_tags = $auto;",
+            )
+            .bind(("id", surreal_id.into_record()))
+            .bind(("auto", auto_tags))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .check()
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(())
+    }
+
+    async fn delete_list_item(&self, id: &ListItemId) -> Result<(), RepoError> {
+        // Check first, deleting a missing edge is not an error in SurrealQL.
+        self.get_list_item_summary(id).await?;
+
+        let surreal_id = SurrealListItemId::try_from(id.clone())?;
+        self.db
+            .query("DELETE $id;")
+            .bind(("id", surreal_id.into_record()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .check()
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(())
+    }
+}
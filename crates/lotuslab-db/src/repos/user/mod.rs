@@ -2,8 +2,12 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+pub(crate) mod catalog_update;
+pub(crate) mod collection;
+pub(crate) mod custom_format;
 pub(crate) mod folder;
 pub(crate) mod list;
 pub(crate) mod list_item;
 pub(crate) mod project;
 pub(crate) mod tag;
+pub(crate) mod tag_rule;
@@ -7,7 +7,11 @@ use serde::{Deserialize, Serialize};
 use std::convert::{TryFrom, TryInto};
 use surrealdb::types::{RecordId, RecordIdKey, SurrealValue, ToSql};
 
-use crate::{Store, repos::user::folder::SurrealFolderId};
+use crate::repos::record_id_string;
+use crate::{
+    Store,
+    repos::user::{folder::SurrealFolderId, list_item::SurrealListItemId},
+};
 use lotuslab_types::{
     NewProject, Patch, PatchProject, Project, ProjectId, ProjectReadRepo, ProjectWriteRepo,
     RepoError,
@@ -67,9 +71,9 @@ impl TryFrom<ProjectId> for SurrealProjectId {
     }
 }
 
-impl Into<ProjectId> for SurrealProjectId {
-    fn into(self) -> ProjectId {
-        ProjectId(self.to_sql())
+impl From<SurrealProjectId> for ProjectId {
+    fn from(value: SurrealProjectId) -> ProjectId {
+        ProjectId(record_id_string(&value.0))
     }
 }
 
@@ -78,14 +82,16 @@ pub(crate) struct SurrealProject {
     pub(crate) id: SurrealProjectId,
     pub(crate) name: String,
     pub(crate) folder: SurrealFolderId,
+    pub(crate) commanders: Vec<SurrealListItemId>,
 }
 
-impl Into<Project> for SurrealProject {
-    fn into(self) -> Project {
+impl From<SurrealProject> for Project {
+    fn from(value: SurrealProject) -> Project {
         Project {
-            id: self.id.into(),
-            name: self.name,
-            folder: self.folder.into(),
+            id: value.id.into(),
+            name: value.name,
+            folder: value.folder.into(),
+            commanders: value.commanders.into_iter().map(|c| c.into()).collect(),
         }
     }
 }
@@ -158,6 +164,18 @@ impl ProjectWriteRepo for Store {
             content.push(format!("folder = {}", id.to_sql()));
         }
 
+        match &patch.commanders {
+            Patch::Set(commanders) => {
+                let ids = commanders
+                    .iter()
+                    .map(|c| SurrealListItemId::try_from(c.clone()).map(|id| id.to_sql()))
+                    .collect::<Result<Vec<String>, RepoError>>()?;
+                content.push(format!("commanders = [{}]", ids.join(", ")));
+            }
+            Patch::Clear => content.push("commanders = []".to_string()),
+            Patch::Ignore => {}
+        }
+
         if content.is_empty() {
             return Err(RepoError::NoOp);
         }
@@ -1,3 +1,234 @@
 // SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, RecordIdKey, SurrealValue, ToSql};
+
+use crate::Store;
+use crate::repos::record_id_string;
+use lotuslab_types::{NewTag, Patch, RepoError, Tag, TagId, TagReadRepo, TagWriteRepo, UpdateTag};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, SurrealValue)]
+#[serde(transparent)]
+pub(crate) struct SurrealTagId(RecordId);
+
+impl SurrealTagId {
+    /// *Infallible* constructor for building an ID in code.
+    /// Panics if key is not kind `RecordIdKey::String`
+    pub(crate) fn from_key_unchecked(key: impl Into<RecordIdKey>) -> Self {
+        let key = key.into();
+        debug_assert!(matches!(key, RecordIdKey::String(_)));
+
+        SurrealTagId(RecordId::new("tag", key))
+    }
+
+    /// *Fallible* constructor for parsing from a [RecordId]-ish at runtime.
+    /// Checks that the id has table type `tag`.
+    pub(crate) fn from_record_id(id: impl Into<RecordId>) -> Result<Self, RepoError> {
+        let record_id = id.into();
+
+        match record_id.is_table_type(&["tag".to_string()]) {
+            false => Err(RepoError::InvalidInput("not a tag id".into())),
+            true => Ok(SurrealTagId::from_key_unchecked(record_id.key)),
+        }
+    }
+
+    /// Convert to a [RecordId] for passing into db queries.
+    pub fn into_record(self) -> RecordId {
+        self.0
+    }
+
+    /// Convert to a string id of form `"tag:id"`.
+    pub fn to_sql(&self) -> String {
+        self.0.to_sql()
+    }
+}
+
+impl TryFrom<RecordId> for SurrealTagId {
+    type Error = RepoError;
+
+    fn try_from(id: RecordId) -> Result<Self, RepoError> {
+        SurrealTagId::from_record_id(id)
+    }
+}
+
+impl TryFrom<TagId> for SurrealTagId {
+    type Error = RepoError;
+
+    fn try_from(id: TagId) -> Result<Self, RepoError> {
+        let record_id = RecordId::parse_simple(id.as_ref())
+            .map_err(|e| RepoError::InvalidInput(e.to_string()))?;
+        SurrealTagId::try_from(record_id)
+    }
+}
+
+impl From<SurrealTagId> for TagId {
+    fn from(value: SurrealTagId) -> TagId {
+        TagId(record_id_string(&value.0))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealTag {
+    pub(crate) id: SurrealTagId,
+    pub(crate) name: String,
+    pub(crate) color: Option<String>,
+}
+
+impl From<SurrealTag> for Tag {
+    fn from(value: SurrealTag) -> Tag {
+        Tag {
+            id: value.id.into(),
+            name: value.name,
+            color: value.color,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealNewTag {
+    pub(crate) name: String,
+    pub(crate) color: Option<String>,
+}
+
+impl From<NewTag> for SurrealNewTag {
+    fn from(t: NewTag) -> Self {
+        SurrealNewTag {
+            name: t.name,
+            color: t.color,
+        }
+    }
+}
+
+#[async_trait]
+impl TagReadRepo for Store {
+    async fn get_tag(&self, id: &TagId) -> Result<Tag, RepoError> {
+        let surreal_id = SurrealTagId::try_from(id.clone())?;
+        let tag = self
+            .db
+            .select::<Option<SurrealTag>>(surreal_id.into_record())
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(tag.into())
+    }
+
+    async fn find_tag_by_name(&self, name: &str) -> Result<Tag, RepoError> {
+        let tag = self
+            .db
+            .query("SELECT * FROM tag WHERE name = $name LIMIT 1;")
+            .bind(("name", name.to_string()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Option<SurrealTag>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(tag.into())
+    }
+
+    async fn list_tags(&self) -> Result<Vec<Tag>, RepoError> {
+        let tags = self
+            .db
+            .query("SELECT * FROM tag ORDER BY name;")
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealTag>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(tags.into_iter().map(|tag| tag.into()).collect())
+    }
+}
+
+#[async_trait]
+impl TagWriteRepo for Store {
+    async fn create_tag(&self, new: NewTag) -> Result<Tag, RepoError> {
+        if new.name.trim().is_empty() {
+            return Err(RepoError::InvalidInput("tag name must not be empty".into()));
+        }
+
+        let created = self
+            .db
+            .create::<Option<SurrealTag>>("tag")
+            .content(SurrealNewTag::from(new))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::ToDo)?;
+
+        Ok(created.into())
+    }
+
+    async fn update_tag(&self, id: &TagId, patch: UpdateTag) -> Result<Tag, RepoError> {
+        let mut content = Vec::new();
+
+        let name = match patch.name {
+            Patch::Set(name) if !name.trim().is_empty() => {
+                content.push("name = $name");
+                Some(name)
+            }
+            Patch::Set(_) | Patch::Clear => {
+                return Err(RepoError::InvalidInput("tag name must not be empty".into()));
+            }
+            Patch::Ignore => None,
+        };
+
+        let color = match patch.color {
+            Patch::Set(color) => {
+                content.push("color = $color");
+                Some(color)
+            }
+            Patch::Clear => {
+                content.push("color = NONE");
+                None
+            }
+            Patch::Ignore => None,
+        };
+
+        if content.is_empty() {
+            return Err(RepoError::NoOp);
+        }
+
+        let surreal_id = SurrealTagId::try_from(id.clone())?;
+        let query = format!("UPDATE {} SET {}", surreal_id.to_sql(), content.join(", "));
+        let updated = self
+            .db
+            .query(query)
+            .bind(("name", name))
+            .bind(("color", color))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Option<SurrealTag>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(updated.into())
+    }
+
+    async fn delete_tag(&self, id: &TagId) -> Result<(), RepoError> {
+        let surreal_id = SurrealTagId::try_from(id.clone())?;
+        self.db
+            .delete::<Option<SurrealTag>>(surreal_id.clone().into_record())
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        // Tags are plain record links on list items and rules, so drop the
+        // dangling ones.
+        self.db
+            .query(
+                "UPDATE list_contains SET tags -= $tag, auto_tags -= $tag, \
+                 dismissed_tags -= $tag WHERE tags CONTAINS $tag OR dismissed_tags CONTAINS $tag; \
+                 DELETE tag_rule WHERE tag = $tag;",
+            )
+            .bind(("tag", surreal_id.into_record()))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .check()
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(())
+    }
+}
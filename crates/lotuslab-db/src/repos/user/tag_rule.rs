@@ -0,0 +1,224 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, RecordIdKey, SurrealValue, ToSql};
+
+use crate::Store;
+use crate::repos::record_id_string;
+use crate::repos::user::tag::SurrealTagId;
+use lotuslab_types::{
+    CardQuery, NewTagRule, Patch, RepoError, TagRule, TagRuleId, TagRuleReadRepo, TagRuleWriteRepo,
+    UpdateTagRule,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, SurrealValue)]
+#[serde(transparent)]
+pub(crate) struct SurrealTagRuleId(RecordId);
+
+impl SurrealTagRuleId {
+    /// *Infallible* constructor for building an ID in code.
+    /// Panics if key is not kind `RecordIdKey::String`
+    pub(crate) fn from_key_unchecked(key: impl Into<RecordIdKey>) -> Self {
+        let key = key.into();
+        debug_assert!(matches!(key, RecordIdKey::String(_)));
+
+        SurrealTagRuleId(RecordId::new("tag_rule", key))
+    }
+
+    /// *Fallible* constructor for parsing from a [RecordId]-ish at runtime.
+    /// Checks that the id has table type `tag_rule`.
+    pub(crate) fn from_record_id(id: impl Into<RecordId>) -> Result<Self, RepoError> {
+        let record_id = id.into();
+
+        match record_id.is_table_type(&["tag_rule".to_string()]) {
+            false => Err(RepoError::InvalidInput("not a tag rule id".into())),
+            true => Ok(SurrealTagRuleId::from_key_unchecked(record_id.key)),
+        }
+    }
+
+    /// Convert to a [RecordId] for passing into db queries.
+    pub fn into_record(self) -> RecordId {
+        self.0
+    }
+
+    /// Convert to a string id of form `"tag_rule:id"`.
+    pub fn to_sql(&self) -> String {
+        self.0.to_sql()
+    }
+}
+
+impl TryFrom<RecordId> for SurrealTagRuleId {
+    type Error = RepoError;
+
+    fn try_from(id: RecordId) -> Result<Self, RepoError> {
+        SurrealTagRuleId::from_record_id(id)
+    }
+}
+
+impl TryFrom<TagRuleId> for SurrealTagRuleId {
+    type Error = RepoError;
+
+    fn try_from(id: TagRuleId) -> Result<Self, RepoError> {
+        let record_id = RecordId::parse_simple(id.as_ref())
+            .map_err(|e| RepoError::InvalidInput(e.to_string()))?;
+        SurrealTagRuleId::try_from(record_id)
+    }
+}
+
+impl From<SurrealTagRuleId> for TagRuleId {
+    fn from(value: SurrealTagRuleId) -> TagRuleId {
+        TagRuleId(record_id_string(&value.0))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealTagRule {
+    pub(crate) id: SurrealTagRuleId,
+    pub(crate) query: String,
+    pub(crate) tag: SurrealTagId,
+    pub(crate) enabled: bool,
+}
+
+impl From<SurrealTagRule> for TagRule {
+    fn from(value: SurrealTagRule) -> TagRule {
+        TagRule {
+            id: value.id.into(),
+            query: value.query,
+            tag: value.tag.into(),
+            enabled: value.enabled,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, SurrealValue)]
+pub(crate) struct SurrealNewTagRule {
+    pub(crate) query: String,
+    pub(crate) tag: SurrealTagId,
+}
+
+impl TryFrom<NewTagRule> for SurrealNewTagRule {
+    type Error = RepoError;
+
+    fn try_from(rule: NewTagRule) -> Result<Self, RepoError> {
+        Ok(SurrealNewTagRule {
+            query: checked_query(rule.query)?,
+            tag: rule.tag.try_into()?,
+        })
+    }
+}
+
+/// Rules are stored as typed, but only once they parse.
+fn checked_query(query: String) -> Result<String, RepoError> {
+    CardQuery::parse(&query).map_err(|e| RepoError::InvalidInput(e.to_string()))?;
+    Ok(query)
+}
+
+#[async_trait]
+impl TagRuleReadRepo for Store {
+    async fn get_tag_rule(&self, id: &TagRuleId) -> Result<TagRule, RepoError> {
+        let surreal_id = SurrealTagRuleId::try_from(id.clone())?;
+        let rule = self
+            .db
+            .select::<Option<SurrealTagRule>>(surreal_id.into_record())
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(rule.into())
+    }
+
+    async fn list_tag_rules(&self) -> Result<Vec<TagRule>, RepoError> {
+        let rules = self
+            .db
+            .query("SELECT * FROM tag_rule ORDER BY tag, query;")
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Vec<SurrealTagRule>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        Ok(rules.into_iter().map(|rule| rule.into()).collect())
+    }
+}
+
+#[async_trait]
+impl TagRuleWriteRepo for Store {
+    async fn create_tag_rule(&self, new: NewTagRule) -> Result<TagRule, RepoError> {
+        let created = self
+            .db
+            .create::<Option<SurrealTagRule>>("tag_rule")
+            .content(SurrealNewTagRule::try_from(new)?)
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::ToDo)?;
+
+        Ok(created.into())
+    }
+
+    async fn update_tag_rule(
+        &self,
+        id: &TagRuleId,
+        patch: UpdateTagRule,
+    ) -> Result<TagRule, RepoError> {
+        let mut content = Vec::new();
+
+        let query = match patch.query {
+            Patch::Set(query) => {
+                content.push("query = $query".to_string());
+                Some(checked_query(query)?)
+            }
+            Patch::Clear => {
+                return Err(RepoError::InvalidInput("tag rule needs a query".into()));
+            }
+            Patch::Ignore => None,
+        };
+
+        match patch.tag {
+            Patch::Set(tag) => {
+                let tag = SurrealTagId::try_from(tag)?;
+                content.push(format!("tag = {}", tag.to_sql()));
+            }
+            Patch::Clear => return Err(RepoError::InvalidInput("tag rule needs a tag".into())),
+            Patch::Ignore => {}
+        }
+
+        match patch.enabled {
+            Patch::Set(enabled) => content.push(format!("enabled = {}", enabled)),
+            Patch::Clear => {
+                return Err(RepoError::InvalidInput("enabled can't be cleared".into()));
+            }
+            Patch::Ignore => {}
+        }
+
+        if content.is_empty() {
+            return Err(RepoError::NoOp);
+        }
+
+        let surreal_id = SurrealTagRuleId::try_from(id.clone())?;
+        let query_text = format!("UPDATE {} SET {}", surreal_id.to_sql(), content.join(", "));
+        let updated = self
+            .db
+            .query(query_text)
+            .bind(("query", query))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .take::<Option<SurrealTagRule>>(0)
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(updated.into())
+    }
+
+    async fn delete_tag_rule(&self, id: &TagRuleId) -> Result<(), RepoError> {
+        let surreal_id = SurrealTagRuleId::try_from(id.clone())?;
+        self.db
+            .delete::<Option<SurrealTagRule>>(surreal_id.into_record())
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .ok_or(RepoError::NotFound)?;
+
+        Ok(())
+    }
+}
@@ -8,8 +8,17 @@
 //! This abstraction provides two storage backends:
 //! - In-memory storage for testing and development
 //! - RocksDB storage for production persistence
+//!
+//! Both backends hold two datastores: `db` for the user's own data and
+//! `catalog` for card data. The catalog can be dropped and imported again
+//! without touching user data, which only refers to cards by their ids. They
+//! are separate datastores rather than two databases of one, since every
+//! clone of a connection shares the same session and with it the selected
+//! database.
 
 use std::path::Path;
+
+use lotuslab_types::RepoError;
 use surrealdb::{
     Surreal,
     engine::local::{Db, RocksDb},
@@ -20,15 +29,29 @@ use surrealdb::{
     },
 };
 
+use crate::CATALOG_SCHEMA;
+
+pub(crate) const NAMESPACE: &str = "lotuslab";
+pub(crate) const USER_DB: &str = "db";
+pub(crate) const CATALOG_DB: &str = "catalog";
+
 pub struct Store {
+    /// Connection to the user database.
     pub(crate) db: Surreal<Db>,
+    /// Connection to the card catalog database.
+    pub(crate) catalog: Surreal<Db>,
 }
 
 impl Store {
     /// Creates an in-memory SurrealDB instance for testing. Do not use in production code.
     #[cfg(test)]
     pub(crate) async fn new_mem() -> surrealdb::Result<Self> {
-        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
+        let config = Config::default().capabilities(
+            Capabilities::all()
+                .with_experimental_features_allowed(&[ExperimentalFeature::RecordReferences]),
+        );
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(config.clone()).await?;
+        let catalog = Surreal::new::<surrealdb::engine::local::Mem>(config).await?;
 
         let _version = db.version().await;
         debug_assert!(
@@ -36,16 +59,23 @@ impl Store {
             "Database connection should be valid after initialization"
         );
 
-        Ok(Self { db })
+        Self::with_databases(db, catalog).await
     }
 
     /// Creates a RocksDB-backed SurrealDB instance for production use.
     ///
     /// The path must be a valid directory path where RocksDB files will be stored.
-    /// The directory will be created if it doesn't exist.
+    /// The directory will be created if it doesn't exist. The catalog is kept
+    /// next to it, in the same path with a `.catalog` extension.
     pub(crate) async fn new_rocksdb(path: impl AsRef<Path>) -> surrealdb::Result<Self> {
-        let path_ref = path.as_ref();
+        let path = path.as_ref();
+        let db = Self::open_rocksdb(path).await?;
+        let catalog = Self::open_rocksdb(&path.with_extension("catalog")).await?;
+
+        Self::with_databases(db, catalog).await
+    }
 
+    async fn open_rocksdb(path_ref: &Path) -> surrealdb::Result<Surreal<Db>> {
         debug_assert!(
             !path_ref.as_os_str().is_empty(),
             "RocksDB path must not be empty"
@@ -76,9 +106,36 @@ impl Store {
         })
         .await?;
 
-        db.use_ns("lotuslab").use_db("db").await?;
+        Ok(db)
+    }
+
+    async fn with_databases(db: Surreal<Db>, catalog: Surreal<Db>) -> surrealdb::Result<Self> {
+        db.use_ns(NAMESPACE).use_db(USER_DB).await?;
+        catalog.use_ns(NAMESPACE).use_db(CATALOG_DB).await?;
+
+        Ok(Self { db, catalog })
+    }
 
-        return Ok(Self { db });
+    /// Drops all card data so the catalog can be imported again from scratch.
+    /// User data is left alone.
+    pub async fn reset_catalog(&self) -> Result<(), RepoError> {
+        self.catalog
+            .query(format!("REMOVE DATABASE IF EXISTS {};", CATALOG_DB))
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?
+            .check()
+            .map_err(|e| RepoError::DbError(e.into()))?;
+
+        self.catalog
+            .use_ns(NAMESPACE)
+            .use_db(CATALOG_DB)
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?;
+        self.catalog
+            .query(CATALOG_SCHEMA)
+            .await
+            .map_err(|e| RepoError::DbError(e.into()))?;
+        self.migrate_catalog().await
     }
 }
 
@@ -106,6 +163,10 @@ mod tests {
             db_path.exists(),
             "RocksDB should create files at the specified path"
         );
+        assert!(
+            db_path.with_extension("catalog").exists(),
+            "RocksDB should create the catalog next to the user database"
+        );
 
         drop(store);
         temp_dir
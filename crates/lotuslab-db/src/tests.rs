@@ -1,3 +1,562 @@
 // SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Repo tests against an in-memory database with the real schema.
+
+#![cfg(test)]
+
+use lotuslab_types::{
+    BackupScope, CardCoreDetail, CardCoreReadRepo, CardPrintingDetail, CardRuling, CardRulings,
+    CatalogChanges, CatalogReadRepo, CatalogWriteRepo, CollectionReadRepo, CollectionWriteRepo,
+    CustomFormatReadRepo, CustomFormatWriteRepo, FormatRules, ListId, ListItemReadRepo,
+    ListItemWriteRepo, ListReadRepo, ListRole, ListWriteRepo, NewCollectionEntry, NewList,
+    NewListItem, NewProject, PointsTable, PrintingRemap, ProjectWriteRepo, Rarity, RepoError,
+    RulingSource,
+};
+
+use crate::{CATALOG_SCHEMA, SCHEMA, SCHEMA_VERSION, Store};
+
+pub(crate) async fn test_store() -> Store {
+    let store = Store::new_mem().await.expect("store should open");
+    store
+        .db
+        .query(SCHEMA)
+        .await
+        .expect("schema should apply")
+        .check()
+        .expect("schema should apply");
+    store
+        .catalog
+        .query(CATALOG_SCHEMA)
+        .await
+        .expect("catalog schema should apply")
+        .check()
+        .expect("catalog schema should apply");
+    store
+}
+
+async fn test_list(store: &Store) -> ListId {
+    let project = store
+        .create_project(NewProject {
+            name: "Test".into(),
+            folder: None,
+        })
+        .await
+        .expect("project should be created");
+    store
+        .create_list(NewList {
+            name: "Main".into(),
+            project: project.id,
+            role: ListRole::Main,
+        })
+        .await
+        .expect("list should be created")
+        .id
+}
+
+fn card(key: &str, name: &str) -> CardCoreDetail {
+    CardCoreDetail {
+        id: format!("card_core:{}", key).into(),
+        name: name.into(),
+        mana_cost: None,
+        back_mana_costs: Vec::new(),
+        color_indicator: Vec::new(),
+        type_line: "Artifact".into(),
+        oracle_text: None,
+        legalities: Default::default(),
+    }
+}
+
+fn printing(key: &str, card: &str, collector_number: &str) -> CardPrintingDetail {
+    CardPrintingDetail {
+        id: format!("card_printing:{}", key).into(),
+        card_core: format!("card_core:{}", card).into(),
+        set_code: "C21".into(),
+        collector_number: collector_number.into(),
+        scryfall_id: None,
+        rarity: None,
+        price_usd: None,
+    }
+}
+
+fn new_item(list_id: &ListId, card: &str, quantity: i32) -> NewListItem {
+    NewListItem {
+        list_id: list_id.clone(),
+        card_core_id: format!("card_core:{}", card).into(),
+        selected_printing: None,
+        quantity: Some(quantity),
+        notes: None,
+        tags: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn test_create_list_items_is_all_or_nothing() {
+    let store = test_store().await;
+    let list_id = test_list(&store).await;
+
+    store
+        .create_list_items(vec![
+            new_item(&list_id, "sol_ring", 1),
+            new_item(&list_id, "forest", 30),
+        ])
+        .await
+        .expect("items should be created");
+    assert_eq!(store.list_items_for_list(&list_id).await.unwrap().len(), 2);
+
+    let err = store
+        .create_list_items(vec![
+            new_item(&list_id, "island", 30),
+            new_item(&list_id, "swamp", 0),
+        ])
+        .await;
+    assert!(err.is_err());
+    assert_eq!(store.list_items_for_list(&list_id).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_list_item_details_skip_cards_missing_from_catalog() {
+    let store = test_store().await;
+    let list_id = test_list(&store).await;
+    store
+        .apply_catalog_changes(CatalogChanges {
+            upsert_cards: vec![card("sol_ring", "Sol Ring")],
+            ..CatalogChanges::default()
+        })
+        .await
+        .expect("catalog should update");
+    store
+        .create_list_items(vec![
+            new_item(&list_id, "sol_ring", 1),
+            new_item(&list_id, "gone", 1),
+        ])
+        .await
+        .expect("items should be created");
+
+    let details = store.list_item_details_for_list(&list_id).await.unwrap();
+    assert_eq!(details.len(), 1);
+    assert_eq!(details[0].card_core_details.name, "Sol Ring");
+}
+
+#[tokio::test]
+async fn test_catalog_ids_round_trip_with_uuid_keys() {
+    let store = test_store().await;
+    let card = card("a8b1f7a4-0b31-5c6f-9b0f-1f2d6a4c3e21", "Sol Ring");
+    let printing = printing(
+        "3d1e6c2b-9f4a-4e8b-a1c7-5b2d8e0f6a93",
+        "a8b1f7a4-0b31-5c6f-9b0f-1f2d6a4c3e21",
+        "263",
+    );
+    let rulings = CardRulings {
+        card_core: card.id.clone(),
+        rulings: vec![CardRuling {
+            date: "2021-04-16".into(),
+            text: "Colorless.".into(),
+            source: RulingSource::Wotc,
+        }],
+    };
+    store
+        .apply_catalog_changes(CatalogChanges {
+            upsert_cards: vec![card.clone()],
+            upsert_printings: vec![printing.clone()],
+            replace_rulings: vec![rulings.clone()],
+            ..CatalogChanges::default()
+        })
+        .await
+        .expect("catalog should update");
+
+    let snapshot = store.catalog_snapshot().await.unwrap();
+    assert_eq!(snapshot.cards, vec![card.clone()]);
+    assert_eq!(snapshot.printings, vec![printing]);
+    assert_eq!(snapshot.rulings, Some(vec![rulings]));
+    assert_eq!(store.get_card_core(&card.id).await.unwrap(), card);
+}
+
+#[tokio::test]
+async fn test_apply_catalog_changes_remaps_printing_references() {
+    let store = test_store().await;
+    let list_id = test_list(&store).await;
+    store
+        .apply_catalog_changes(CatalogChanges {
+            upsert_cards: vec![card("sol_ring", "Sol Ring")],
+            upsert_printings: vec![printing("old", "sol_ring", "263")],
+            ..CatalogChanges::default()
+        })
+        .await
+        .expect("catalog should update");
+    let mut item = new_item(&list_id, "sol_ring", 1);
+    item.selected_printing = Some("card_printing:old".into());
+    store
+        .create_list_items(vec![item])
+        .await
+        .expect("item should be created");
+    let entry = store
+        .create_collection_entry(NewCollectionEntry {
+            card_printing_id: "card_printing:old".into(),
+            quantity: None,
+            finish: Default::default(),
+            condition: Default::default(),
+            language: None,
+            purchase_price: None,
+            acquired_on: None,
+            location: None,
+        })
+        .await
+        .expect("entry should be created");
+
+    store
+        .apply_catalog_changes(CatalogChanges {
+            upsert_printings: vec![printing("new", "sol_ring", "263")],
+            delete_printings: vec!["card_printing:old".into()],
+            remap_printings: vec![PrintingRemap {
+                from: "card_printing:old".into(),
+                to: "card_printing:new".into(),
+            }],
+            ..CatalogChanges::default()
+        })
+        .await
+        .expect("catalog should update");
+
+    let items = store.list_items_for_list(&list_id).await.unwrap();
+    assert_eq!(items[0].selected_printing, Some("card_printing:new".into()));
+    let entry = store.get_collection_entry(&entry.id).await.unwrap();
+    assert_eq!(entry.card_printing_id, Some("card_printing:new".into()));
+}
+
+#[tokio::test]
+async fn test_apply_catalog_changes_clears_deleted_printing_references() {
+    let store = test_store().await;
+    let list_id = test_list(&store).await;
+    store
+        .apply_catalog_changes(CatalogChanges {
+            upsert_cards: vec![card("sol_ring", "Sol Ring")],
+            upsert_printings: vec![printing("old", "sol_ring", "263")],
+            ..CatalogChanges::default()
+        })
+        .await
+        .expect("catalog should update");
+    let mut item = new_item(&list_id, "sol_ring", 1);
+    item.selected_printing = Some("card_printing:old".into());
+    store
+        .create_list_items(vec![item])
+        .await
+        .expect("item should be created");
+    let entry = store
+        .create_collection_entry(NewCollectionEntry {
+            card_printing_id: "card_printing:old".into(),
+            quantity: None,
+            finish: Default::default(),
+            condition: Default::default(),
+            language: None,
+            purchase_price: None,
+            acquired_on: None,
+            location: None,
+        })
+        .await
+        .expect("entry should be created");
+
+    store
+        .apply_catalog_changes(CatalogChanges {
+            delete_printings: vec!["card_printing:old".into()],
+            ..CatalogChanges::default()
+        })
+        .await
+        .expect("catalog should update");
+
+    let items = store.list_items_for_list(&list_id).await.unwrap();
+    assert_eq!(items[0].selected_printing, None);
+    let entry = store.get_collection_entry(&entry.id).await.unwrap();
+    assert_eq!(entry.card_printing_id, None);
+    assert_eq!(entry.card_core_id, "card_core:sol_ring".into());
+}
+
+#[tokio::test]
+async fn test_create_collection_entries_is_all_or_nothing() {
+    let store = test_store().await;
+    store
+        .apply_catalog_changes(CatalogChanges {
+            upsert_cards: vec![card("sol_ring", "Sol Ring")],
+            upsert_printings: vec![printing("c21", "sol_ring", "263")],
+            ..CatalogChanges::default()
+        })
+        .await
+        .expect("catalog should update");
+    let entry = |printing: &str, quantity: i32| NewCollectionEntry {
+        card_printing_id: format!("card_printing:{}", printing).into(),
+        quantity: Some(quantity),
+        finish: Default::default(),
+        condition: Default::default(),
+        language: Some(" JA ".into()),
+        purchase_price: None,
+        acquired_on: None,
+        location: None,
+    };
+
+    store
+        .create_collection_entries(vec![entry("c21", 2), entry("c21", 1)])
+        .await
+        .expect("entries should be created");
+    let entries = store.list_collection_entries().await.unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].card_core_id, "card_core:sol_ring".into());
+    assert_eq!(entries[0].language, "ja");
+
+    let missing = store
+        .create_collection_entries(vec![entry("c21", 1), entry("gone", 1)])
+        .await;
+    assert!(matches!(missing, Err(RepoError::InvalidInput(_))));
+    let invalid = store
+        .create_collection_entries(vec![entry("c21", 1), entry("c21", 0)])
+        .await;
+    assert!(matches!(invalid, Err(RepoError::InvalidInput(_))));
+    assert_eq!(store.list_collection_entries().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_migrate_sets_list_roles_from_names() {
+    let store = test_store().await;
+    // lists from before roles existed
+    store
+        .db
+        .query(
+            "REMOVE FIELD role ON list; \
+             CREATE project:deck SET name = 'Deck', folder = folder:root; \
+             CREATE list:main SET name = 'Main', project = project:deck; \
+             CREATE list:side SET name = ' sideboard ', project = project:deck; \
+             CREATE list:maybe SET name = 'Maybeboard', project = project:deck;",
+        )
+        .await
+        .expect("old lists should be created")
+        .check()
+        .expect("old lists should be created");
+    store.db.query(SCHEMA).await.expect("schema should apply");
+
+    store.migrate().await.expect("migration should run");
+    // a second run has nothing left to do
+    store.migrate().await.expect("migration should run");
+
+    let mut roles: Vec<(String, ListRole)> = store
+        .lists_for_project(&"project:deck".into())
+        .await
+        .expect("lists should load")
+        .into_iter()
+        .map(|list| (list.name, list.role))
+        .collect();
+    roles.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        roles,
+        vec![
+            (" sideboard ".to_string(), ListRole::Sideboard),
+            ("Main".to_string(), ListRole::Main),
+            ("Maybeboard".to_string(), ListRole::Maybeboard),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_fills_auto_tags() {
+    let store = test_store().await;
+    // list items from before tag rules
+    store
+        .db
+        .query(
+            "REMOVE FIELD auto_tags ON list_contains; \
+             REMOVE FIELD dismissed_tags ON list_contains; \
+             CREATE project:deck SET name = 'Deck', folder = folder:root; \
+             CREATE list:main SET name = 'Main', project = project:deck; \
+             RELATE list:main->list_contains->card_core:sol_ring SET quantity = 1;",
+        )
+        .await
+        .expect("old items should be created")
+        .check()
+        .expect("old items should be created");
+    store.db.query(SCHEMA).await.expect("schema should apply");
+
+    store.migrate().await.expect("migration should run");
+
+    let missing: Option<i64> = store
+        .db
+        .query("SELECT VALUE count() FROM list_contains WHERE auto_tags IS NONE GROUP ALL;")
+        .await
+        .expect("items should load")
+        .take(0)
+        .expect("items should load");
+    assert_eq!(missing, None);
+    assert_eq!(
+        store
+            .list_items_for_list(&"list:main".into())
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_reset_catalog_keeps_user_data() {
+    let store = test_store().await;
+    let list_id = test_list(&store).await;
+    store
+        .apply_catalog_changes(CatalogChanges {
+            upsert_cards: vec![card("sol_ring", "Sol Ring")],
+            ..CatalogChanges::default()
+        })
+        .await
+        .expect("catalog should update");
+    store
+        .create_list_item(new_item(&list_id, "sol_ring", 1))
+        .await
+        .expect("item should be created");
+
+    store.reset_catalog().await.expect("catalog should reset");
+
+    assert!(store.catalog_snapshot().await.unwrap().cards.is_empty());
+    assert_eq!(store.list_items_for_list(&list_id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_restore_brings_back_backed_up_data() {
+    let store = test_store().await;
+    let list_id = test_list(&store).await;
+    store
+        .apply_catalog_changes(CatalogChanges {
+            upsert_cards: vec![card("sol_ring", "Sol Ring")],
+            ..CatalogChanges::default()
+        })
+        .await
+        .expect("catalog should update");
+    let item = store
+        .create_list_item(new_item(&list_id, "sol_ring", 1))
+        .await
+        .expect("item should be created");
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let path = dir.path().join("backup.surql");
+    store
+        .backup(&path, BackupScope::UserAndCards)
+        .await
+        .expect("backup should be written");
+
+    store
+        .apply_catalog_changes(CatalogChanges {
+            upsert_cards: vec![card("forest", "Forest")],
+            delete_cards: vec!["card_core:sol_ring".into()],
+            ..CatalogChanges::default()
+        })
+        .await
+        .expect("catalog should update");
+    store
+        .delete_list_item(&item.id)
+        .await
+        .expect("item should be deleted");
+    store
+        .create_list_item(new_item(&list_id, "forest", 30))
+        .await
+        .expect("item should be created");
+
+    let header = store.restore(&path).await.expect("backup should restore");
+
+    assert_eq!(header.scope, BackupScope::UserAndCards);
+    let items = store.list_items_for_list(&list_id).await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].id, item.id);
+    assert_eq!(items[0].quantity, 1);
+    let cards = store.catalog_snapshot().await.unwrap().cards;
+    assert_eq!(cards, vec![card("sol_ring", "Sol Ring")]);
+}
+
+/// Sets the schema version in the header of the backup at `path`.
+fn set_backup_version(path: &std::path::Path, version: u32) {
+    let backup = std::fs::read_to_string(path).expect("backup should read");
+    let backup = backup.replacen(
+        &format!("schema_version={}", SCHEMA_VERSION),
+        &format!("schema_version={}", version),
+        1,
+    );
+    std::fs::write(path, backup).expect("backup should write");
+}
+
+#[tokio::test]
+async fn test_restore_migrates_older_backups() {
+    let store = test_store().await;
+    // a list from before roles existed
+    store
+        .db
+        .query(
+            "REMOVE FIELD role ON list; \
+             CREATE project:deck SET name = 'Deck', folder = folder:root; \
+             CREATE list:side SET name = 'Sideboard', project = project:deck;",
+        )
+        .await
+        .expect("old list should be created")
+        .check()
+        .expect("old list should be created");
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let path = dir.path().join("backup.surql");
+    store
+        .backup(&path, BackupScope::User)
+        .await
+        .expect("backup should be written");
+    set_backup_version(&path, 8);
+
+    store.restore(&path).await.expect("backup should restore");
+
+    let list = store.get_list(&"list:side".into()).await.unwrap();
+    assert_eq!(list.role, ListRole::Sideboard);
+}
+
+#[tokio::test]
+async fn test_restore_rejects_newer_backups() {
+    let store = test_store().await;
+    let list_id = test_list(&store).await;
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let path = dir.path().join("backup.surql");
+    store
+        .backup(&path, BackupScope::User)
+        .await
+        .expect("backup should be written");
+    set_backup_version(&path, SCHEMA_VERSION + 1);
+    store
+        .delete_list(&list_id)
+        .await
+        .expect("list should be deleted");
+
+    let err = store.restore(&path).await;
+
+    assert!(matches!(err, Err(RepoError::InvalidInput(_))));
+    assert!(store.get_list(&list_id).await.is_err());
+}
+
+fn house_rules(name: &str) -> FormatRules {
+    FormatRules {
+        name: name.into(),
+        min_deck_size: Some(60),
+        max_copies: 4,
+        banned: vec!["Sol Ring".into()],
+        allowed_rarities: Some(vec![Rarity::Common, Rarity::Uncommon]),
+        points: Some(PointsTable {
+            cap: 10,
+            points: [("Black Lotus".to_string(), 7)].into(),
+        }),
+        ..FormatRules::default()
+    }
+}
+
+#[tokio::test]
+async fn test_custom_format_names_are_unique_ignoring_case() {
+    let store = test_store().await;
+    let created = store
+        .create_custom_format(house_rules("Pauper Cube"))
+        .await
+        .expect("format should be created");
+    assert_eq!(created.rules, house_rules("Pauper Cube"));
+    assert_eq!(
+        store.get_custom_format(&created.id).await.unwrap().rules,
+        house_rules("Pauper Cube")
+    );
+
+    let duplicate = store
+        .create_custom_format(house_rules(" pauper cube"))
+        .await;
+    assert!(matches!(duplicate, Err(RepoError::DbError(_))));
+}
@@ -0,0 +1,13 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ExternalError {
+    #[error("malformed json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("bulk file has one printing per card, use default_cards instead of oracle_cards")]
+    OracleCardsBulk,
+}
@@ -2,4 +2,8 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+mod error;
 pub mod mtgjson;
+pub mod scryfall;
+
+pub use crate::error::*;
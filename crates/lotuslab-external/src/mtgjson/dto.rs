@@ -1,3 +1,103 @@
 // SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Serde mirrors of the MTGJSON v5 file models. Only the fields LotusLab
+//! reads are declared, everything else is skipped while deserializing.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+/// Every MTGJSON file wraps its payload as `{ "meta": ..., "data": ... }`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct MtgjsonFile<T> {
+    #[serde(default)]
+    pub(crate) meta: Option<Meta>,
+    pub(crate) data: T,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Meta {
+    pub(crate) version: String,
+}
+
+/// An entry of `DeckList.json`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeckListEntry {
+    pub(crate) code: String,
+    pub(crate) file_name: String,
+    pub(crate) name: String,
+    pub(crate) release_date: Option<String>,
+    #[serde(rename = "type")]
+    pub(crate) deck_type: String,
+}
+
+/// A single deck file from `AllDeckFiles`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Deck {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) commander: Vec<CardDeck>,
+    #[serde(default)]
+    pub(crate) main_board: Vec<CardDeck>,
+    #[serde(default)]
+    pub(crate) side_board: Vec<CardDeck>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CardDeck {
+    pub(crate) count: i32,
+    pub(crate) name: String,
+    pub(crate) set_code: String,
+    pub(crate) number: String,
+}
+
+/// A set from `AllPrintings.json`, which maps set codes to these.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Set {
+    pub(crate) code: String,
+    #[serde(default)]
+    pub(crate) cards: Vec<CardSet>,
+}
+
+/// A card face as printed in a set. Multi-faced cards have one of these per
+/// face, told apart by `side`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CardSet {
+    pub(crate) uuid: String,
+    pub(crate) name: String,
+    pub(crate) mana_cost: Option<String>,
+    #[serde(rename = "type")]
+    pub(crate) type_line: String,
+    pub(crate) text: Option<String>,
+    /// Color symbols like `"G"`, missing on faces without an indicator.
+    #[serde(default)]
+    pub(crate) color_indicator: Vec<String>,
+    pub(crate) number: String,
+    pub(crate) side: Option<String>,
+    pub(crate) rarity: Option<String>,
+    #[serde(default)]
+    pub(crate) legalities: BTreeMap<String, String>,
+    #[serde(default)]
+    pub(crate) rulings: Vec<Ruling>,
+    pub(crate) identifiers: Identifiers,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Ruling {
+    pub(crate) date: String,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Identifiers {
+    pub(crate) scryfall_id: Option<String>,
+    pub(crate) scryfall_oracle_id: Option<String>,
+}
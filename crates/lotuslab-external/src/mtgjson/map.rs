@@ -1,3 +1,356 @@
 // SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
+
+use lotuslab_types::{
+    CardCoreDetail, CardPrintingDetail, CardRuling, CardRulings, CatalogData, Color, Decklist,
+    DecklistEntry, DecklistSection, Legality, PreconSummary, Rarity, RulingSource,
+};
+
+use super::dto::{CardDeck, CardSet, Deck, DeckListEntry, MtgjsonFile, Set};
+use crate::ExternalError;
+
+/// Card data read from `AllPrintings.json`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MtgjsonCatalog {
+    /// MTGJSON's build version, e.g. `"5.2.2+20250101"`.
+    pub version: Option<String>,
+    pub catalog: CatalogData,
+}
+
+/// Maps `DeckList.json`, the index of every preconstructed deck.
+pub fn map_deck_list(json: &str) -> Result<Vec<PreconSummary>, ExternalError> {
+    let file: MtgjsonFile<Vec<DeckListEntry>> = serde_json::from_str(json)?;
+
+    Ok(file
+        .data
+        .into_iter()
+        .map(|entry| PreconSummary {
+            set_code: entry.code,
+            file_name: entry.file_name,
+            name: entry.name,
+            release_date: entry.release_date,
+            deck_type: entry.deck_type,
+        })
+        .collect())
+}
+
+/// Maps a deck file, keeping the exact printing of every card.
+pub fn map_deck(json: &str) -> Result<Decklist, ExternalError> {
+    let file: MtgjsonFile<Deck> = serde_json::from_str(json)?;
+    let deck = file.data;
+
+    let entry = |section: DecklistSection| {
+        move |card: CardDeck| DecklistEntry {
+            section,
+            quantity: card.count,
+            name: card.name,
+            set_code: Some(card.set_code),
+            collector_number: Some(card.number),
+            art_index: None,
+        }
+    };
+
+    let entries = deck
+        .commander
+        .into_iter()
+        .map(entry(DecklistSection::Commander))
+        .chain(
+            deck.main_board
+                .into_iter()
+                .map(entry(DecklistSection::Main)),
+        )
+        .chain(
+            deck.side_board
+                .into_iter()
+                .map(entry(DecklistSection::Sideboard)),
+        )
+        .collect();
+
+    Ok(Decklist {
+        name: Some(deck.name),
+        entries,
+    })
+}
+
+/// Maps `AllPrintings.json` into catalog cards and printings.
+///
+/// Cards are keyed by Scryfall oracle id and printings by Scryfall id, the
+/// same as the Scryfall import, so the same card and printing get the same
+/// ids on every import from either source. Printings Scryfall doesn't know
+/// fall back to the MTGJSON uuid of their front face.
+/// Faces without an oracle id are skipped. Rulings come from Gatherer, so
+/// they're all marked as official.
+pub fn map_all_printings(reader: impl Read) -> Result<MtgjsonCatalog, ExternalError> {
+    let file: MtgjsonFile<BTreeMap<String, Set>> = serde_json::from_reader(reader)?;
+
+    let mut catalog = CatalogData::default();
+    // Some printings only carry the front face (e.g. promos of modal double
+    // faced cards), so each card keeps the printing with the most faces.
+    let mut cards: BTreeMap<String, (usize, CardCoreDetail, Vec<CardRuling>)> = BTreeMap::new();
+
+    for set in file.data.into_values() {
+        // Group the faces of each physical card.
+        let mut printed: BTreeMap<(String, String), Vec<CardSet>> = BTreeMap::new();
+        for face in set.cards {
+            let Some(oracle_id) = face.identifiers.scryfall_oracle_id.clone() else {
+                continue;
+            };
+            printed
+                .entry((oracle_id, face.number.clone()))
+                .or_default()
+                .push(face);
+        }
+
+        for ((oracle_id, number), mut faces) in printed {
+            faces.sort_by(|a, b| a.side.cmp(&b.side));
+            let front = &faces[0];
+            let card_core = format!("card_core:{}", oracle_id);
+
+            let printing_key = front
+                .identifiers
+                .scryfall_id
+                .as_ref()
+                .unwrap_or(&front.uuid);
+            catalog.printings.push(CardPrintingDetail {
+                id: format!("card_printing:{}", printing_key).into(),
+                card_core: card_core.clone().into(),
+                set_code: set.code.to_ascii_uppercase(),
+                collector_number: number,
+                scryfall_id: front.identifiers.scryfall_id.clone(),
+                rarity: front.rarity.as_deref().and_then(Rarity::parse),
+                // MTGJSON ships prices in a separate file.
+                price_usd: None,
+            });
+
+            if cards
+                .get(&oracle_id)
+                .is_none_or(|(n, _, _)| faces.len() > *n)
+            {
+                let rulings = map_rulings(&faces[0]);
+                cards.insert(
+                    oracle_id,
+                    (faces.len(), map_card_core(card_core, &faces), rulings),
+                );
+            }
+        }
+    }
+    let mut rulings = Vec::new();
+    for (_, card, card_rulings) in cards.into_values() {
+        if !card_rulings.is_empty() {
+            rulings.push(CardRulings {
+                card_core: card.id.clone(),
+                rulings: card_rulings,
+            });
+        }
+        catalog.cards.push(card);
+    }
+    catalog.rulings = Some(rulings);
+
+    Ok(MtgjsonCatalog {
+        version: file.meta.map(|m| m.version),
+        catalog,
+    })
+}
+
+fn map_rulings(face: &CardSet) -> Vec<CardRuling> {
+    face.rulings
+        .iter()
+        .map(|ruling| CardRuling {
+            date: ruling.date.clone(),
+            text: ruling.text.clone(),
+            source: RulingSource::Wotc,
+        })
+        .collect()
+}
+
+fn map_card_core(id: String, faces: &[CardSet]) -> CardCoreDetail {
+    let front = &faces[0];
+    let texts: Vec<&str> = faces.iter().filter_map(|f| f.text.as_deref()).collect();
+    let color_indicator: BTreeSet<Color> = faces
+        .iter()
+        .flat_map(|f| &f.color_indicator)
+        .filter_map(|symbol| symbol.chars().next().and_then(Color::from_symbol))
+        .collect();
+
+    CardCoreDetail {
+        id: id.into(),
+        name: front.name.clone(),
+        mana_cost: front.mana_cost.clone(),
+        back_mana_costs: faces[1..]
+            .iter()
+            .filter_map(|f| f.mana_cost.clone())
+            .collect(),
+        color_indicator: color_indicator.into_iter().collect(),
+        type_line: faces
+            .iter()
+            .map(|f| f.type_line.as_str())
+            .collect::<Vec<_>>()
+            .join(" // "),
+        oracle_text: match texts.is_empty() {
+            true => None,
+            false => Some(texts.join("\n//\n")),
+        },
+        legalities: front
+            .legalities
+            .iter()
+            .filter_map(|(format, legality)| {
+                let legality = match legality.as_str() {
+                    "Legal" => Legality::Legal,
+                    "Restricted" => Legality::Restricted,
+                    "Banned" => Legality::Banned,
+                    _ => return None,
+                };
+                Some((format.clone(), legality))
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_deck() {
+        let json = r#"{
+            "meta": { "date": "2025-01-01", "version": "5.2.2" },
+            "data": {
+                "code": "C16",
+                "name": "Breed Lethality",
+                "releaseDate": "2016-11-11",
+                "type": "Commander Deck",
+                "commander": [
+                    { "count": 1, "name": "Atraxa, Praetors' Voice", "setCode": "C16", "number": "28", "uuid": "a" }
+                ],
+                "mainBoard": [
+                    { "count": 1, "name": "Sol Ring", "setCode": "C16", "number": "272", "uuid": "b" },
+                    { "count": 6, "name": "Forest", "setCode": "C16", "number": "349", "uuid": "c" }
+                ],
+                "sideBoard": []
+            }
+        }"#;
+
+        let deck = map_deck(json).expect("deck should map");
+
+        assert_eq!(deck.name.as_deref(), Some("Breed Lethality"));
+        assert_eq!(deck.entries.len(), 3);
+        assert_eq!(deck.entries[0].section, DecklistSection::Commander);
+        assert_eq!(deck.entries[2].quantity, 6);
+        assert_eq!(deck.entries[2].collector_number.as_deref(), Some("349"));
+    }
+
+    #[test]
+    fn test_map_all_printings() {
+        let json = r#"{
+            "meta": { "date": "2025-01-01", "version": "5.2.2+20250101" },
+            "data": {
+                "ZNR": {
+                    "code": "ZNR",
+                    "cards": [
+                        {
+                            "uuid": "f1", "name": "Agadeem's Awakening // Agadeem, the Undercrypt",
+                            "manaCost": "{X}{B}{B}{B}", "type": "Sorcery", "text": "Return creatures.",
+                            "number": "90", "side": "a", "rarity": "uncommon",
+                            "legalities": { "commander": "Legal", "standard": "Not Legal" },
+                            "rulings": [{ "date": "2020-09-25", "text": "X can be 0." }],
+                            "identifiers": { "scryfallId": "s1", "scryfallOracleId": "o1" }
+                        },
+                        {
+                            "uuid": "f2", "name": "Agadeem's Awakening // Agadeem, the Undercrypt",
+                            "type": "Land", "text": "Agadeem enters tapped.",
+                            "number": "90", "side": "b",
+                            "legalities": { "commander": "Legal" },
+                            "identifiers": { "scryfallId": "s1", "scryfallOracleId": "o1" }
+                        }
+                    ]
+                },
+                "ZNE": {
+                    "code": "ZNE",
+                    "cards": [
+                        {
+                            "uuid": "p1", "name": "Agadeem's Awakening // Agadeem, the Undercrypt",
+                            "manaCost": "{X}{B}{B}{B}", "type": "Sorcery", "text": "Return creatures.",
+                            "number": "1", "side": "a",
+                            "identifiers": { "scryfallOracleId": "o1" }
+                        }
+                    ]
+                }
+            }
+        }"#;
+
+        let mtgjson = map_all_printings(json.as_bytes()).expect("printings should map");
+
+        assert_eq!(mtgjson.version.as_deref(), Some("5.2.2+20250101"));
+        assert_eq!(mtgjson.catalog.cards.len(), 1);
+        assert_eq!(mtgjson.catalog.printings.len(), 2);
+
+        let card = &mtgjson.catalog.cards[0];
+        assert_eq!(card.id.as_ref(), "card_core:o1");
+        assert_eq!(card.type_line, "Sorcery // Land");
+        assert!(card.back_mana_costs.is_empty());
+        assert_eq!(
+            card.oracle_text.as_deref(),
+            Some("Return creatures.\n//\nAgadeem enters tapped.")
+        );
+        assert_eq!(card.legalities.get("commander"), Some(&Legality::Legal));
+        assert!(!card.legalities.contains_key("standard"));
+
+        let rulings = mtgjson.catalog.rulings.expect("rulings should map");
+        assert_eq!(rulings[0].card_core, card.id);
+        assert_eq!(rulings[0].rulings[0].text, "X can be 0.");
+        assert_eq!(rulings[0].rulings[0].source, RulingSource::Wotc);
+
+        let printing = &mtgjson.catalog.printings[1];
+        assert_eq!(printing.id.as_ref(), "card_printing:s1");
+        assert_eq!(mtgjson.catalog.printings[0].id.as_ref(), "card_printing:p1");
+        assert_eq!(printing.set_code, "ZNR");
+        assert_eq!(printing.rarity, Some(Rarity::Uncommon));
+        assert_eq!(mtgjson.catalog.printings[0].rarity, None);
+    }
+
+    #[test]
+    fn test_map_all_printings_keeps_back_faces_colors() {
+        let json = r#"{
+            "data": {
+                "KHM": {
+                    "code": "KHM",
+                    "cards": [
+                        {
+                            "uuid": "v1", "name": "Valki, God of Lies // Tibalt, Cosmic Impostor",
+                            "manaCost": "{1}{B}", "type": "Legendary Creature — God",
+                            "number": "114", "side": "a",
+                            "identifiers": { "scryfallId": "s1", "scryfallOracleId": "o1" }
+                        },
+                        {
+                            "uuid": "v2", "name": "Valki, God of Lies // Tibalt, Cosmic Impostor",
+                            "manaCost": "{5}{B}{R}", "type": "Legendary Planeswalker — Tibalt",
+                            "number": "114", "side": "b",
+                            "identifiers": { "scryfallId": "s1", "scryfallOracleId": "o1" }
+                        }
+                    ]
+                },
+                "FUT": {
+                    "code": "FUT",
+                    "cards": [
+                        {
+                            "uuid": "d1", "name": "Dryad Arbor", "colorIndicator": ["G"],
+                            "type": "Land Creature — Forest Dryad", "number": "174",
+                            "identifiers": { "scryfallId": "s2", "scryfallOracleId": "o2" }
+                        }
+                    ]
+                }
+            }
+        }"#;
+
+        let mtgjson = map_all_printings(json.as_bytes()).expect("printings should map");
+
+        let valki = &mtgjson.catalog.cards[0];
+        assert_eq!(valki.mana_cost.as_deref(), Some("{1}{B}"));
+        assert_eq!(valki.back_mana_costs, vec!["{5}{B}{R}".to_string()]);
+        assert_eq!(mtgjson.catalog.cards[1].color_indicator, vec![Color::Green]);
+    }
+}
@@ -0,0 +1,55 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Serde mirrors of Scryfall's card objects as found in the bulk data files.
+//! Only the fields LotusLab reads are declared, everything else is skipped
+//! while deserializing.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+/// A card object. Bulk files are a json array of these, one per printing in
+/// `default_cards`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Card {
+    pub(crate) id: String,
+    /// Missing on reversible cards, which carry it on their faces instead.
+    pub(crate) oracle_id: Option<String>,
+    pub(crate) name: String,
+    pub(crate) layout: String,
+    pub(crate) mana_cost: Option<String>,
+    pub(crate) type_line: Option<String>,
+    pub(crate) oracle_text: Option<String>,
+    /// Color symbols like `"G"`, missing on cards without an indicator.
+    #[serde(default)]
+    pub(crate) color_indicator: Vec<String>,
+    #[serde(default)]
+    pub(crate) card_faces: Vec<CardFace>,
+    #[serde(default)]
+    pub(crate) legalities: BTreeMap<String, String>,
+    pub(crate) set: String,
+    pub(crate) collector_number: String,
+    pub(crate) rarity: Option<String>,
+    #[serde(default)]
+    pub(crate) prices: Prices,
+}
+
+/// Market prices as decimal strings, `None` when Scryfall has no recent sale.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct Prices {
+    pub(crate) usd: Option<String>,
+    pub(crate) usd_foil: Option<String>,
+    pub(crate) usd_etched: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CardFace {
+    pub(crate) oracle_id: Option<String>,
+    pub(crate) mana_cost: Option<String>,
+    pub(crate) type_line: Option<String>,
+    pub(crate) oracle_text: Option<String>,
+    #[serde(default)]
+    pub(crate) color_indicator: Vec<String>,
+}
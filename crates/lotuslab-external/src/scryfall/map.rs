@@ -0,0 +1,289 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
+
+use lotuslab_types::{CardCoreDetail, CardPrintingDetail, CatalogData, Color, Legality, Rarity};
+
+use super::dto::Card;
+use crate::ExternalError;
+
+/// Layouts that aren't cards of their own. MTGJSON keeps these out of a
+/// set's cards too.
+const SKIPPED_LAYOUTS: &[&str] = &["token", "double_faced_token", "emblem", "art_series"];
+
+/// Maps a Scryfall `default_cards` bulk file into catalog cards and
+/// printings.
+///
+/// Cards are keyed by oracle id and printings by Scryfall id like the MTGJSON
+/// import, so list items and collection entries keep pointing at the same
+/// records whichever source the catalog came from.
+///
+/// `oracle_cards` is rejected: a catalog update removes every printing the
+/// file lacks, and it only has one per card. It's told apart by no card
+/// having a second printing.
+pub fn map_bulk_cards(reader: impl Read) -> Result<CatalogData, ExternalError> {
+    let bulk: Vec<Card> = serde_json::from_reader(reader)?;
+
+    let mut catalog = CatalogData::default();
+    let mut cards: BTreeMap<String, CardCoreDetail> = BTreeMap::new();
+
+    for card in bulk {
+        if SKIPPED_LAYOUTS.contains(&card.layout.as_str()) {
+            continue;
+        }
+        let oracle_id = card
+            .oracle_id
+            .clone()
+            .or_else(|| card.card_faces.iter().find_map(|f| f.oracle_id.clone()));
+        let Some(oracle_id) = oracle_id else {
+            continue;
+        };
+        let card_core = format!("card_core:{}", oracle_id);
+
+        catalog.printings.push(CardPrintingDetail {
+            id: format!("card_printing:{}", card.id).into(),
+            card_core: card_core.clone().into(),
+            set_code: card.set.to_ascii_uppercase(),
+            collector_number: card.collector_number.clone(),
+            scryfall_id: Some(card.id.clone()),
+            rarity: card.rarity.as_deref().and_then(Rarity::parse),
+            price_usd: price_usd(&card),
+        });
+
+        cards
+            .entry(oracle_id)
+            .or_insert_with(|| map_card_core(card_core, card));
+    }
+    if cards.len() > 1 && catalog.printings.len() == cards.len() {
+        return Err(ExternalError::OracleCardsBulk);
+    }
+    catalog.cards = cards.into_values().collect();
+
+    Ok(catalog)
+}
+
+/// The nonfoil price, falling back to foil and etched for printings that
+/// only come in those. Scryfall gives prices as decimal strings like `"0.25"`.
+fn price_usd(card: &Card) -> Option<u32> {
+    let prices = &card.prices;
+    [&prices.usd, &prices.usd_foil, &prices.usd_etched]
+        .into_iter()
+        .find_map(|price| price.as_deref().and_then(parse_cents))
+}
+
+fn parse_cents(price: &str) -> Option<u32> {
+    let (dollars, cents) = price.trim().split_once('.').unwrap_or((price.trim(), "0"));
+    let cents = match cents.len() {
+        1 => cents.parse::<u32>().ok()? * 10,
+        2 => cents.parse::<u32>().ok()?,
+        _ => return None,
+    };
+    dollars
+        .parse::<u32>()
+        .ok()?
+        .checked_mul(100)?
+        .checked_add(cents)
+}
+
+fn map_card_core(id: String, card: Card) -> CardCoreDetail {
+    // Scryfall uses empty strings where MTGJSON leaves fields out.
+    let non_empty = |s: Option<String>| s.filter(|s| !s.is_empty());
+
+    let color_indicator: BTreeSet<Color> = card
+        .color_indicator
+        .iter()
+        .chain(card.card_faces.iter().flat_map(|f| &f.color_indicator))
+        .filter_map(|symbol| symbol.chars().next().and_then(Color::from_symbol))
+        .collect();
+    let back_mana_costs = card
+        .card_faces
+        .iter()
+        .skip(1)
+        .filter_map(|f| non_empty(f.mana_cost.clone()))
+        .collect();
+
+    let (mana_cost, type_line, oracle_text) = match card.card_faces.first() {
+        None => (
+            non_empty(card.mana_cost),
+            card.type_line.unwrap_or_default(),
+            non_empty(card.oracle_text),
+        ),
+        Some(front) => {
+            let texts: Vec<&str> = card
+                .card_faces
+                .iter()
+                .filter_map(|f| f.oracle_text.as_deref())
+                .filter(|text| !text.is_empty())
+                .collect();
+            (
+                non_empty(front.mana_cost.clone()),
+                card.card_faces
+                    .iter()
+                    .filter_map(|f| f.type_line.as_deref())
+                    .collect::<Vec<_>>()
+                    .join(" // "),
+                match texts.is_empty() {
+                    true => None,
+                    false => Some(texts.join("\n//\n")),
+                },
+            )
+        }
+    };
+
+    CardCoreDetail {
+        id: id.into(),
+        name: card.name,
+        mana_cost,
+        back_mana_costs,
+        color_indicator: color_indicator.into_iter().collect(),
+        type_line,
+        oracle_text,
+        legalities: card
+            .legalities
+            .into_iter()
+            .filter_map(|(format, legality)| {
+                Legality::parse(&legality).map(|legality| (format, legality))
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_bulk_cards() {
+        let json = r#"[
+            {
+                "object": "card", "id": "s1", "oracle_id": "o1", "layout": "modal_dfc",
+                "name": "Agadeem's Awakening // Agadeem, the Undercrypt",
+                "type_line": "Sorcery // Land", "set": "znr", "collector_number": "90",
+                "card_faces": [
+                    {
+                        "name": "Agadeem's Awakening", "mana_cost": "{X}{B}{B}{B}",
+                        "type_line": "Sorcery", "oracle_text": "Return creatures."
+                    },
+                    {
+                        "name": "Agadeem, the Undercrypt", "mana_cost": "",
+                        "type_line": "Land", "oracle_text": "Agadeem enters tapped."
+                    }
+                ],
+                "legalities": { "commander": "legal", "standard": "not_legal", "vintage": "restricted" }
+            },
+            {
+                "object": "card", "id": "s2", "oracle_id": "o1", "layout": "modal_dfc",
+                "name": "Agadeem's Awakening // Agadeem, the Undercrypt",
+                "type_line": "Sorcery // Land", "set": "znr", "collector_number": "364",
+                "rarity": "uncommon", "prices": { "usd": null, "usd_foil": "1.5" },
+                "card_faces": [], "legalities": {}
+            },
+            {
+                "object": "card", "id": "s3", "oracle_id": "o2", "layout": "normal",
+                "name": "Forest", "mana_cost": "", "type_line": "Basic Land — Forest",
+                "oracle_text": "({T}: Add {G}.)", "set": "lea", "collector_number": "294",
+                "prices": { "usd": "12.05", "usd_foil": null },
+                "legalities": { "commander": "legal" }
+            },
+            {
+                "object": "card", "id": "t1", "oracle_id": "o3", "layout": "token",
+                "name": "Soldier", "type_line": "Token Creature — Soldier",
+                "set": "tznr", "collector_number": "1", "legalities": {}
+            }
+        ]"#;
+
+        let catalog = map_bulk_cards(json.as_bytes()).expect("bulk cards should map");
+
+        assert_eq!(catalog.cards.len(), 2);
+        assert_eq!(catalog.printings.len(), 3);
+
+        let card = &catalog.cards[0];
+        assert_eq!(card.id.as_ref(), "card_core:o1");
+        assert_eq!(card.mana_cost.as_deref(), Some("{X}{B}{B}{B}"));
+        assert_eq!(card.type_line, "Sorcery // Land");
+        assert_eq!(
+            card.oracle_text.as_deref(),
+            Some("Return creatures.\n//\nAgadeem enters tapped.")
+        );
+        assert_eq!(card.legalities.get("commander"), Some(&Legality::Legal));
+        assert_eq!(card.legalities.get("vintage"), Some(&Legality::Restricted));
+        assert!(!card.legalities.contains_key("standard"));
+
+        assert!(card.back_mana_costs.is_empty());
+
+        let forest = &catalog.cards[1];
+        assert_eq!(forest.mana_cost, None);
+        assert_eq!(forest.type_line, "Basic Land — Forest");
+
+        let printing = &catalog.printings[1];
+        assert_eq!(printing.id.as_ref(), "card_printing:s2");
+        assert_eq!(printing.card_core.as_ref(), "card_core:o1");
+        assert_eq!(printing.set_code, "ZNR");
+        assert_eq!(printing.scryfall_id.as_deref(), Some("s2"));
+        assert_eq!(printing.rarity, Some(Rarity::Uncommon));
+        assert_eq!(printing.price_usd, Some(150));
+        assert_eq!(catalog.printings[0].price_usd, None);
+        assert_eq!(catalog.printings[2].price_usd, Some(1205));
+    }
+
+    #[test]
+    fn test_map_bulk_cards_keeps_back_faces_colors() {
+        let json = r#"[
+            {
+                "object": "card", "id": "s1", "oracle_id": "o1", "layout": "modal_dfc",
+                "name": "Valki, God of Lies // Tibalt, Cosmic Impostor",
+                "type_line": "Legendary Creature — God // Legendary Planeswalker — Tibalt",
+                "set": "khm", "collector_number": "114",
+                "card_faces": [
+                    { "name": "Valki, God of Lies", "mana_cost": "{1}{B}",
+                      "type_line": "Legendary Creature — God" },
+                    { "name": "Tibalt, Cosmic Impostor", "mana_cost": "{5}{B}{R}",
+                      "type_line": "Legendary Planeswalker — Tibalt" }
+                ]
+            },
+            {
+                "object": "card", "id": "s2", "oracle_id": "o2", "layout": "normal",
+                "name": "Dryad Arbor", "type_line": "Land Creature — Forest Dryad",
+                "color_indicator": ["G"], "set": "fut", "collector_number": "174"
+            },
+            {
+                "object": "card", "id": "s3", "oracle_id": "o2", "layout": "normal",
+                "name": "Dryad Arbor", "type_line": "Land Creature — Forest Dryad",
+                "color_indicator": ["G"], "set": "v12", "collector_number": "5"
+            }
+        ]"#;
+
+        let catalog = map_bulk_cards(json.as_bytes()).expect("bulk cards should map");
+
+        assert_eq!(catalog.cards[0].mana_cost.as_deref(), Some("{1}{B}"));
+        assert_eq!(
+            catalog.cards[0].back_mana_costs,
+            vec!["{5}{B}{R}".to_string()]
+        );
+        assert_eq!(catalog.cards[1].color_indicator, vec![Color::Green]);
+    }
+
+    #[test]
+    fn test_map_bulk_cards_rejects_oracle_cards() {
+        let json = r#"[
+            {
+                "object": "card", "id": "s1", "oracle_id": "o1", "layout": "normal",
+                "name": "Sol Ring", "type_line": "Artifact", "set": "c21",
+                "collector_number": "263"
+            },
+            {
+                "object": "card", "id": "s2", "oracle_id": "o2", "layout": "normal",
+                "name": "Forest", "type_line": "Basic Land — Forest", "set": "lea",
+                "collector_number": "294"
+            }
+        ]"#;
+
+        assert!(matches!(
+            map_bulk_cards(json.as_bytes()),
+            Err(ExternalError::OracleCardsBulk)
+        ));
+    }
+}
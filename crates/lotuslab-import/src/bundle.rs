@@ -0,0 +1,255 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! `.lotus` project bundles.
+//!
+//! A bundle is a zip archive holding a `bundle.json` manifest and the
+//! project's custom images under `images/`. Cards are referenced by their
+//! oracle and printing ids, which are stable across databases, with the card
+//! name kept alongside as a fallback. Nothing in a bundle refers to records
+//! of the database it came from, ids are assigned fresh on import.
+
+use std::io::{Cursor, Read, Write};
+
+use lotuslab_types::{CardCoreId, CardPrintingId, ListRole};
+use serde::{Deserialize, Serialize};
+use zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::ImportError;
+
+/// The bundle format version written by this build. Older versions are read,
+/// newer ones are rejected.
+pub const BUNDLE_VERSION: u32 = 1;
+pub const BUNDLE_EXTENSION: &str = "lotus";
+
+const MANIFEST_PATH: &str = "bundle.json";
+const IMAGES_DIR: &str = "images/";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProjectBundle {
+    pub version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub tags: Vec<BundleTag>,
+    pub lists: Vec<BundleList>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BundleTag {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BundleList {
+    pub name: String,
+    pub role: ListRole,
+    pub items: Vec<BundleItem>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BundleItem {
+    pub card_core_id: CardCoreId,
+    pub card_name: String,
+    pub selected_printing: Option<CardPrintingId>,
+    pub quantity: i32,
+    pub notes: Option<String>,
+    /// Tag names, matching [BundleTag::name].
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub commander: bool,
+    /// Path of the item's custom image inside the archive.
+    pub custom_image: Option<String>,
+    /// Custom images hosted elsewhere are kept as links.
+    #[serde(default)]
+    pub custom_image_url: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BundleImage {
+    pub path: String,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BundleArchive {
+    pub bundle: ProjectBundle,
+    pub images: Vec<BundleImage>,
+}
+
+/// Builds the archive path for the `index`th image, keeping the extension of
+/// the original file so it stays viewable once extracted.
+pub fn bundle_image_path(index: usize, original: &str) -> String {
+    let extension = original
+        .rsplit_once('.')
+        .map(|(_, ext)| ext)
+        .filter(|ext| !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or("img");
+    format!("{}{}.{}", IMAGES_DIR, index, extension.to_ascii_lowercase())
+}
+
+pub fn write_project_bundle(archive: &BundleArchive) -> Result<Vec<u8>, ImportError> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+    zip.start_file(MANIFEST_PATH, SimpleFileOptions::default())?;
+    zip.write_all(&serde_json::to_vec_pretty(&archive.bundle)?)?;
+
+    // Images are already compressed, deflating them again only costs time.
+    let stored = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    for image in &archive.images {
+        check_image_path(&image.path)?;
+        zip.start_file(image.path.as_str(), stored)?;
+        zip.write_all(&image.bytes)?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+pub fn read_project_bundle(bytes: &[u8]) -> Result<BundleArchive, ImportError> {
+    let mut zip = ZipArchive::new(Cursor::new(bytes))?;
+
+    let bundle: ProjectBundle = match zip.by_name(MANIFEST_PATH) {
+        Ok(manifest) => serde_json::from_reader(manifest)?,
+        Err(zip::result::ZipError::FileNotFound) => {
+            return Err(ImportError::InvalidBundle(format!(
+                "missing {}",
+                MANIFEST_PATH
+            )));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if bundle.version > BUNDLE_VERSION {
+        return Err(ImportError::UnsupportedBundleVersion(bundle.version));
+    }
+
+    let mut images = Vec::new();
+    for item in bundle.lists.iter().flat_map(|l| &l.items) {
+        let Some(path) = &item.custom_image else {
+            continue;
+        };
+        if images.iter().any(|i: &BundleImage| &i.path == path) {
+            continue;
+        }
+        check_image_path(path)?;
+
+        let mut file = zip.by_name(path).map_err(|e| match e {
+            zip::result::ZipError::FileNotFound => {
+                ImportError::InvalidBundle(format!("missing image {}", path))
+            }
+            e => e.into(),
+        })?;
+        let mut bytes = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut bytes)?;
+        images.push(BundleImage {
+            path: path.clone(),
+            bytes,
+        });
+    }
+
+    Ok(BundleArchive { bundle, images })
+}
+
+/// Image paths end up as file names on import, so only flat names inside the
+/// images directory are allowed.
+fn check_image_path(path: &str) -> Result<(), ImportError> {
+    match path.strip_prefix(IMAGES_DIR) {
+        Some(name)
+            if !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\', ':']) =>
+        {
+            Ok(())
+        }
+        _ => Err(ImportError::InvalidBundle(format!(
+            "invalid image path {}",
+            path
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive() -> BundleArchive {
+        BundleArchive {
+            bundle: ProjectBundle {
+                version: BUNDLE_VERSION,
+                name: "Atraxa Counters".into(),
+                tags: vec![BundleTag {
+                    name: "ramp".into(),
+                    color: Some("#2e7d32".into()),
+                }],
+                lists: vec![BundleList {
+                    name: "Main".into(),
+                    role: ListRole::Main,
+                    items: vec![
+                        BundleItem {
+                            card_core_id: "card_core:atraxa".into(),
+                            card_name: "Atraxa, Praetors' Voice".into(),
+                            selected_printing: Some("card_printing:c16-28".into()),
+                            quantity: 1,
+                            notes: None,
+                            tags: vec![],
+                            commander: true,
+                            custom_image: Some(bundle_image_path(0, "/art/atraxa.PNG")),
+                            custom_image_url: None,
+                        },
+                        BundleItem {
+                            card_core_id: "card_core:sol-ring".into(),
+                            card_name: "Sol Ring".into(),
+                            selected_printing: None,
+                            quantity: 1,
+                            notes: Some("always".into()),
+                            tags: vec!["ramp".into()],
+                            commander: false,
+                            custom_image: None,
+                            custom_image_url: Some("https://example.com/sol-ring.jpg".into()),
+                        },
+                    ],
+                }],
+            },
+            images: vec![BundleImage {
+                path: "images/0.png".into(),
+                bytes: vec![0x89, b'P', b'N', b'G'],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_project_bundle_round_trip() {
+        let archive = archive();
+        let bytes = write_project_bundle(&archive).expect("bundle should write");
+
+        assert_eq!(
+            read_project_bundle(&bytes).expect("bundle should read"),
+            archive
+        );
+    }
+
+    #[test]
+    fn test_read_project_bundle_rejects_newer_version() {
+        let mut archive = archive();
+        archive.bundle.version = BUNDLE_VERSION + 1;
+        let bytes = write_project_bundle(&archive).expect("bundle should write");
+
+        assert!(matches!(
+            read_project_bundle(&bytes),
+            Err(ImportError::UnsupportedBundleVersion(v)) if v == BUNDLE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn test_read_project_bundle_rejects_escaping_image_path() {
+        let mut archive = archive();
+        archive.images.clear();
+        archive.bundle.lists[0].items[0].custom_image = Some("images/../../evil.png".into());
+        let bytes = write_project_bundle(&archive).expect("bundle should write");
+
+        assert!(matches!(
+            read_project_bundle(&bytes),
+            Err(ImportError::InvalidBundle(_))
+        ));
+    }
+}
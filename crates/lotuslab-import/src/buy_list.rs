@@ -0,0 +1,90 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Buy lists hold the missing cards of a [HaveNeedReport], one card per line:
+//!
+//! ```text
+//! 1 Sol Ring [C21]
+//! 2 Swords to Plowshares
+//! ```
+//!
+//! TCGplayer's mass entry reads the set code in brackets to pick a printing
+//! and falls back to any printing without one. Plain text leaves it off.
+
+use std::fmt::Write;
+
+use lotuslab_types::{BuyListFormat, HaveNeedReport};
+
+pub fn write_buy_list(report: &HaveNeedReport, format: BuyListFormat) -> String {
+    let mut out = String::new();
+
+    for card in report.cards.iter().filter(|card| card.missing > 0) {
+        let set_code = match format {
+            BuyListFormat::PlainText => None,
+            BuyListFormat::TcgPlayer => card.buy_printing.as_ref().map(|p| &p.set_code),
+        };
+        let _ = match set_code {
+            Some(set) => writeln!(out, "{} {} [{}]", card.missing, card.name, set),
+            None => writeln!(out, "{} {}", card.missing, card.name),
+        };
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use lotuslab_types::{CardPrintingDetail, HaveNeedCard};
+
+    use super::*;
+
+    fn card(name: &str, missing: u32, set_code: Option<&str>) -> HaveNeedCard {
+        HaveNeedCard {
+            card_core_id: format!("card_core:{}", name).into(),
+            name: name.to_string(),
+            wanted: missing,
+            owned: 0,
+            allocated: 0,
+            have: 0,
+            missing,
+            allocations: Vec::new(),
+            buy_printing: set_code.map(|set_code| CardPrintingDetail {
+                id: format!("card_printing:{}", name).into(),
+                card_core: format!("card_core:{}", name).into(),
+                set_code: set_code.to_string(),
+                collector_number: "1".to_string(),
+                scryfall_id: None,
+                rarity: None,
+                price_usd: None,
+            }),
+            unit_price: None,
+        }
+    }
+
+    #[test]
+    fn test_write_buy_list() {
+        let report = HaveNeedReport {
+            project_id: "project:zada".into(),
+            cards: vec![
+                card("Fire // Ice", 1, Some("MH2")),
+                card("Sol Ring", 0, Some("C21")),
+                card("Swords to Plowshares", 2, None),
+            ],
+            wanted: 4,
+            have: 1,
+            missing: 3,
+            cost_to_complete: 0,
+            unpriced: 3,
+        };
+
+        assert_eq!(
+            write_buy_list(&report, BuyListFormat::PlainText),
+            "1 Fire // Ice\n2 Swords to Plowshares\n"
+        );
+        assert_eq!(
+            write_buy_list(&report, BuyListFormat::TcgPlayer),
+            "1 Fire // Ice [MH2]\n2 Swords to Plowshares\n"
+        );
+    }
+}
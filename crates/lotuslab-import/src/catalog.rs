@@ -0,0 +1,353 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Card catalog data from MTGJSON's `AllPrintings.json` or Scryfall's
+//! `default_cards` bulk file.
+//!
+//! MTGJSON publishes the file plain and as `.gz`, `.xz`, `.bz2` and `.zip`
+//! downloads, each with a `.sha256` sidecar holding the hash of the download.
+//! Any of them can be read here, compressed files are decompressed while they
+//! are parsed.
+
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use bzip2::read::MultiBzDecoder;
+use flate2::read::MultiGzDecoder;
+use lotuslab_external::{
+    ExternalError, mtgjson::map::map_all_printings, scryfall::map::map_bulk_cards,
+};
+use lotuslab_types::CatalogData;
+use sha2::{Digest, Sha256};
+use xz2::read::XzDecoder;
+use zip::ZipArchive;
+
+use crate::ImportError;
+
+const CHECKSUM_EXTENSION: &str = "sha256";
+
+/// Card data read from a catalog file, whichever source it came from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CatalogFile {
+    /// MTGJSON's build version. Scryfall bulk files don't carry one.
+    pub version: Option<String>,
+    pub catalog: CatalogData,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Compression {
+    None,
+    Gzip,
+    Xz,
+    Bzip2,
+    Zip,
+}
+
+impl Compression {
+    fn from_path(path: &Path) -> Result<Self, ImportError> {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.to_ascii_lowercase())
+            .ok_or_else(|| ImportError::InvalidFileName(path.to_string_lossy().into_owned()))?;
+
+        let compression = match name.rsplit_once('.') {
+            Some((_, "json")) => Compression::None,
+            Some((_, "gz")) => Compression::Gzip,
+            Some((_, "xz")) => Compression::Xz,
+            Some((_, "bz2")) => Compression::Bzip2,
+            Some((_, "zip")) => Compression::Zip,
+            _ => return Err(ImportError::UnsupportedFileType(name)),
+        };
+        Ok(compression)
+    }
+}
+
+/// Reads a card catalog file, plain or compressed.
+///
+/// MTGJSON files are json objects and Scryfall bulk files are json arrays,
+/// which is how the two are told apart. The file is checked against the
+/// `.sha256` sidecar next to `path` before anything is parsed. Scryfall
+/// publishes no sidecars, so a missing one is only accepted with
+/// `allow_missing_checksum`.
+pub fn read_card_catalog(
+    path: &Path,
+    allow_missing_checksum: bool,
+) -> Result<CatalogFile, ImportError> {
+    read_decompressed(path, allow_missing_checksum, |mut reader| {
+        if first_json_byte(&mut reader)? == Some(b'[') {
+            return Ok(CatalogFile {
+                version: None,
+                catalog: map_bulk_cards(reader)?,
+            });
+        }
+
+        let mtgjson = map_all_printings(reader)?;
+        Ok(CatalogFile {
+            version: mtgjson.version,
+            catalog: mtgjson.catalog,
+        })
+    })
+}
+
+/// Verifies `path` and hands `read` a decompressed stream of its contents.
+fn read_decompressed<T>(
+    path: &Path,
+    allow_missing_checksum: bool,
+    read: impl FnOnce(&mut dyn BufRead) -> Result<T, ImportError>,
+) -> Result<T, ImportError> {
+    let compression = Compression::from_path(path)?;
+    verify_checksum(path, allow_missing_checksum)?;
+
+    let mut file = BufReader::new(File::open(path)?);
+    let result = match compression {
+        Compression::None => read(&mut file),
+        Compression::Gzip => read(&mut BufReader::new(MultiGzDecoder::new(file))),
+        Compression::Xz => read(&mut BufReader::new(XzDecoder::new_multi_decoder(file))),
+        Compression::Bzip2 => read(&mut BufReader::new(MultiBzDecoder::new(file))),
+        Compression::Zip => {
+            let mut zip = ZipArchive::new(file)?;
+            let index = (0..zip.len())
+                .find(|&i| {
+                    zip.name_for_index(i)
+                        .is_some_and(|name| name.to_ascii_lowercase().ends_with(".json"))
+                })
+                .ok_or_else(|| {
+                    ImportError::CorruptFile("zip archive holds no json file".to_string())
+                })?;
+            read(&mut BufReader::new(zip.by_index(index)?))
+        }
+    };
+
+    // Decompression errors surface as read errors, often from inside the json
+    // parser.
+    result.map_err(|e| match e {
+        ImportError::Io(e) => ImportError::CorruptFile(e.to_string()),
+        ImportError::External(ExternalError::Json(e)) if e.is_io() || e.is_eof() => {
+            ImportError::CorruptFile(e.to_string())
+        }
+        e => e,
+    })
+}
+
+/// Skips leading whitespace and returns the first byte of the json document.
+fn first_json_byte(reader: &mut dyn BufRead) -> Result<Option<u8>, ImportError> {
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(None);
+        }
+        match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(i) => {
+                let byte = buf[i];
+                reader.consume(i);
+                return Ok(Some(byte));
+            }
+            None => {
+                let len = buf.len();
+                reader.consume(len);
+            }
+        }
+    }
+}
+
+/// Checks `path` against its `.sha256` sidecar. A missing sidecar is an
+/// error unless `allow_missing` is set.
+pub fn verify_checksum(path: &Path, allow_missing: bool) -> Result<(), ImportError> {
+    let sidecar = checksum_path(path);
+    let contents = match std::fs::read_to_string(&sidecar) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && allow_missing => return Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(ImportError::MissingChecksumFile(
+                sidecar.to_string_lossy().into_owned(),
+            ));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    // Either a bare hash or `sha256sum` output, `<hash>  <file name>`.
+    let expected = contents
+        .split_whitespace()
+        .next()
+        .filter(|hash| hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(|hash| hash.to_ascii_lowercase())
+        .ok_or_else(|| ImportError::InvalidChecksumFile(sidecar.to_string_lossy().into_owned()))?;
+
+    let actual = sha256_hex(File::open(path)?)?;
+    if actual != expected {
+        return Err(ImportError::ChecksumMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+fn checksum_path(path: &Path) -> PathBuf {
+    let mut sidecar = OsString::from(path.as_os_str());
+    sidecar.push(".");
+    sidecar.push(CHECKSUM_EXTENSION);
+    PathBuf::from(sidecar)
+}
+
+fn sha256_hex(mut reader: impl Read) -> Result<String, ImportError> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut reader, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    const ALL_PRINTINGS: &str = r#"{
+        "meta": { "version": "5.2.2+20250101" },
+        "data": {
+            "LEA": {
+                "code": "LEA",
+                "cards": [
+                    {
+                        "uuid": "u1", "name": "Black Lotus", "manaCost": "{0}",
+                        "type": "Artifact", "text": "Add three mana of any one color.",
+                        "number": "232", "identifiers": { "scryfallOracleId": "o1" }
+                    }
+                ]
+            }
+        }
+    }"#;
+
+    fn compress(name: &str, bytes: &[u8]) -> Vec<u8> {
+        match Compression::from_path(Path::new(name)).expect("supported name") {
+            Compression::None => bytes.to_vec(),
+            Compression::Gzip => {
+                let mut out = flate2::write::GzEncoder::new(Vec::new(), Default::default());
+                out.write_all(bytes).expect("gzip");
+                out.finish().expect("gzip")
+            }
+            Compression::Xz => {
+                let mut out = xz2::write::XzEncoder::new(Vec::new(), 6);
+                out.write_all(bytes).expect("xz");
+                out.finish().expect("xz")
+            }
+            Compression::Bzip2 => {
+                let mut out = bzip2::write::BzEncoder::new(Vec::new(), Default::default());
+                out.write_all(bytes).expect("bzip2");
+                out.finish().expect("bzip2")
+            }
+            Compression::Zip => {
+                let mut out = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+                out.start_file(
+                    "AllPrintings.json",
+                    zip::write::SimpleFileOptions::default(),
+                )
+                .expect("zip");
+                out.write_all(bytes).expect("zip");
+                out.finish().expect("zip").into_inner()
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_card_catalog_compressed() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        for name in [
+            "AllPrintings.json",
+            "AllPrintings.json.gz",
+            "AllPrintings.json.xz",
+            "AllPrintings.json.bz2",
+            "AllPrintings.json.zip",
+        ] {
+            let path = dir.path().join(name);
+            let bytes = compress(name, ALL_PRINTINGS.as_bytes());
+            std::fs::write(&path, &bytes).expect("write file");
+            std::fs::write(
+                checksum_path(&path),
+                format!(
+                    "{}  {}\n",
+                    sha256_hex(bytes.as_slice()).expect("hash"),
+                    name
+                ),
+            )
+            .expect("write sidecar");
+
+            let mtgjson = read_card_catalog(&path, false).expect(name);
+            assert_eq!(mtgjson.version.as_deref(), Some("5.2.2+20250101"));
+            assert_eq!(mtgjson.catalog.cards.len(), 1, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_read_card_catalog_scryfall() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("default-cards.json");
+        let json = r#"
+            [{
+                "id": "s1", "oracle_id": "o1", "layout": "normal", "name": "Black Lotus",
+                "mana_cost": "{0}", "type_line": "Artifact", "set": "lea",
+                "collector_number": "232", "legalities": { "vintage": "restricted" }
+            }]"#;
+        std::fs::write(&path, json).expect("write file");
+
+        let scryfall = read_card_catalog(&path, true).expect("scryfall file should read");
+        assert_eq!(scryfall.version, None);
+        assert_eq!(scryfall.catalog.cards[0].name, "Black Lotus");
+        assert_eq!(
+            scryfall.catalog.printings[0].scryfall_id.as_deref(),
+            Some("s1")
+        );
+    }
+
+    #[test]
+    fn test_read_card_catalog_rejects_checksum_mismatch() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("AllPrintings.json.gz");
+        std::fs::write(&path, compress("x.gz", ALL_PRINTINGS.as_bytes())).expect("write file");
+        std::fs::write(checksum_path(&path), "0".repeat(64)).expect("write sidecar");
+
+        assert!(matches!(
+            read_card_catalog(&path, true),
+            Err(ImportError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_card_catalog_requires_checksum() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("AllPrintings.json");
+        std::fs::write(&path, ALL_PRINTINGS).expect("write file");
+
+        assert!(matches!(
+            read_card_catalog(&path, false),
+            Err(ImportError::MissingChecksumFile(_))
+        ));
+        assert!(read_card_catalog(&path, true).is_ok());
+    }
+
+    #[test]
+    fn test_read_card_catalog_rejects_truncated_file() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("AllPrintings.json.gz");
+        let bytes = compress("x.gz", ALL_PRINTINGS.as_bytes());
+        std::fs::write(&path, &bytes[..bytes.len() / 2]).expect("write file");
+
+        assert!(matches!(
+            read_card_catalog(&path, true),
+            Err(ImportError::CorruptFile(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_card_catalog_rejects_unknown_extension() {
+        assert!(matches!(
+            read_card_catalog(Path::new("AllPrintings.json.7z"), false),
+            Err(ImportError::UnsupportedFileType(_))
+        ));
+    }
+}
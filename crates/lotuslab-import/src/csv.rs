@@ -0,0 +1,308 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Card CSV exports from collection and deck building tools.
+//!
+//! Every tool names its columns differently and spells conditions and
+//! finishes its own way, so rows are read through a [CsvColumns] mapping and
+//! values are normalized leniently. Rows that can't be read are collected as
+//! [CsvRowError]s instead of failing the whole file.
+
+use lotuslab_types::{CardCondition, CsvCardRow, CsvColumns, CsvPreset, CsvRowError, Finish};
+
+use crate::ImportError;
+
+#[derive(Clone, Debug, Default)]
+pub struct ParsedCsv {
+    pub rows: Vec<CsvCardRow>,
+    pub errors: Vec<CsvRowError>,
+}
+
+pub fn csv_preset_columns(preset: CsvPreset) -> CsvColumns {
+    let columns = |quantity: &str,
+                   name: &str,
+                   set_code: &str,
+                   collector_number: &str,
+                   finish: &str,
+                   condition: &str,
+                   language: &str| CsvColumns {
+        quantity: quantity.into(),
+        name: name.into(),
+        set_code: Some(set_code.into()),
+        collector_number: Some(collector_number.into()),
+        finish: Some(finish.into()),
+        condition: Some(condition.into()),
+        language: Some(language.into()),
+    };
+
+    match preset {
+        CsvPreset::Moxfield => columns(
+            "Count",
+            "Name",
+            "Edition",
+            "Collector Number",
+            "Foil",
+            "Condition",
+            "Language",
+        ),
+        CsvPreset::Archidekt => columns(
+            "Quantity",
+            "Name",
+            "Edition Code",
+            "Collector Number",
+            "Finish",
+            "Condition",
+            "Language",
+        ),
+        CsvPreset::ManaBox => columns(
+            "Quantity",
+            "Name",
+            "Set code",
+            "Collector number",
+            "Foil",
+            "Condition",
+            "Language",
+        ),
+        CsvPreset::DelverLens => columns(
+            "Quantity",
+            "Name",
+            "Edition code",
+            "Collector's number",
+            "Foil",
+            "Condition",
+            "Language",
+        ),
+        CsvPreset::TcgPlayer => columns(
+            "Quantity",
+            "Name",
+            "Set Code",
+            "Card Number",
+            "Printing",
+            "Condition",
+            "Language",
+        ),
+    }
+}
+
+pub fn parse_card_csv(input: &str, columns: &CsvColumns) -> Result<ParsedCsv, ImportError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(input.as_bytes());
+
+    let headers = reader.headers()?.clone();
+    let find = |column: &str| {
+        headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(column.trim()))
+    };
+    let find_optional = |column: &Option<String>| column.as_deref().and_then(find);
+
+    let quantity = find(&columns.quantity)
+        .ok_or_else(|| ImportError::MissingColumn(columns.quantity.clone()))?;
+    let name =
+        find(&columns.name).ok_or_else(|| ImportError::MissingColumn(columns.name.clone()))?;
+    let set_code = find_optional(&columns.set_code);
+    let collector_number = find_optional(&columns.collector_number);
+    let finish = find_optional(&columns.finish);
+    let condition = find_optional(&columns.condition);
+    let language = find_optional(&columns.language);
+
+    let mut parsed = ParsedCsv::default();
+
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                parsed.errors.push(CsvRowError {
+                    line: e.position().map(|p| p.line() as u32).unwrap_or_default(),
+                    raw: String::new(),
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        let line = record
+            .position()
+            .map(|p| p.line() as u32)
+            .unwrap_or_default();
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|i| record.get(i))
+                .filter(|value| !value.is_empty())
+        };
+        let error = |reason: String| CsvRowError {
+            line,
+            raw: record.iter().collect::<Vec<_>>().join(","),
+            reason,
+        };
+
+        let Some(name) = field(Some(name)) else {
+            parsed.errors.push(error("missing card name".into()));
+            continue;
+        };
+
+        let quantity = match field(Some(quantity)).map(str::parse::<i32>) {
+            Some(Ok(q)) if q > 0 => q,
+            Some(_) => {
+                parsed
+                    .errors
+                    .push(error(format!("invalid quantity for '{}'", name)));
+                continue;
+            }
+            None => 1,
+        };
+
+        let condition = match field(condition).map(parse_condition) {
+            Some(Some(c)) => Some(c),
+            Some(None) => {
+                parsed.errors.push(error(format!(
+                    "unknown condition '{}'",
+                    field(condition).unwrap_or_default()
+                )));
+                continue;
+            }
+            None => None,
+        };
+
+        parsed.rows.push(CsvCardRow {
+            line,
+            quantity,
+            name: name.to_string(),
+            set_code: field(set_code).map(str::to_ascii_uppercase),
+            collector_number: field(collector_number).map(str::to_string),
+            finish: field(finish).map(parse_finish).unwrap_or_default(),
+            condition,
+            language: field(language).map(parse_language),
+        });
+    }
+
+    Ok(parsed)
+}
+
+/// Normalizes the many spellings of a finish, e.g. `"foil"`, `"true"`, `"Etched"`.
+fn parse_finish(value: &str) -> Finish {
+    match value.to_ascii_lowercase().as_str() {
+        "foil" | "true" | "yes" | "1" => Finish::Foil,
+        "etched" | "etched foil" => Finish::Etched,
+        _ => Finish::Nonfoil,
+    }
+}
+
+/// Normalizes condition names and grading abbreviations, e.g. `"Near Mint"`,
+/// `"near_mint"`, `"NM"`. TCGplayer appends the finish, as in `"Near Mint Foil"`.
+fn parse_condition(value: &str) -> Option<CardCondition> {
+    let normalized = value
+        .to_ascii_lowercase()
+        .replace([' ', '_', '-'], "")
+        .trim_end_matches("foil")
+        .to_string();
+
+    match normalized.as_str() {
+        "mint" | "m" | "nearmint" | "nm" => Some(CardCondition::NearMint),
+        "lightlyplayed" | "lp" | "slightlyplayed" | "sp" | "excellent" | "ex" => {
+            Some(CardCondition::LightlyPlayed)
+        }
+        "moderatelyplayed" | "mp" | "played" | "pl" | "good" | "gd" => {
+            Some(CardCondition::ModeratelyPlayed)
+        }
+        "heavilyplayed" | "hp" | "poor" => Some(CardCondition::HeavilyPlayed),
+        "damaged" | "dmg" => Some(CardCondition::Damaged),
+        _ => None,
+    }
+}
+
+/// Maps language names to the codes Scryfall uses, e.g. `"Japanese"` to
+/// `"ja"`. Codes and unknown names are returned as given.
+fn parse_language(value: &str) -> String {
+    let code = match value.to_ascii_lowercase().replace(['-', '_'], " ").as_str() {
+        "english" => "en",
+        "spanish" => "es",
+        "french" => "fr",
+        "german" => "de",
+        "italian" => "it",
+        "portuguese" | "portuguese (brazil)" => "pt",
+        "japanese" => "ja",
+        "korean" => "ko",
+        "russian" => "ru",
+        "chinese simplified" | "simplified chinese" => "zhs",
+        "chinese traditional" | "traditional chinese" => "zht",
+        "hebrew" => "he",
+        "latin" => "la",
+        "ancient greek" => "grc",
+        "arabic" => "ar",
+        "sanskrit" => "sa",
+        "phyrexian" => "ph",
+        _ => return value.to_string(),
+    };
+    code.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manabox_csv() {
+        let input = "\
+Name,Set code,Set name,Collector number,Foil,Rarity,Quantity,Condition,Language
+Sol Ring,C21,Commander 2021,263,normal,uncommon,2,near_mint,en
+\"Atraxa, Praetors' Voice\",C16,Commander 2016,28,foil,mythic,1,lightly_played,en
+Forest,,,,,,zero,,
+";
+        let parsed = parse_card_csv(input, &csv_preset_columns(CsvPreset::ManaBox))
+            .expect("headers should match");
+
+        assert_eq!(parsed.rows.len(), 2);
+        assert_eq!(parsed.errors.len(), 1);
+
+        let sol_ring = &parsed.rows[0];
+        assert_eq!(sol_ring.quantity, 2);
+        assert_eq!(sol_ring.set_code.as_deref(), Some("C21"));
+        assert_eq!(sol_ring.collector_number.as_deref(), Some("263"));
+        assert_eq!(sol_ring.condition, Some(CardCondition::NearMint));
+
+        let atraxa = &parsed.rows[1];
+        assert_eq!(atraxa.name, "Atraxa, Praetors' Voice");
+        assert_eq!(atraxa.finish, Finish::Foil);
+
+        assert_eq!(parsed.errors[0].line, 4);
+    }
+
+    #[test]
+    fn test_parse_card_csv_missing_column() {
+        let err = parse_card_csv(
+            "Card,Qty\nSol Ring,1\n",
+            &csv_preset_columns(CsvPreset::Moxfield),
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, ImportError::MissingColumn(column) if column == "Count"));
+    }
+
+    #[test]
+    fn test_parse_condition_spellings() {
+        assert_eq!(parse_condition("Near Mint"), Some(CardCondition::NearMint));
+        assert_eq!(
+            parse_condition("Near Mint Foil"),
+            Some(CardCondition::NearMint)
+        );
+        assert_eq!(parse_condition("MP"), Some(CardCondition::ModeratelyPlayed));
+        assert_eq!(
+            parse_condition("heavily_played"),
+            Some(CardCondition::HeavilyPlayed)
+        );
+        assert_eq!(parse_condition("sleeved"), None);
+    }
+
+    #[test]
+    fn test_parse_language_names() {
+        assert_eq!(parse_language("English"), "en");
+        assert_eq!(parse_language("Chinese Simplified"), "zhs");
+        assert_eq!(parse_language("chinese_traditional"), "zht");
+        assert_eq!(parse_language("ja"), "ja");
+        assert_eq!(parse_language("Klingon"), "Klingon");
+    }
+}
@@ -0,0 +1,196 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Forge `.dck` files are INI-like:
+//!
+//! ```text
+//! [metadata]
+//! Name=Mono Red
+//! [Main]
+//! 4 Lightning Bolt|M10|1
+//! [Sideboard]
+//! 2 Pyroblast|ICE
+//! ```
+//!
+//! Card lines are `quantity name|SET|art index`. Forge has no collector
+//! numbers, so only the set code and art index of a printing survive a round
+//! trip.
+
+use std::fmt::Write;
+
+use lotuslab_types::{Decklist, DecklistEntry, DecklistSection};
+
+use super::split_quantity;
+use crate::ImportError;
+
+pub fn parse_forge_dck(input: &str) -> Result<Decklist, ImportError> {
+    let mut deck = Decklist::default();
+    // `None` while inside metadata or a section we don't model (e.g. `[Planes]`).
+    let mut section: Option<DecklistSection> = None;
+    let mut in_metadata = false;
+
+    for (i, line) in input.lines().enumerate() {
+        let line_no = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_metadata = header.eq_ignore_ascii_case("metadata");
+            section = match header.to_ascii_lowercase().as_str() {
+                "main" => Some(DecklistSection::Main),
+                "sideboard" => Some(DecklistSection::Sideboard),
+                "commander" => Some(DecklistSection::Commander),
+                _ => None,
+            };
+            continue;
+        }
+
+        if in_metadata {
+            if let Some((key, value)) = line.split_once('=')
+                && key.trim().eq_ignore_ascii_case("name")
+            {
+                deck.name = Some(value.trim().to_string());
+            }
+            continue;
+        }
+
+        let Some(section) = section else {
+            continue;
+        };
+
+        let (quantity, card) = split_quantity(line, line_no)?;
+        let mut fields = card.split('|');
+        let name = fields.next().unwrap_or_default().trim();
+        if name.is_empty() {
+            return Err(ImportError::InvalidLine {
+                line: line_no,
+                reason: "missing card name".into(),
+            });
+        }
+        let set_code = fields
+            .next()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_ascii_uppercase);
+        let art_index = match fields.next().map(str::trim).filter(|s| !s.is_empty()) {
+            Some(index) => Some(index.parse::<u32>().map_err(|_| ImportError::InvalidLine {
+                line: line_no,
+                reason: format!("invalid art index `{}`", index),
+            })?),
+            None => None,
+        };
+
+        deck.entries.push(DecklistEntry {
+            section,
+            quantity,
+            name: name.to_string(),
+            set_code,
+            collector_number: None,
+            art_index,
+        });
+    }
+
+    Ok(deck)
+}
+
+pub fn write_forge_dck(deck: &Decklist) -> String {
+    let mut out = String::new();
+
+    out.push_str("[metadata]\n");
+    if let Some(name) = &deck.name {
+        let _ = writeln!(out, "Name={}", name);
+    }
+
+    for (header, section) in [
+        ("Commander", DecklistSection::Commander),
+        ("Main", DecklistSection::Main),
+        ("Sideboard", DecklistSection::Sideboard),
+    ] {
+        let mut entries = deck.section(section).peekable();
+        if entries.peek().is_none() && section != DecklistSection::Main {
+            continue;
+        }
+
+        let _ = writeln!(out, "[{}]", header);
+        for entry in entries {
+            match (&entry.set_code, entry.art_index) {
+                (Some(set), Some(art)) => {
+                    let _ = writeln!(out, "{} {}|{}|{}", entry.quantity, entry.name, set, art);
+                }
+                (Some(set), None) => {
+                    let _ = writeln!(out, "{} {}|{}", entry.quantity, entry.name, set);
+                }
+                (None, _) => {
+                    let _ = writeln!(out, "{} {}", entry.quantity, entry.name);
+                }
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECK: &str = "\
+[metadata]
+Name=Atraxa Counters
+[Commander]
+1 Atraxa, Praetors' Voice|C16|1
+[Main]
+1 Sol Ring|C16
+30 Forest
+[Sideboard]
+1 Pyroblast|ICE|2
+[Planes]
+1 Tazeem|HOP
+";
+
+    #[test]
+    fn test_parse_forge_dck() {
+        let deck = parse_forge_dck(DECK).expect("deck should parse");
+
+        assert_eq!(deck.name.as_deref(), Some("Atraxa Counters"));
+        assert_eq!(deck.entries.len(), 4);
+
+        let commander = &deck.entries[0];
+        assert_eq!(commander.section, DecklistSection::Commander);
+        assert_eq!(commander.name, "Atraxa, Praetors' Voice");
+        assert_eq!(commander.set_code.as_deref(), Some("C16"));
+        assert_eq!(commander.art_index, Some(1));
+
+        let forest = &deck.entries[2];
+        assert_eq!(forest.quantity, 30);
+        assert_eq!(forest.set_code, None);
+        assert_eq!(forest.art_index, None);
+    }
+
+    #[test]
+    fn test_forge_dck_round_trip() {
+        let deck = parse_forge_dck(DECK).expect("deck should parse");
+        let written = write_forge_dck(&deck);
+        let reparsed = parse_forge_dck(&written).expect("output should parse");
+
+        assert!(written.contains("1 Pyroblast|ICE|2\n"));
+        assert_eq!(deck, reparsed);
+    }
+
+    #[test]
+    fn test_parse_forge_dck_rejects_bad_quantity() {
+        let err = parse_forge_dck("[Main]\nfour Forest\n").unwrap_err();
+
+        assert!(matches!(err, ImportError::InvalidLine { line: 2, .. }));
+    }
+
+    #[test]
+    fn test_parse_forge_dck_rejects_bad_art_index() {
+        let err = parse_forge_dck("[Main]\n4 Forest|M10|a\n").unwrap_err();
+
+        assert!(matches!(err, ImportError::InvalidLine { line: 2, .. }));
+    }
+}
@@ -0,0 +1,52 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Forge and XMage both save decks as `.dck` files, but the two syntaxes are
+//! unrelated. Use the module matching the engine the file came from.
+
+mod forge;
+mod xmage;
+
+pub use self::forge::*;
+pub use self::xmage::*;
+
+use lotuslab_types::{DckFormat, Decklist};
+
+use crate::ImportError;
+
+pub fn parse_dck(input: &str, format: DckFormat) -> Result<Decklist, ImportError> {
+    match format {
+        DckFormat::Forge => parse_forge_dck(input),
+        DckFormat::XMage => parse_xmage_dck(input),
+    }
+}
+
+pub fn write_dck(deck: &Decklist, format: DckFormat) -> String {
+    match format {
+        DckFormat::Forge => write_forge_dck(deck),
+        DckFormat::XMage => write_xmage_dck(deck),
+    }
+}
+
+/// Splits a `"4 Lightning Bolt"` style line into its quantity and the rest.
+fn split_quantity(line: &str, line_no: usize) -> Result<(i32, &str), ImportError> {
+    let (quantity, rest) =
+        line.split_once(char::is_whitespace)
+            .ok_or_else(|| ImportError::InvalidLine {
+                line: line_no,
+                reason: "expected a quantity followed by a card".into(),
+            })?;
+
+    let quantity = quantity
+        .trim_end_matches('x')
+        .parse::<i32>()
+        .ok()
+        .filter(|q| *q > 0)
+        .ok_or_else(|| ImportError::InvalidLine {
+            line: line_no,
+            reason: format!("invalid quantity '{}'", quantity),
+        })?;
+
+    Ok((quantity, rest.trim()))
+}
@@ -0,0 +1,170 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! XMage `.dck` files are line based:
+//!
+//! ```text
+//! NAME:Mono Red
+//! 4 [M10:146] Lightning Bolt
+//! SB: 2 [ICE:200] Pyroblast
+//! LAYOUT MAIN:(1,4)(NONE,false,50)|([M10:146])
+//! ```
+//!
+//! XMage reads commanders from the sideboard, so commanders are written as
+//! `SB:` lines and come back as sideboard entries. `LAYOUT` lines only store
+//! the deck editor layout and are ignored.
+
+use std::fmt::Write;
+
+use lotuslab_types::{Decklist, DecklistEntry, DecklistSection};
+
+use super::split_quantity;
+use crate::ImportError;
+
+pub fn parse_xmage_dck(input: &str) -> Result<Decklist, ImportError> {
+    let mut deck = Decklist::default();
+
+    for (i, line) in input.lines().enumerate() {
+        let line_no = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("LAYOUT") {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix("NAME:") {
+            deck.name = Some(name.trim().to_string());
+            continue;
+        }
+
+        let (section, line) = match line.strip_prefix("SB:") {
+            Some(rest) => (DecklistSection::Sideboard, rest.trim()),
+            None => (DecklistSection::Main, line),
+        };
+
+        let (quantity, card) = split_quantity(line, line_no)?;
+        let (set_code, collector_number, name) = match card.strip_prefix('[') {
+            Some(rest) => {
+                let (printing, name) =
+                    rest.split_once(']')
+                        .ok_or_else(|| ImportError::InvalidLine {
+                            line: line_no,
+                            reason: "unterminated printing, expected '[SET:NUM]'".into(),
+                        })?;
+                let (set, number) = printing.split_once(':').unwrap_or((printing, ""));
+                (
+                    Some(set.trim().to_ascii_uppercase()).filter(|s| !s.is_empty()),
+                    Some(number.trim().to_string()).filter(|n| !n.is_empty()),
+                    name.trim(),
+                )
+            }
+            None => (None, None, card),
+        };
+
+        if name.is_empty() {
+            return Err(ImportError::InvalidLine {
+                line: line_no,
+                reason: "missing card name".into(),
+            });
+        }
+
+        deck.entries.push(DecklistEntry {
+            section,
+            quantity,
+            name: name.to_string(),
+            set_code,
+            collector_number,
+            art_index: None,
+        });
+    }
+
+    Ok(deck)
+}
+
+pub fn write_xmage_dck(deck: &Decklist) -> String {
+    let mut out = String::new();
+
+    if let Some(name) = &deck.name {
+        let _ = writeln!(out, "NAME:{}", name);
+    }
+
+    let main = deck.section(DecklistSection::Main).map(|e| ("", e));
+    let side = deck
+        .section(DecklistSection::Commander)
+        .chain(deck.section(DecklistSection::Sideboard))
+        .map(|e| ("SB: ", e));
+
+    for (prefix, entry) in main.chain(side) {
+        let _ = match (&entry.set_code, &entry.collector_number) {
+            (Some(set), Some(number)) => writeln!(
+                out,
+                "{}{} [{}:{}] {}",
+                prefix, entry.quantity, set, number, entry.name
+            ),
+            (Some(set), None) => {
+                writeln!(out, "{}{} [{}] {}", prefix, entry.quantity, set, entry.name)
+            }
+            _ => writeln!(out, "{}{} {}", prefix, entry.quantity, entry.name),
+        };
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECK: &str = "\
+NAME:Mono Red
+4 [M10:146] Lightning Bolt
+20 Mountain
+SB: 2 [ICE:200] Pyroblast
+LAYOUT MAIN:(1,2)(NONE,false,50)|([M10:146])
+";
+
+    #[test]
+    fn test_parse_xmage_dck() {
+        let deck = parse_xmage_dck(DECK).expect("deck should parse");
+
+        assert_eq!(deck.name.as_deref(), Some("Mono Red"));
+        assert_eq!(deck.entries.len(), 3);
+
+        let bolt = &deck.entries[0];
+        assert_eq!(bolt.quantity, 4);
+        assert_eq!(bolt.name, "Lightning Bolt");
+        assert_eq!(bolt.set_code.as_deref(), Some("M10"));
+        assert_eq!(bolt.collector_number.as_deref(), Some("146"));
+
+        let pyroblast = &deck.entries[2];
+        assert_eq!(pyroblast.section, DecklistSection::Sideboard);
+    }
+
+    #[test]
+    fn test_xmage_dck_round_trip() {
+        let deck = parse_xmage_dck(DECK).expect("deck should parse");
+        let reparsed = parse_xmage_dck(&write_xmage_dck(&deck)).expect("output should parse");
+
+        assert_eq!(deck, reparsed);
+    }
+
+    #[test]
+    fn test_write_xmage_dck_puts_commander_in_sideboard() {
+        let deck = Decklist {
+            name: None,
+            entries: vec![DecklistEntry {
+                section: DecklistSection::Commander,
+                quantity: 1,
+                name: "Atraxa, Praetors' Voice".into(),
+                set_code: Some("C16".into()),
+                collector_number: Some("28".into()),
+                art_index: None,
+            }],
+        };
+
+        assert_eq!(
+            write_xmage_dck(&deck),
+            "SB: 1 [C16:28] Atraxa, Praetors' Voice\n"
+        );
+    }
+}
@@ -0,0 +1,41 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("line {line}: {reason}")]
+    InvalidLine { line: usize, reason: String },
+    #[error("missing column '{0}'")]
+    MissingColumn(String),
+    #[error("invalid file name '{0}'")]
+    InvalidFileName(String),
+    #[error("invalid bundle: {0}")]
+    InvalidBundle(String),
+    #[error("bundle version {0} is newer than this version of LotusLab supports")]
+    UnsupportedBundleVersion(u32),
+    #[error("format file version {0} is newer than this version of LotusLab supports")]
+    UnsupportedFormatFileVersion(u32),
+    #[error("unsupported file type '{0}'")]
+    UnsupportedFileType(String),
+    #[error("checksum file '{0}' is missing")]
+    MissingChecksumFile(String),
+    #[error("invalid checksum file '{0}'")]
+    InvalidChecksumFile(String),
+    #[error("checksum mismatch, expected {expected} but the file hashes to {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+    #[error("file is corrupt or incomplete: {0}")]
+    CorruptFile(String),
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    #[error(transparent)]
+    External(#[from] lotuslab_external::ExternalError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+}
@@ -0,0 +1,82 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Custom format files.
+//!
+//! A format file is a small json document holding a format's rules, so
+//! playgroups can share house formats. It's the [FormatRules] with a
+//! `version` next to them.
+
+use lotuslab_types::FormatRules;
+use serde::{Deserialize, Serialize};
+
+use crate::ImportError;
+
+/// The format file version written by this build. Older versions are read,
+/// newer ones are rejected.
+pub const FORMAT_FILE_VERSION: u32 = 1;
+pub const FORMAT_FILE_EXTENSION: &str = "json";
+
+#[derive(Serialize, Deserialize)]
+struct FormatFile {
+    version: u32,
+    #[serde(flatten)]
+    rules: FormatRules,
+}
+
+pub fn write_format_file(rules: &FormatRules) -> Result<Vec<u8>, ImportError> {
+    let file = FormatFile {
+        version: FORMAT_FILE_VERSION,
+        rules: rules.clone(),
+    };
+    Ok(serde_json::to_vec_pretty(&file)?)
+}
+
+pub fn read_format_file(bytes: &[u8]) -> Result<FormatRules, ImportError> {
+    let file: FormatFile = serde_json::from_slice(bytes)?;
+    if file.version > FORMAT_FILE_VERSION {
+        return Err(ImportError::UnsupportedFormatFileVersion(file.version));
+    }
+    Ok(file.rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use lotuslab_types::{Format, PointsTable, Rarity};
+
+    use super::*;
+
+    #[test]
+    fn test_format_file_round_trip() {
+        let rules = FormatRules {
+            name: "Pauper Cube".into(),
+            banned: vec!["Sol Ring".into()],
+            allowed_sets: Some(vec!["DMU".into(), "BRO".into()]),
+            allowed_rarities: Some(vec![Rarity::Common]),
+            points: Some(PointsTable {
+                cap: 10,
+                points: [("Ponder".to_string(), 1)].into_iter().collect(),
+            }),
+            ..Format::Pauper.rules()
+        };
+
+        let bytes = write_format_file(&rules).expect("format should write");
+        assert_eq!(read_format_file(&bytes).expect("format should read"), rules);
+    }
+
+    #[test]
+    fn test_read_format_file() {
+        let minimal = br#"{ "version": 1, "name": "Highlander", "max_copies": 1 }"#;
+        let rules = read_format_file(minimal).expect("minimal format should read");
+        assert_eq!(rules.name, "Highlander");
+        assert_eq!(rules.min_deck_size, None);
+        assert!(rules.banned.is_empty());
+
+        let newer = br#"{ "version": 2, "name": "Highlander", "max_copies": 1 }"#;
+        assert!(matches!(
+            read_format_file(newer),
+            Err(ImportError::UnsupportedFormatFileVersion(2))
+        ));
+    }
+}
@@ -0,0 +1,25 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+mod bundle;
+mod buy_list;
+mod catalog;
+mod csv;
+mod dck;
+mod error;
+mod format;
+mod points;
+mod precon;
+mod tts;
+
+pub use crate::bundle::*;
+pub use crate::buy_list::*;
+pub use crate::catalog::*;
+pub use crate::csv::*;
+pub use crate::dck::*;
+pub use crate::error::*;
+pub use crate::format::*;
+pub use crate::points::*;
+pub use crate::precon::*;
+pub use crate::tts::*;
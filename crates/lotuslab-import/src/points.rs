@@ -0,0 +1,81 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Points lists for formats like Canadian Highlander, as a `Card Name,points`
+//! CSV. A header row is optional, names with commas go in double quotes.
+
+use std::collections::BTreeMap;
+
+use crate::ImportError;
+
+pub fn parse_points_csv(input: &str) -> Result<BTreeMap<String, u32>, ImportError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(input.as_bytes());
+
+    let mut points = BTreeMap::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record?;
+        let line = record
+            .position()
+            .map(|p| p.line() as usize)
+            .unwrap_or_default();
+        let invalid = |reason: String| ImportError::InvalidLine { line, reason };
+
+        let (name, value) = match (record.get(0), record.get(1)) {
+            (Some(name), Some(value)) if !name.is_empty() => (name, value),
+            _ => return Err(invalid("expected 'Card Name,points'".into())),
+        };
+        let value = match value.parse::<u32>() {
+            Ok(value) => value,
+            Err(_) if index == 0 => continue,
+            Err(_) => return Err(invalid(format!("invalid points for '{}'", name))),
+        };
+
+        if points.insert(name.to_string(), value).is_some() {
+            return Err(invalid(format!("'{}' is listed twice", name)));
+        }
+    }
+
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_points_csv() {
+        let input = "Card Name,Points\nAncestral Recall,7\n\n\"Sol Ring\", 4\n";
+        let points = parse_points_csv(input).expect("points should parse");
+        assert_eq!(
+            points,
+            BTreeMap::from([
+                ("Ancestral Recall".to_string(), 7),
+                ("Sol Ring".to_string(), 4),
+            ])
+        );
+
+        let no_header = parse_points_csv("Time Walk,6").expect("points should parse");
+        assert_eq!(no_header.get("Time Walk"), Some(&6));
+    }
+
+    #[test]
+    fn test_parse_points_csv_errors() {
+        assert!(matches!(
+            parse_points_csv("Time Walk,6\nSol Ring,lots"),
+            Err(ImportError::InvalidLine { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse_points_csv("Time Walk,6\nTime Walk,5"),
+            Err(ImportError::InvalidLine { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse_points_csv("Time Walk"),
+            Err(ImportError::InvalidLine { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,29 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! MTGJSON preconstructed decks, read from an extracted `AllDeckFiles`
+//! directory that also contains `DeckList.json`.
+
+use std::fs;
+use std::path::Path;
+
+use lotuslab_external::mtgjson::map::{map_deck, map_deck_list};
+use lotuslab_types::{Decklist, PreconSummary};
+
+use crate::ImportError;
+
+pub fn read_precon_index(decks_dir: &Path) -> Result<Vec<PreconSummary>, ImportError> {
+    let json = fs::read_to_string(decks_dir.join("DeckList.json"))?;
+    Ok(map_deck_list(&json)?)
+}
+
+pub fn read_precon(decks_dir: &Path, file_name: &str) -> Result<Decklist, ImportError> {
+    // File names come from the index, don't let them point outside the directory.
+    if file_name.contains(['/', '\\']) || file_name.starts_with('.') {
+        return Err(ImportError::InvalidFileName(file_name.to_string()));
+    }
+
+    let json = fs::read_to_string(decks_dir.join(format!("{}.json", file_name)))?;
+    Ok(map_deck(&json)?)
+}
@@ -0,0 +1,257 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Tabletop Simulator saved objects.
+//!
+//! Every pile becomes a `DeckCustom` object (or a lone `Card`, TTS won't load
+//! a deck of one) laid out side by side. Each distinct face image gets its own
+//! single-card `CustomDeck` sheet, so images are referenced by URL and nothing
+//! is downloaded at export time.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// The default Magic card back as hosted by Scryfall.
+pub const TTS_CARD_BACK_URL: &str =
+    "https://backs.scryfall.io/large/0/a/0aeebaf5-8c7d-4636-9e82-8c27447861f7.jpg";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TtsCard {
+    pub name: String,
+    pub face_url: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TtsPile {
+    pub name: String,
+    pub cards: Vec<TtsCard>,
+}
+
+/// Builds the front image URL for a printing from its Scryfall id.
+pub fn scryfall_image_url(scryfall_id: &str) -> String {
+    let mut chars = scryfall_id.chars();
+    let a = chars.next().unwrap_or_default();
+    let b = chars.next().unwrap_or_default();
+    format!(
+        "https://cards.scryfall.io/large/front/{}/{}/{}.jpg",
+        a, b, scryfall_id
+    )
+}
+
+/// Turns a local image path into a URL TTS can load. URLs pass through as is.
+pub fn local_image_url(path: &str) -> String {
+    if path.contains("://") {
+        return path.to_string();
+    }
+    format!(
+        "file:///{}",
+        path.trim_start_matches('/').replace('\\', "/")
+    )
+}
+
+pub fn write_tts_saved_object(piles: &[TtsPile]) -> String {
+    let mut faces: Vec<&str> = Vec::new();
+    let mut objects = Vec::new();
+
+    for (i, pile) in piles.iter().filter(|p| !p.cards.is_empty()).enumerate() {
+        let transform = Transform::at(i as f32 * 2.5);
+        let mut cards = Vec::with_capacity(pile.cards.len());
+        let mut custom_deck = BTreeMap::new();
+
+        for card in &pile.cards {
+            let deck_id = match faces.iter().position(|f| *f == card.face_url) {
+                Some(index) => index + 1,
+                None => {
+                    faces.push(&card.face_url);
+                    faces.len()
+                }
+            };
+            custom_deck
+                .entry(deck_id.to_string())
+                .or_insert_with(|| CustomDeck::single(&card.face_url));
+            cards.push(ObjectState {
+                name: "Card",
+                nickname: card.name.clone(),
+                transform,
+                card_id: Some(deck_id * 100),
+                deck_ids: None,
+                custom_deck: None,
+                contained_objects: None,
+            });
+        }
+
+        let object = match cards.len() {
+            1 => {
+                let mut card = cards.remove(0);
+                card.custom_deck = Some(custom_deck);
+                card
+            }
+            _ => ObjectState {
+                name: "DeckCustom",
+                nickname: pile.name.clone(),
+                transform,
+                card_id: None,
+                deck_ids: Some(cards.iter().filter_map(|c| c.card_id).collect()),
+                custom_deck: Some(custom_deck),
+                contained_objects: Some(cards),
+            },
+        };
+        objects.push(object);
+    }
+
+    let save = SavedObject {
+        object_states: objects,
+    };
+    serde_json::to_string_pretty(&save).expect("saved object should always serialize")
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct SavedObject {
+    object_states: Vec<ObjectState>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct ObjectState {
+    name: &'static str,
+    nickname: String,
+    transform: Transform,
+    #[serde(rename = "CardID", skip_serializing_if = "Option::is_none")]
+    card_id: Option<usize>,
+    #[serde(rename = "DeckIDs", skip_serializing_if = "Option::is_none")]
+    deck_ids: Option<Vec<usize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    custom_deck: Option<BTreeMap<String, CustomDeck>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contained_objects: Option<Vec<ObjectState>>,
+}
+
+#[derive(Clone, Copy, Serialize)]
+struct Transform {
+    #[serde(rename = "posX")]
+    pos_x: f32,
+    #[serde(rename = "posY")]
+    pos_y: f32,
+    #[serde(rename = "posZ")]
+    pos_z: f32,
+    #[serde(rename = "rotX")]
+    rot_x: f32,
+    #[serde(rename = "rotY")]
+    rot_y: f32,
+    #[serde(rename = "rotZ")]
+    rot_z: f32,
+    #[serde(rename = "scaleX")]
+    scale_x: f32,
+    #[serde(rename = "scaleY")]
+    scale_y: f32,
+    #[serde(rename = "scaleZ")]
+    scale_z: f32,
+}
+
+impl Transform {
+    /// Face down, in a row along the x axis.
+    fn at(pos_x: f32) -> Self {
+        Transform {
+            pos_x,
+            pos_y: 1.0,
+            pos_z: 0.0,
+            rot_x: 0.0,
+            rot_y: 180.0,
+            rot_z: 180.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            scale_z: 1.0,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct CustomDeck {
+    #[serde(rename = "FaceURL")]
+    face_url: String,
+    #[serde(rename = "BackURL")]
+    back_url: String,
+    num_width: u32,
+    num_height: u32,
+    back_is_hidden: bool,
+    unique_back: bool,
+}
+
+impl CustomDeck {
+    fn single(face_url: &str) -> Self {
+        CustomDeck {
+            face_url: face_url.to_string(),
+            back_url: TTS_CARD_BACK_URL.to_string(),
+            num_width: 1,
+            num_height: 1,
+            back_is_hidden: true,
+            unique_back: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(name: &str, face_url: &str) -> TtsCard {
+        TtsCard {
+            name: name.into(),
+            face_url: face_url.into(),
+        }
+    }
+
+    #[test]
+    fn test_write_tts_saved_object() {
+        let piles = vec![
+            TtsPile {
+                name: "Commander".into(),
+                cards: vec![card("Atraxa, Praetors' Voice", "a.jpg")],
+            },
+            TtsPile {
+                name: "Main".into(),
+                cards: vec![
+                    card("Forest", "b.jpg"),
+                    card("Forest", "b.jpg"),
+                    card("Sol Ring", "c.jpg"),
+                ],
+            },
+            TtsPile {
+                name: "Sideboard".into(),
+                cards: vec![],
+            },
+        ];
+
+        let json: serde_json::Value =
+            serde_json::from_str(&write_tts_saved_object(&piles)).expect("output should be json");
+        let objects = json["ObjectStates"].as_array().expect("object states");
+
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0]["Name"], "Card");
+        assert_eq!(objects[0]["CardID"], 100);
+        assert_eq!(objects[1]["Name"], "DeckCustom");
+        assert_eq!(objects[1]["DeckIDs"], serde_json::json!([200, 200, 300]));
+        assert_eq!(objects[1]["CustomDeck"]["2"]["FaceURL"], "b.jpg");
+        assert_eq!(objects[1]["ContainedObjects"][2]["Nickname"], "Sol Ring");
+    }
+
+    #[test]
+    fn test_image_urls() {
+        assert_eq!(
+            scryfall_image_url("0aeebaf5-8c7d-4636-9e82-8c27447861f7"),
+            "https://cards.scryfall.io/large/front/0/a/0aeebaf5-8c7d-4636-9e82-8c27447861f7.jpg"
+        );
+        assert_eq!(
+            local_image_url("/home/me/art/bolt.png"),
+            "file:///home/me/art/bolt.png"
+        );
+        assert_eq!(
+            local_image_url("C:\\art\\bolt.png"),
+            "file:///C:/art/bolt.png"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use lotuslab_db::{BackupHeader, Store, read_backup_header};
+use lotuslab_types::{BackupInfo, BackupScope, BackupSettings};
+
+const BACKUP_EXTENSION: &str = "surql";
+const SETTINGS_FILE: &str = "settings.json";
+/// Automatic backups are named `auto-<timestamp>.surql`, rotation only ever
+/// deletes files with this prefix.
+const AUTOMATIC_PREFIX: &str = "auto-";
+
+pub async fn backup_database(
+    store: &Store,
+    path: &Path,
+    scope: BackupScope,
+) -> Result<BackupInfo, String> {
+    let header = store.backup(path, scope).await.map_err(|e| e.to_string())?;
+    backup_info(path, header)
+}
+
+/// Restores the backup at `path`, first saving the current user data to
+/// `backups_dir` so a restore can be undone.
+pub async fn restore_database(
+    store: &Store,
+    path: &Path,
+    backups_dir: &Path,
+) -> Result<BackupInfo, String> {
+    // Check the file before taking the safety backup, a bad file is the
+    // most likely failure.
+    read_backup_header(path).map_err(|e| e.to_string())?;
+
+    std::fs::create_dir_all(backups_dir).map_err(|e| e.to_string())?;
+    let safety = backups_dir.join(backup_file_name("pre-restore-"));
+    store
+        .backup(&safety, BackupScope::User)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let header = store.restore(path).await.map_err(|e| e.to_string())?;
+    backup_info(path, header)
+}
+
+/// Lists the backups in `backups_dir`, newest first. Files that aren't
+/// backups are skipped.
+pub fn list_backups(backups_dir: &Path) -> Result<Vec<BackupInfo>, String> {
+    let entries = match std::fs::read_dir(backups_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut backups = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_none_or(|ext| ext != BACKUP_EXTENSION) {
+            continue;
+        }
+        if let Ok(header) = read_backup_header(&path) {
+            backups.push(backup_info(&path, header)?);
+        }
+    }
+
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(backups)
+}
+
+pub fn get_backup_settings(backups_dir: &Path) -> Result<BackupSettings, String> {
+    match std::fs::read(backups_dir.join(SETTINGS_FILE)) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BackupSettings::default()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+pub fn set_backup_settings(backups_dir: &Path, settings: &BackupSettings) -> Result<(), String> {
+    if settings.interval_hours == 0 {
+        return Err("Backup interval must be at least one hour".to_string());
+    }
+    if settings.keep == 0 {
+        return Err("At least one automatic backup must be kept".to_string());
+    }
+
+    std::fs::create_dir_all(backups_dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(backups_dir.join(SETTINGS_FILE), json).map_err(|e| e.to_string())
+}
+
+/// Takes an automatic backup if they're enabled and the last one is older
+/// than the configured interval, then deletes automatic backups beyond the
+/// number to keep. Meant to be called periodically.
+pub async fn run_scheduled_backup(
+    store: &Store,
+    backups_dir: &Path,
+) -> Result<Option<BackupInfo>, String> {
+    let settings = get_backup_settings(backups_dir)?;
+    if !settings.enabled {
+        return Ok(None);
+    }
+
+    let automatic: Vec<BackupInfo> = list_backups(backups_dir)?
+        .into_iter()
+        .filter(|b| b.automatic)
+        .collect();
+
+    let due = Utc::now() - Duration::hours(settings.interval_hours as i64);
+    if automatic.first().is_some_and(|latest| {
+        DateTime::parse_from_rfc3339(&latest.created_at).is_ok_and(|created| created > due)
+    }) {
+        return Ok(None);
+    }
+
+    std::fs::create_dir_all(backups_dir).map_err(|e| e.to_string())?;
+    let path = backups_dir.join(backup_file_name(AUTOMATIC_PREFIX));
+    let created = backup_database(store, &path, settings.scope).await?;
+
+    // The new backup isn't in `automatic` yet, so keep one fewer of those.
+    for old in automatic
+        .iter()
+        .skip((settings.keep as usize).saturating_sub(1))
+    {
+        std::fs::remove_file(&old.path).map_err(|e| e.to_string())?;
+    }
+
+    Ok(Some(created))
+}
+
+fn backup_file_name(prefix: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "{}{}.{}",
+        prefix,
+        Utc::now().format("%Y%m%d-%H%M%S"),
+        BACKUP_EXTENSION
+    ))
+}
+
+fn backup_info(path: &Path, header: BackupHeader) -> Result<BackupInfo, String> {
+    let size_bytes = std::fs::metadata(path).map_err(|e| e.to_string())?.len();
+    let automatic = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with(AUTOMATIC_PREFIX));
+
+    Ok(BackupInfo {
+        path: path.to_string_lossy().into_owned(),
+        scope: header.scope,
+        schema_version: header.schema_version,
+        created_at: header.created_at.to_rfc3339(),
+        automatic,
+        size_bytes,
+    })
+}
@@ -0,0 +1,9 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+mod ruling;
+mod search;
+pub(crate) mod text;
+pub use self::ruling::*;
+pub use self::search::*;
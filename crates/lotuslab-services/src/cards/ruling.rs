@@ -0,0 +1,10 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use lotuslab_db::Store;
+use lotuslab_types::{CardCoreId, CardRuling, CardRulingReadRepo};
+
+pub async fn get_card_rulings(store: &Store, id: &CardCoreId) -> Result<Vec<CardRuling>, String> {
+    store.get_card_rulings(id).await.map_err(|e| e.to_string())
+}
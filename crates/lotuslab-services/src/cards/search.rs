@@ -0,0 +1,22 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use lotuslab_db::Store;
+use lotuslab_types::{CardCoreDetail, CardQuery, CardSearchRepo};
+
+/// Results returned when the caller doesn't ask for a limit.
+const DEFAULT_SEARCH_LIMIT: u32 = 100;
+
+/// Searches the card catalog with the search language, see [CardQuery].
+pub async fn search_cards(
+    store: &Store,
+    query: &str,
+    limit: Option<u32>,
+) -> Result<Vec<CardCoreDetail>, String> {
+    let query = CardQuery::parse(query).map_err(|e| e.to_string())?;
+    store
+        .search_cards(&query, limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,50 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! The parsed type line, mana cost and oracle text of a card.
+
+use std::collections::BTreeSet;
+
+use lotuslab_types::{CardCoreDetail, Color, ManaCost, ManaSymbol, OracleText, TypeLine};
+
+/// The front face's type line.
+pub(crate) fn type_line(card: &CardCoreDetail) -> TypeLine {
+    TypeLine::front(&card.type_line)
+}
+
+pub(crate) fn oracle_text(card: &CardCoreDetail) -> OracleText {
+    OracleText::parse(card.oracle_text.as_deref().unwrap_or_default())
+}
+
+/// The card's mana cost. Symbols that don't parse are left out.
+pub(crate) fn mana_cost(card: &CardCoreDetail) -> ManaCost {
+    ManaSymbol::scan(card.mana_cost.as_deref().unwrap_or_default()).collect()
+}
+
+/// Colors of mana a land taps for: the basic land types of its faces and
+/// what its abilities add. Basic land types a fetch land searches for count
+/// too.
+pub(crate) fn produced_colors(card: &CardCoreDetail) -> BTreeSet<Color> {
+    let oracle = oracle_text(card);
+    let mut colors = oracle.produced_colors();
+    for face in TypeLine::faces(&card.type_line) {
+        colors.extend(face.basic_land_colors());
+    }
+
+    for ability in &oracle.abilities {
+        if let Some((_, searched)) = ability.text.split_once("Search your library for") {
+            let searched = TypeLine {
+                subtypes: searched
+                    .replace([',', '.'], " ")
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+                ..TypeLine::default()
+            };
+            colors.extend(searched.basic_land_colors());
+        }
+    }
+
+    colors
+}
@@ -0,0 +1,389 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeSet, HashMap};
+
+use lotuslab_types::{
+    CardCoreDetail, CardCoreId, CardPrintingId, CardRulings, CatalogCardChange,
+    CatalogChangeReport, CatalogChanges, CatalogData, LegalityChange, OracleErrata, PrintingRemap,
+    RemovedPrinting,
+};
+
+/// Compares incoming catalog data to the stored catalog by id, returning the
+/// writes that bring the stored catalog up to date and a report of what
+/// changed for the user.
+pub(crate) fn diff_catalog(
+    old: CatalogData,
+    new: CatalogData,
+) -> (CatalogChanges, CatalogChangeReport) {
+    let mut changes = CatalogChanges::default();
+    let mut report = CatalogChangeReport::default();
+
+    let mut old_cards: HashMap<_, _> = old.cards.into_iter().map(|c| (c.id.clone(), c)).collect();
+    let card_names: HashMap<_, _> = old_cards
+        .values()
+        .chain(new.cards.iter())
+        .map(|c| (c.id.clone(), c.name.clone()))
+        .collect();
+
+    for card in new.cards {
+        match old_cards.remove(&card.id) {
+            None => {
+                report.new_cards.push(CatalogCardChange {
+                    card_core_id: card.id.clone(),
+                    name: card.name.clone(),
+                });
+                changes.upsert_cards.push(card);
+            }
+            Some(old_card) if old_card != card => {
+                if !report_card_update(&mut report, &old_card, &card) {
+                    report.other_updates += 1;
+                }
+                changes.upsert_cards.push(card);
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (id, card) in old_cards {
+        report.removed_cards.push(CatalogCardChange {
+            card_core_id: id.clone(),
+            name: card.name,
+        });
+        changes.delete_cards.push(id);
+    }
+
+    let mut old_printings: HashMap<_, _> = old
+        .printings
+        .into_iter()
+        .map(|p| (p.id.clone(), p))
+        .collect();
+
+    // Printings that only changed id, found by card, set and collector
+    // number, are moved rather than reported as removed and new.
+    let mut added: HashMap<(CardCoreId, String, String), CardPrintingId> = HashMap::new();
+    for printing in new.printings {
+        match old_printings.remove(&printing.id) {
+            None => {
+                report.new_printings += 1;
+                added.insert(
+                    (
+                        printing.card_core.clone(),
+                        printing.set_code.clone(),
+                        printing.collector_number.clone(),
+                    ),
+                    printing.id.clone(),
+                );
+                changes.upsert_printings.push(printing);
+            }
+            Some(old_printing) if old_printing != printing => {
+                changes.upsert_printings.push(printing);
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (id, printing) in old_printings {
+        let key = (
+            printing.card_core.clone(),
+            printing.set_code.clone(),
+            printing.collector_number.clone(),
+        );
+        if let Some(to) = added.remove(&key) {
+            report.new_printings -= 1;
+            changes.remap_printings.push(PrintingRemap {
+                from: id.clone(),
+                to,
+            });
+            changes.delete_printings.push(id);
+            continue;
+        }
+
+        report.removed_printings.push(RemovedPrinting {
+            card_printing_id: id.clone(),
+            name: card_names
+                .get(&printing.card_core)
+                .cloned()
+                .unwrap_or_default(),
+            set_code: printing.set_code,
+            collector_number: printing.collector_number,
+        });
+        changes.delete_printings.push(id);
+    }
+
+    // Sources without rulings leave the stored ones alone.
+    if let Some(new_rulings) = new.rulings {
+        let mut old_rulings: HashMap<_, _> = old
+            .rulings
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mut r| {
+                r.rulings.sort();
+                (r.card_core, r.rulings)
+            })
+            .collect();
+
+        for mut card in new_rulings {
+            card.rulings.sort();
+            if old_rulings.remove(&card.card_core).unwrap_or_default() != card.rulings {
+                report.ruling_updates += 1;
+                changes.replace_rulings.push(card);
+            }
+        }
+        for (card_core, _) in old_rulings {
+            changes.replace_rulings.push(CardRulings {
+                card_core,
+                rulings: Vec::new(),
+            });
+        }
+    }
+
+    report.new_cards.sort_by(|a, b| a.name.cmp(&b.name));
+    report.removed_cards.sort_by(|a, b| a.name.cmp(&b.name));
+    report.oracle_errata.sort_by(|a, b| a.name.cmp(&b.name));
+    report
+        .legality_changes
+        .sort_by(|a, b| a.format.cmp(&b.format).then_with(|| a.name.cmp(&b.name)));
+    report.removed_printings.sort_by(|a, b| {
+        (&a.set_code, &a.collector_number).cmp(&(&b.set_code, &b.collector_number))
+    });
+
+    (changes, report)
+}
+
+/// Adds errata and legality changes between two versions of a card to the
+/// report. Returns whether anything was added.
+fn report_card_update(
+    report: &mut CatalogChangeReport,
+    old: &CardCoreDetail,
+    new: &CardCoreDetail,
+) -> bool {
+    let mut reported = false;
+
+    if old.oracle_text != new.oracle_text {
+        report.oracle_errata.push(OracleErrata {
+            card_core_id: new.id.clone(),
+            name: new.name.clone(),
+            old_text: old.oracle_text.clone(),
+            new_text: new.oracle_text.clone(),
+        });
+        reported = true;
+    }
+
+    let formats: BTreeSet<&String> = old.legalities.keys().chain(new.legalities.keys()).collect();
+    for format in formats {
+        let (before, after) = (old.legalities.get(format), new.legalities.get(format));
+        if before != after {
+            report.legality_changes.push(LegalityChange {
+                card_core_id: new.id.clone(),
+                name: new.name.clone(),
+                format: format.clone(),
+                old: before.copied(),
+                new: after.copied(),
+            });
+            reported = true;
+        }
+    }
+
+    reported
+}
+
+#[cfg(test)]
+mod tests {
+    use lotuslab_types::{CardPrintingDetail, CardRuling, Legality, RulingSource};
+
+    use super::*;
+
+    fn card(id: &str, text: &str, commander: Legality) -> CardCoreDetail {
+        CardCoreDetail {
+            id: id.into(),
+            name: id.trim_start_matches("card_core:").into(),
+            mana_cost: None,
+            back_mana_costs: Vec::new(),
+            color_indicator: Vec::new(),
+            type_line: "Artifact".into(),
+            oracle_text: Some(text.into()),
+            legalities: [("commander".to_string(), commander)].into(),
+        }
+    }
+
+    fn rulings(card: &str, texts: &[&str]) -> CardRulings {
+        CardRulings {
+            card_core: card.into(),
+            rulings: texts
+                .iter()
+                .map(|text| CardRuling {
+                    date: "2025-01-01".into(),
+                    text: text.to_string(),
+                    source: RulingSource::Wotc,
+                })
+                .collect(),
+        }
+    }
+
+    fn printing(id: &str, card: &str, number: &str) -> CardPrintingDetail {
+        CardPrintingDetail {
+            id: id.into(),
+            card_core: card.into(),
+            set_code: "TST".into(),
+            collector_number: number.into(),
+            scryfall_id: None,
+            rarity: None,
+            price_usd: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_catalog() {
+        let old = CatalogData {
+            cards: vec![
+                card("card_core:sol-ring", "{T}: Add {C}{C}.", Legality::Legal),
+                card(
+                    "card_core:jeweled-lotus",
+                    "{T}, Sacrifice: Add three mana.",
+                    Legality::Legal,
+                ),
+                card("card_core:gone", "Nothing.", Legality::Legal),
+            ],
+            printings: vec![
+                printing("card_printing:1", "card_core:sol-ring", "1"),
+                printing("card_printing:2", "card_core:gone", "2"),
+            ],
+            rulings: Some(vec![
+                rulings("card_core:sol-ring", &["Colorless."]),
+                rulings("card_core:gone", &["Gone."]),
+            ]),
+        };
+        let new = CatalogData {
+            cards: vec![
+                card("card_core:sol-ring", "{T}: Add {C}{C}.", Legality::Legal),
+                card(
+                    "card_core:jeweled-lotus",
+                    "{T}, Sacrifice: Add three mana of one color.",
+                    Legality::Banned,
+                ),
+                card("card_core:new", "Something.", Legality::Legal),
+            ],
+            printings: vec![
+                printing("card_printing:1", "card_core:sol-ring", "1"),
+                printing("card_printing:3", "card_core:new", "3"),
+            ],
+            rulings: Some(vec![
+                rulings("card_core:sol-ring", &["Colorless."]),
+                rulings("card_core:new", &["New."]),
+            ]),
+        };
+
+        let (changes, report) = diff_catalog(old, new);
+
+        assert_eq!(changes.upsert_cards.len(), 2);
+        assert_eq!(changes.delete_cards, vec!["card_core:gone".into()]);
+        assert_eq!(changes.upsert_printings.len(), 1);
+        assert_eq!(changes.delete_printings, vec!["card_printing:2".into()]);
+        assert_eq!(
+            changes.replace_rulings,
+            vec![
+                rulings("card_core:new", &["New."]),
+                rulings("card_core:gone", &[])
+            ]
+        );
+
+        assert_eq!(report.new_cards[0].name, "new");
+        assert_eq!(report.removed_cards[0].name, "gone");
+        assert_eq!(report.oracle_errata[0].name, "jeweled-lotus");
+        assert_eq!(report.legality_changes[0].old, Some(Legality::Legal));
+        assert_eq!(report.legality_changes[0].new, Some(Legality::Banned));
+        assert_eq!(report.new_printings, 1);
+        assert_eq!(report.removed_printings[0].name, "gone");
+        assert_eq!(report.other_updates, 0);
+        assert_eq!(report.ruling_updates, 1);
+    }
+
+    #[test]
+    fn test_diff_catalog_with_uuid_keys() {
+        // Catalog sources key records by UUID, and the stored catalog hands
+        // the same `table:key` ids back.
+        let sol_ring = "card_core:a8b1f7a4-0b31-5c6f-9b0f-1f2d6a4c3e21";
+        let lotus = "card_core:0c3e8f2e-7d4b-5a1e-8c6d-3b9a2f1e4d57";
+        let old = CatalogData {
+            cards: vec![
+                card(sol_ring, "{T}: Add {C}{C}.", Legality::Legal),
+                card(lotus, "{T}, Sacrifice: Add three mana.", Legality::Legal),
+            ],
+            printings: vec![printing(
+                "card_printing:3d1e6c2b-9f4a-4e8b-a1c7-5b2d8e0f6a93",
+                sol_ring,
+                "1",
+            )],
+            rulings: Some(vec![rulings(sol_ring, &["Colorless."])]),
+        };
+        let mut new = old.clone();
+        new.cards[1] = card(lotus, "{T}, Sacrifice: Add three mana.", Legality::Banned);
+
+        let (changes, report) = diff_catalog(old.clone(), old.clone());
+        assert!(changes.is_empty());
+        assert_eq!(report, CatalogChangeReport::default());
+
+        let (changes, report) = diff_catalog(old, new);
+        assert_eq!(changes.upsert_cards.len(), 1);
+        assert!(changes.delete_cards.is_empty());
+        assert!(changes.upsert_printings.is_empty());
+        assert!(changes.delete_printings.is_empty());
+        assert!(changes.replace_rulings.is_empty());
+        assert!(report.new_cards.is_empty());
+        assert!(report.removed_cards.is_empty());
+        assert_eq!(report.legality_changes.len(), 1);
+    }
+
+    #[test]
+    fn test_diff_catalog_remaps_rekeyed_printings() {
+        let old = CatalogData {
+            cards: vec![card(
+                "card_core:sol-ring",
+                "{T}: Add {C}{C}.",
+                Legality::Legal,
+            )],
+            printings: vec![
+                printing("card_printing:mtgjson-1", "card_core:sol-ring", "1"),
+                printing("card_printing:mtgjson-2", "card_core:sol-ring", "2"),
+            ],
+            rulings: None,
+        };
+        let new = CatalogData {
+            cards: old.cards.clone(),
+            printings: vec![
+                printing("card_printing:scryfall-1", "card_core:sol-ring", "1"),
+                printing("card_printing:scryfall-3", "card_core:sol-ring", "3"),
+            ],
+            rulings: None,
+        };
+
+        let (changes, report) = diff_catalog(old, new);
+
+        assert_eq!(
+            changes.remap_printings,
+            vec![PrintingRemap {
+                from: "card_printing:mtgjson-1".into(),
+                to: "card_printing:scryfall-1".into(),
+            }]
+        );
+        assert_eq!(changes.upsert_printings.len(), 2);
+        assert_eq!(changes.delete_printings.len(), 2);
+        assert_eq!(report.new_printings, 1);
+        assert_eq!(report.removed_printings.len(), 1);
+        assert_eq!(report.removed_printings[0].collector_number, "2");
+    }
+
+    #[test]
+    fn test_diff_catalog_keeps_rulings_without_source_rulings() {
+        let old = CatalogData {
+            rulings: Some(vec![rulings("card_core:sol-ring", &["Colorless."])]),
+            ..Default::default()
+        };
+
+        let (changes, _) = diff_catalog(old, CatalogData::default());
+
+        assert!(changes.replace_rulings.is_empty());
+    }
+}
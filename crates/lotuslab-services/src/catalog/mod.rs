@@ -0,0 +1,63 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+mod diff;
+
+use std::path::Path;
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    CatalogReadRepo, CatalogUpdate, CatalogUpdateId, CatalogUpdateRepo, CatalogUpdateSummary,
+    CatalogWriteRepo,
+};
+
+use self::diff::diff_catalog;
+
+/// Updates the card catalog from an MTGJSON `AllPrintings` file or a Scryfall
+/// bulk data file, plain or compressed. The file is checked against its
+/// `.sha256` sidecar and fully parsed before the database is touched. Files
+/// without a sidecar, like Scryfall's, are refused unless
+/// `allow_missing_checksum` is set.
+///
+/// Only cards and printings that changed are written. What changed is kept
+/// as a report that can be looked at later with [get_catalog_update].
+pub async fn update_card_catalog(
+    store: &Store,
+    path: &Path,
+    allow_missing_checksum: bool,
+) -> Result<CatalogUpdateSummary, String> {
+    let incoming = lotuslab_import::read_card_catalog(path, allow_missing_checksum)
+        .map_err(|e| e.to_string())?;
+    let stored = store.catalog_snapshot().await.map_err(|e| e.to_string())?;
+
+    let (changes, report) = diff_catalog(stored, incoming.catalog);
+    if !changes.is_empty() {
+        store
+            .apply_catalog_changes(changes)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    store
+        .record_catalog_update(incoming.version, report)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn list_catalog_updates(store: &Store) -> Result<Vec<CatalogUpdateSummary>, String> {
+    store
+        .list_catalog_updates()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn get_catalog_update(
+    store: &Store,
+    id: &CatalogUpdateId,
+) -> Result<CatalogUpdate, String> {
+    store
+        .get_catalog_update(id)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,121 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use lotuslab_db::Store;
+use lotuslab_import::{
+    BUNDLE_VERSION, BundleArchive, BundleImage, BundleItem, BundleList, BundleTag, ProjectBundle,
+    bundle_image_path, write_project_bundle,
+};
+use lotuslab_types::{
+    ListItemReadRepo, ListReadRepo, ProjectId, ProjectReadRepo, TagId, TagReadRepo,
+};
+
+/// Writes a project to a self-contained `.lotus` bundle at `path`.
+///
+/// Custom images stored on disk are copied into the bundle, custom images
+/// that are already URLs are kept as links.
+pub async fn export_project_bundle(
+    store: &Store,
+    project_id: &ProjectId,
+    path: &Path,
+) -> Result<(), String> {
+    let project = store
+        .get_project(project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let lists = store
+        .lists_for_project(project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut tags: HashMap<TagId, BundleTag> = HashMap::new();
+    let mut images: Vec<BundleImage> = Vec::new();
+    let mut image_paths: HashMap<String, String> = HashMap::new();
+    let mut bundle_lists = Vec::with_capacity(lists.len());
+
+    for list in lists {
+        let items = store
+            .list_item_details_for_list(&list.id)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut bundle_items = Vec::with_capacity(items.len());
+
+        for item in items {
+            let mut tag_names = Vec::with_capacity(item.tags.len());
+            for tag_id in &item.tags {
+                if !tags.contains_key(tag_id) {
+                    let tag = store.get_tag(tag_id).await.map_err(|e| e.to_string())?;
+                    tags.insert(
+                        tag_id.clone(),
+                        BundleTag {
+                            name: tag.name,
+                            color: tag.color,
+                        },
+                    );
+                }
+                tag_names.push(tags[tag_id].name.clone());
+            }
+
+            let (custom_image, custom_image_url) = match item.custom_image_uri {
+                Some(uri) if uri.contains("://") => (None, Some(uri)),
+                Some(file) => {
+                    let archive_path = match image_paths.get(&file) {
+                        Some(archive_path) => archive_path.clone(),
+                        None => {
+                            let bytes = std::fs::read(&file).map_err(|e| {
+                                format!("Failed to read custom image {}: {}", file, e)
+                            })?;
+                            let archive_path = bundle_image_path(images.len(), &file);
+                            images.push(BundleImage {
+                                path: archive_path.clone(),
+                                bytes,
+                            });
+                            image_paths.insert(file, archive_path.clone());
+                            archive_path
+                        }
+                    };
+                    (Some(archive_path), None)
+                }
+                None => (None, None),
+            };
+
+            bundle_items.push(BundleItem {
+                commander: project.commanders.contains(&item.id),
+                card_core_id: item.card_core_id,
+                card_name: item.card_core_details.name,
+                selected_printing: item.selected_printing,
+                quantity: item.quantity,
+                notes: item.notes,
+                tags: tag_names,
+                custom_image,
+                custom_image_url,
+            });
+        }
+
+        bundle_lists.push(BundleList {
+            name: list.name,
+            role: list.role,
+            items: bundle_items,
+        });
+    }
+
+    let mut tags: Vec<BundleTag> = tags.into_values().collect();
+    tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let bytes = write_project_bundle(&BundleArchive {
+        bundle: ProjectBundle {
+            version: BUNDLE_VERSION,
+            name: project.name,
+            tags,
+            lists: bundle_lists,
+        },
+        images,
+    })
+    .map_err(|e| e.to_string())?;
+
+    std::fs::write(path, bytes).map_err(|e| e.to_string())
+}
@@ -0,0 +1,64 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use lotuslab_db::Store;
+use lotuslab_import::write_dck;
+use lotuslab_types::{
+    DckFormat, Decklist, DecklistEntry, DecklistSection, ListItemReadRepo, ListReadRepo, ListRole,
+    ProjectId, ProjectReadRepo,
+};
+
+/// Exports a project as a Forge or XMage `.dck` file.
+///
+/// Main lists go in the main deck and sideboard lists in the sideboard, the
+/// project's commanders go in the commander section. Maybeboards aren't part
+/// of the deck and are left out. Selected printings are kept as far as the
+/// format can hold them.
+pub async fn export_project_dck(
+    store: &Store,
+    project_id: &ProjectId,
+    format: DckFormat,
+) -> Result<String, String> {
+    let project = store
+        .get_project(project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let lists = store
+        .lists_for_project(project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut deck = Decklist {
+        name: Some(project.name),
+        entries: Vec::new(),
+    };
+    for list in lists {
+        let section = match list.role {
+            ListRole::Main => DecklistSection::Main,
+            ListRole::Sideboard => DecklistSection::Sideboard,
+            ListRole::Maybeboard => continue,
+        };
+        let items = store
+            .list_item_details_for_list(&list.id)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        for item in items {
+            let printing = item.card_printing_details.as_ref();
+            deck.entries.push(DecklistEntry {
+                section: match project.commanders.contains(&item.id) {
+                    true => DecklistSection::Commander,
+                    false => section,
+                },
+                quantity: item.quantity,
+                name: item.card_core_details.name,
+                set_code: printing.map(|p| p.set_code.clone()),
+                collector_number: printing.map(|p| p.collector_number.clone()),
+                art_index: None,
+            });
+        }
+    }
+
+    Ok(write_dck(&deck, format))
+}
@@ -0,0 +1,10 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+mod bundle;
+mod dck;
+mod tts;
+pub use self::bundle::*;
+pub use self::dck::*;
+pub use self::tts::*;
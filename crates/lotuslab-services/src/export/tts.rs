@@ -0,0 +1,80 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use lotuslab_db::Store;
+use lotuslab_import::{
+    TtsCard, TtsPile, local_image_url, scryfall_image_url, write_tts_saved_object,
+};
+use lotuslab_types::{ListItemDetail, ListItemReadRepo, ListReadRepo, ProjectId, ProjectReadRepo};
+
+/// Exports a project as a Tabletop Simulator saved object.
+///
+/// Each list becomes a pile, with the project's commanders pulled out into a
+/// pile of their own. Tokens aren't exported, the catalog has no token cards
+/// to take them from. Items need either a custom image or a selected printing
+/// with a Scryfall id.
+pub async fn export_project_tts(store: &Store, project_id: &ProjectId) -> Result<String, String> {
+    let project = store
+        .get_project(project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let lists = store
+        .lists_for_project(project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut commander = TtsPile {
+        name: "Commander".to_string(),
+        cards: Vec::new(),
+    };
+    let mut piles = Vec::with_capacity(lists.len());
+
+    for list in lists {
+        let items = store
+            .list_item_details_for_list(&list.id)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut pile = TtsPile {
+            name: list.name,
+            cards: Vec::new(),
+        };
+
+        for item in items {
+            let name = item.card_core_details.name.clone();
+            let face_url = face_url(&item).ok_or_else(|| {
+                format!(
+                    "'{}' has no image, select a printing or set a custom image",
+                    name
+                )
+            })?;
+
+            let target = match project.commanders.contains(&item.id) {
+                true => &mut commander,
+                false => &mut pile,
+            };
+            for _ in 0..item.quantity {
+                target.cards.push(TtsCard {
+                    name: name.clone(),
+                    face_url: face_url.clone(),
+                });
+            }
+        }
+
+        piles.push(pile);
+    }
+
+    piles.insert(0, commander);
+    Ok(write_tts_saved_object(&piles))
+}
+
+fn face_url(item: &ListItemDetail) -> Option<String> {
+    if let Some(path) = &item.custom_image_uri {
+        return Some(local_image_url(path));
+    }
+
+    item.card_printing_details
+        .as_ref()
+        .and_then(|p| p.scryfall_id.as_deref())
+        .map(scryfall_image_url)
+}
@@ -0,0 +1,573 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Commander deck construction: who can lead a deck, which commanders can
+//! share it, color identity and companions.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use lotuslab_types::{
+    CardCoreDetail, Color, DeckViolation, FormatRules, ListItemDetail, ListItemId, ManaSymbol,
+    TypeLine,
+};
+
+use crate::cards::text::{mana_cost, oracle_text, type_line};
+
+/// The colors of every mana symbol in the mana costs and rules text of all
+/// of a card's faces, reminder text aside, plus its color indicators and the
+/// colors of its basic land types.
+pub fn color_identity(card: &CardCoreDetail) -> BTreeSet<Color> {
+    let mut identity: BTreeSet<Color> = mana_cost(card).colors();
+    for cost in &card.back_mana_costs {
+        identity.extend(ManaSymbol::scan(cost).flat_map(|symbol| symbol.colors()));
+    }
+    identity.extend(card.color_indicator.iter().copied());
+    for ability in oracle_text(card).abilities {
+        identity.extend(ManaSymbol::scan(&ability.text).flat_map(|symbol| symbol.colors()));
+    }
+
+    // Basic land types grant a mana ability even when the text only shows
+    // it as reminder text.
+    for face in TypeLine::faces(&card.type_line) {
+        identity.extend(face.basic_land_colors());
+    }
+
+    identity
+}
+
+/// Checks the commander specific rules. `commanders` are the ids of the
+/// project's commander items, which sit in the main deck.
+pub(crate) fn check_commander_deck(
+    rules: &FormatRules,
+    main: &[ListItemDetail],
+    sideboard: &[ListItemDetail],
+    commanders: &[ListItemId],
+) -> Vec<DeckViolation> {
+    let mut violations = Vec::new();
+    let commanders: Vec<&ListItemDetail> = main
+        .iter()
+        .filter(|item| commanders.contains(&item.id))
+        .collect();
+    let cards: Vec<&CardCoreDetail> = commanders.iter().map(|c| &c.card_core_details).collect();
+
+    match cards.as_slice() {
+        [] => violations.push(DeckViolation::MissingCommander),
+        [commander] => {
+            if !can_be_commander(commander) {
+                violations.push(not_a_commander(commanders[0]));
+            }
+        }
+        [a, b] => match pair_kind(a, b) {
+            Some(Pair::Background { background }) => {
+                let leader = if background == 0 { 1 } else { 0 };
+                if !can_be_commander(cards[leader]) {
+                    violations.push(not_a_commander(commanders[leader]));
+                }
+            }
+            Some(Pair::Partners) => {
+                for (card, item) in cards.iter().zip(&commanders) {
+                    if !can_be_commander(card) {
+                        violations.push(not_a_commander(item));
+                    }
+                }
+            }
+            None => violations.push(invalid_pair(&commanders)),
+        },
+        _ => violations.push(invalid_pair(&commanders)),
+    }
+
+    // Color identity, grouped by card so every copy is reported once. Without
+    // a commander there's no identity to check against.
+    let allowed: BTreeSet<Color> = cards.iter().flat_map(|c| color_identity(c)).collect();
+    let checked: Vec<&ListItemDetail> = match cards.is_empty() {
+        true => Vec::new(),
+        false => main.iter().chain(sideboard).collect(),
+    };
+    let mut outside: BTreeMap<&str, (&CardCoreDetail, Vec<Color>, Vec<ListItemId>)> =
+        BTreeMap::new();
+    for item in checked {
+        let card = &item.card_core_details;
+        let colors: Vec<Color> = color_identity(card).difference(&allowed).copied().collect();
+        if !colors.is_empty() {
+            outside
+                .entry(card.name.as_str())
+                .or_insert_with(|| (card, colors, Vec::new()))
+                .2
+                .push(item.id.clone());
+        }
+    }
+    for (card, colors, items) in outside.into_values() {
+        violations.push(DeckViolation::OutsideColorIdentity {
+            card_core_id: card.id.clone(),
+            name: card.name.clone(),
+            colors,
+            items,
+        });
+    }
+
+    for item in sideboard {
+        let card = &item.card_core_details;
+        match companion_condition(card) {
+            None => violations.push(DeckViolation::NotACompanion {
+                card_core_id: card.id.clone(),
+                name: card.name.clone(),
+                items: vec![item.id.clone()],
+            }),
+            Some(condition) => {
+                if let Some(items) = companion_offenders(card, rules, main) {
+                    violations.push(DeckViolation::CompanionConditionNotMet {
+                        card_core_id: card.id.clone(),
+                        name: card.name.clone(),
+                        condition,
+                        items,
+                    });
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+fn not_a_commander(item: &ListItemDetail) -> DeckViolation {
+    DeckViolation::NotACommander {
+        card_core_id: item.card_core_id.clone(),
+        name: item.card_core_details.name.clone(),
+        items: vec![item.id.clone()],
+    }
+}
+
+fn invalid_pair(commanders: &[&ListItemDetail]) -> DeckViolation {
+    DeckViolation::InvalidCommanderPair {
+        names: commanders
+            .iter()
+            .map(|c| c.card_core_details.name.clone())
+            .collect(),
+        items: commanders.iter().map(|c| c.id.clone()).collect(),
+    }
+}
+
+fn can_be_commander(card: &CardCoreDetail) -> bool {
+    let line = type_line(card);
+    (line.has_supertype("Legendary") && line.has_card_type("Creature"))
+        || card
+            .oracle_text
+            .as_deref()
+            .is_some_and(|text| text.contains("can be your commander"))
+}
+
+enum Pair {
+    /// Two commanders that may each lead the deck.
+    Partners,
+    /// A commander with Choose a Background and the Background at `index`.
+    Background { background: usize },
+}
+
+/// How two commanders are allowed to share a deck, if at all.
+fn pair_kind(a: &CardCoreDetail, b: &CardCoreDetail) -> Option<Pair> {
+    let (a_lines, b_lines) = (ability_lines(a), ability_lines(b));
+    let has = |lines: &[String], keyword: &str| {
+        lines.iter().any(|line| {
+            line.split(", ")
+                .any(|item| item.trim().eq_ignore_ascii_case(keyword))
+        })
+    };
+    let partner_with = |lines: &[String], other: &CardCoreDetail| {
+        lines.iter().any(|line| {
+            line.strip_prefix("Partner with ")
+                .is_some_and(|name| name.trim() == other.name)
+        })
+    };
+    let partner_group = |lines: &[String]| -> Option<String> {
+        lines
+            .iter()
+            .find_map(|line| line.strip_prefix("Partner—").map(|g| g.trim().to_string()))
+    };
+    let is_background = |card: &CardCoreDetail| type_line(card).has_subtype("Background");
+    let is_doctor = |card: &CardCoreDetail| {
+        let line = type_line(card);
+        line.has_subtype("Time Lord") && line.has_subtype("Doctor")
+    };
+
+    if (has(&a_lines, "Partner") && has(&b_lines, "Partner"))
+        || (partner_with(&a_lines, b) && partner_with(&b_lines, a))
+        || (has(&a_lines, "Friends forever") && has(&b_lines, "Friends forever"))
+        || (has(&a_lines, "Doctor's companion") && is_doctor(b))
+        || (has(&b_lines, "Doctor's companion") && is_doctor(a))
+    {
+        return Some(Pair::Partners);
+    }
+    if partner_group(&a_lines).is_some_and(|group| partner_group(&b_lines) == Some(group)) {
+        return Some(Pair::Partners);
+    }
+    if has(&a_lines, "Choose a Background") && is_background(b) {
+        return Some(Pair::Background { background: 1 });
+    }
+    if has(&b_lines, "Choose a Background") && is_background(a) {
+        return Some(Pair::Background { background: 0 });
+    }
+
+    None
+}
+
+/// Oracle text lines of the card with reminder text removed.
+fn ability_lines(card: &CardCoreDetail) -> Vec<String> {
+    oracle_text(card)
+        .abilities
+        .into_iter()
+        .map(|ability| ability.text)
+        .collect()
+}
+
+/// The deckbuilding condition of a companion, `None` if the card isn't one.
+fn companion_condition(card: &CardCoreDetail) -> Option<String> {
+    ability_lines(card).into_iter().find_map(|line| {
+        line.strip_prefix("Companion — ")
+            .map(|condition| condition.to_string())
+    })
+}
+
+/// The starting deck items that break a companion's condition, `None` when
+/// it holds. Companions this doesn't know are assumed to be satisfied.
+fn companion_offenders(
+    companion: &CardCoreDetail,
+    rules: &FormatRules,
+    main: &[ListItemDetail],
+) -> Option<Vec<ListItemId>> {
+    let nonland = || {
+        main.iter()
+            .filter(|item| !type_line(&item.card_core_details).has_card_type("Land"))
+    };
+    let permanent = || {
+        main.iter()
+            .filter(|item| type_line(&item.card_core_details).is_permanent())
+    };
+    let offenders = |items: Vec<&ListItemDetail>| items.into_iter().map(|i| i.id.clone()).collect();
+
+    let offending: Vec<ListItemId> = match companion.name.as_str() {
+        "Gyruda, Doom of Depths" => offenders(
+            main.iter()
+                .filter(|i| {
+                    !mana_cost(&i.card_core_details)
+                        .mana_value()
+                        .is_multiple_of(2)
+                })
+                .collect(),
+        ),
+        "Jegantha, the Wellspring" => offenders(
+            main.iter()
+                .filter(|i| {
+                    let symbols = mana_cost(&i.card_core_details).symbols;
+                    let unique: HashSet<&ManaSymbol> = symbols.iter().collect();
+                    unique.len() < symbols.len()
+                })
+                .collect(),
+        ),
+        "Kaheera, the Orphanguard" => offenders(
+            main.iter()
+                .filter(|i| {
+                    let line = type_line(&i.card_core_details);
+                    line.has_card_type("Creature")
+                        && !["Cat", "Elemental", "Nightmare", "Dinosaur", "Beast"]
+                            .iter()
+                            .any(|kind| line.has_subtype(kind))
+                })
+                .collect(),
+        ),
+        "Keruga, the Macrosage" => offenders(
+            nonland()
+                .filter(|i| mana_cost(&i.card_core_details).mana_value() < 3)
+                .collect(),
+        ),
+        "Lurrus of the Dream-Den" => offenders(
+            permanent()
+                .filter(|i| mana_cost(&i.card_core_details).mana_value() > 2)
+                .collect(),
+        ),
+        "Lutri, the Spellchaser" => {
+            let mut counts: BTreeMap<&str, u32> = BTreeMap::new();
+            for item in nonland() {
+                *counts.entry(&item.card_core_details.name).or_default() +=
+                    item.quantity.max(0) as u32;
+            }
+            offenders(
+                nonland()
+                    .filter(|i| counts[i.card_core_details.name.as_str()] > 1)
+                    .collect(),
+            )
+        }
+        "Obosh, the Preypiercer" => offenders(
+            nonland()
+                .filter(|i| {
+                    mana_cost(&i.card_core_details)
+                        .mana_value()
+                        .is_multiple_of(2)
+                })
+                .collect(),
+        ),
+        "Umori, the Collector" => {
+            // Keep the most common card type and report everything else.
+            let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+            for item in nonland() {
+                for card_type in type_line(&item.card_core_details).card_types {
+                    *counts.entry(card_type).or_default() += 1;
+                }
+            }
+            let shared = counts
+                .into_iter()
+                .max_by_key(|(_, count)| *count)
+                .map(|(card_type, _)| card_type);
+            offenders(
+                nonland()
+                    .filter(|i| {
+                        shared
+                            .as_ref()
+                            .is_none_or(|t| !type_line(&i.card_core_details).has_card_type(t))
+                    })
+                    .collect(),
+            )
+        }
+        "Yorion, Sky Nomad" => {
+            let size: u32 = main.iter().map(|i| i.quantity.max(0) as u32).sum();
+            match size >= rules.min_deck_size.unwrap_or_default() + 20 {
+                true => Vec::new(),
+                false => return Some(Vec::new()),
+            }
+        }
+        "Zirda, the Dawnwaker" => offenders(
+            permanent()
+                .filter(|i| !has_activated_ability(&i.card_core_details))
+                .collect(),
+        ),
+        _ => Vec::new(),
+    };
+
+    match offending.is_empty() {
+        true => None,
+        false => Some(offending),
+    }
+}
+
+/// Keyword abilities that are activated despite having no colon.
+const ACTIVATED_KEYWORDS: &[&str] = &[
+    "Craft",
+    "Crew",
+    "Cycling",
+    "Embalm",
+    "Equip",
+    "Eternalize",
+    "Fortify",
+    "Level up",
+    "Ninjutsu",
+    "Outlast",
+    "Reconfigure",
+    "Scavenge",
+    "Transfigure",
+    "Transmute",
+    "Unearth",
+];
+
+fn has_activated_ability(card: &CardCoreDetail) -> bool {
+    // Reminder text is kept, basic lands only show their mana ability there.
+    card.oracle_text
+        .as_deref()
+        .unwrap_or_default()
+        .lines()
+        .any(|line| line.contains(':') || ACTIVATED_KEYWORDS.iter().any(|k| line.starts_with(k)))
+}
+
+#[cfg(test)]
+mod tests {
+    use lotuslab_types::Format;
+
+    use super::*;
+    use crate::format::validate::tests::{card, item};
+
+    fn commander(name: &str, type_line: &str, mana_cost: &str, text: &str) -> CardCoreDetail {
+        let mut card = card(name, type_line, text);
+        card.mana_cost = Some(mana_cost.into());
+        card
+    }
+
+    fn check(
+        main: &[ListItemDetail],
+        sideboard: &[ListItemDetail],
+        commanders: usize,
+    ) -> Vec<DeckViolation> {
+        let ids: Vec<ListItemId> = main[..commanders].iter().map(|i| i.id.clone()).collect();
+        check_commander_deck(&Format::Commander.rules(), main, sideboard, &ids)
+    }
+
+    #[test]
+    fn test_color_identity() {
+        let hybrid = commander(
+            "Kitchen Finks",
+            "Creature — Ouphe",
+            "{1}{G/W}{G/W}",
+            "Persist (When this creature dies, put it onto the battlefield. {B})",
+        );
+        let activated = commander(
+            "Alesha, Who Smiles at Death",
+            "Legendary Creature — Human Warrior",
+            "{2}{R}",
+            "Whenever Alesha attacks, you may pay {W/B}{W/B}.",
+        );
+        let tundra = commander(
+            "Tundra",
+            "Land — Plains Island",
+            "",
+            "({T}: Add {W} or {U}.)",
+        );
+
+        assert_eq!(
+            color_identity(&hybrid),
+            BTreeSet::from([Color::White, Color::Green])
+        );
+        assert_eq!(
+            color_identity(&activated),
+            BTreeSet::from([Color::White, Color::Black, Color::Red])
+        );
+        assert_eq!(
+            color_identity(&tundra),
+            BTreeSet::from([Color::White, Color::Blue])
+        );
+    }
+
+    #[test]
+    fn test_color_identity_of_every_face() {
+        let mut valki = commander(
+            "Valki, God of Lies // Tibalt, Cosmic Impostor",
+            "Legendary Creature — God // Legendary Planeswalker — Tibalt",
+            "{1}{B}",
+            "",
+        );
+        valki.back_mana_costs = vec!["{5}{B}{R}".into()];
+        let mut arbor = commander("Dryad Arbor", "Land Creature — Forest Dryad", "", "");
+        arbor.color_indicator = vec![Color::Green];
+        let mut ancestral_vision = commander("Ancestral Vision", "Sorcery", "", "");
+        ancestral_vision.color_indicator = vec![Color::Blue];
+
+        assert_eq!(
+            color_identity(&valki),
+            BTreeSet::from([Color::Black, Color::Red])
+        );
+        assert_eq!(color_identity(&arbor), BTreeSet::from([Color::Green]));
+        assert_eq!(
+            color_identity(&ancestral_vision),
+            BTreeSet::from([Color::Blue])
+        );
+    }
+
+    #[test]
+    fn test_check_commander_deck_identity() {
+        let krenko = commander(
+            "Krenko, Mob Boss",
+            "Legendary Creature — Goblin Warrior",
+            "{2}{R}{R}",
+            "{T}: Create X 1/1 red Goblin creature tokens.",
+        );
+        let bolt = commander("Lightning Bolt", "Instant", "{R}", "Deal 3 damage.");
+        let counterspell = commander("Counterspell", "Instant", "{U}{U}", "Counter target spell.");
+        let main = vec![item(&krenko, 1), item(&bolt, 1), item(&counterspell, 1)];
+
+        assert_eq!(
+            check(&main, &[], 1),
+            vec![DeckViolation::OutsideColorIdentity {
+                card_core_id: counterspell.id.clone(),
+                name: counterspell.name.clone(),
+                colors: vec![Color::Blue],
+                items: vec![main[2].id.clone()],
+            }]
+        );
+        assert_eq!(check(&main, &[], 0)[0], DeckViolation::MissingCommander);
+    }
+
+    #[test]
+    fn test_check_commander_deck_pairs() {
+        let partner = |name: &str| {
+            commander(
+                name,
+                "Legendary Creature — Human",
+                "{1}{W}",
+                "Partner (You can have two commanders if both have partner.)",
+            )
+        };
+        let solo = commander("Krenko, Mob Boss", "Legendary Creature — Goblin", "{R}", "");
+        let wilson = commander(
+            "Wilson, Refined Grizzly",
+            "Legendary Creature — Bear Warrior",
+            "{1}{G}",
+            "Choose a Background (You can have a Background as a second commander.)",
+        );
+        let background = commander(
+            "Raised by Giants",
+            "Legendary Enchantment — Background",
+            "{5}{G}",
+            "Commander creatures you own have base power and toughness 10/10.",
+        );
+        let doctor = commander(
+            "The Tenth Doctor",
+            "Legendary Creature — Time Lord Doctor",
+            "{2}{U}{R}",
+            "",
+        );
+        let rose = commander(
+            "Rose Tyler",
+            "Legendary Creature — Human",
+            "{1}{W}",
+            "Doctor's companion (You can have two commanders if the other is the Doctor.)",
+        );
+
+        let ok = |a: &CardCoreDetail, b: &CardCoreDetail| {
+            check(&[item(a, 1), item(b, 1)], &[], 2).is_empty()
+        };
+        assert!(ok(&partner("Thrasios"), &partner("Tymna")));
+        assert!(ok(&wilson, &background));
+        assert!(ok(&rose, &doctor));
+        assert!(!ok(&solo, &partner("Tymna")));
+        assert!(!ok(&background, &solo));
+
+        assert!(matches!(
+            check(&[item(&background, 1)], &[], 1).as_slice(),
+            [DeckViolation::NotACommander { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_check_commander_deck_companion() {
+        let krenko = commander(
+            "Krenko, Mob Boss",
+            "Legendary Creature — Goblin",
+            "{2}{R}{R}",
+            "",
+        );
+        let lurrus = commander(
+            "Lurrus of the Dream-Den",
+            "Legendary Creature — Cat Nightmare",
+            "{1}{W/B}{W/B}",
+            "Companion — Each permanent card in your starting deck has mana value 2 or less.",
+        );
+        let bolt = commander("Lightning Bolt", "Instant", "{R}", "Deal 3 damage.");
+
+        let violations = check(
+            &[item(&krenko, 1), item(&bolt, 1)],
+            &[item(&lurrus, 1), item(&bolt, 1)],
+            1,
+        );
+
+        assert!(violations.iter().any(|v| matches!(
+            v,
+            DeckViolation::CompanionConditionNotMet { items, .. } if items.len() == 1
+        )));
+        assert!(
+            violations
+                .iter()
+                .any(|v| matches!(v, DeckViolation::NotACompanion { .. }))
+        );
+        assert!(violations.iter().any(|v| matches!(
+            v,
+            DeckViolation::OutsideColorIdentity { colors, .. } if colors == &vec![Color::White, Color::Black]
+        )));
+    }
+}
@@ -0,0 +1,77 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::Path;
+
+use lotuslab_db::Store;
+use lotuslab_import::{read_format_file, write_format_file};
+use lotuslab_types::{
+    CustomFormat, CustomFormatId, CustomFormatReadRepo, CustomFormatWriteRepo, FormatRef,
+    FormatRules,
+};
+
+pub async fn list_custom_formats(store: &Store) -> Result<Vec<CustomFormat>, String> {
+    store.list_custom_formats().await.map_err(|e| e.to_string())
+}
+
+pub async fn create_custom_format(
+    store: &Store,
+    rules: FormatRules,
+) -> Result<CustomFormat, String> {
+    store
+        .create_custom_format(rules)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn update_custom_format(
+    store: &Store,
+    id: &CustomFormatId,
+    rules: FormatRules,
+) -> Result<CustomFormat, String> {
+    store
+        .update_custom_format(id, rules)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn delete_custom_format(store: &Store, id: &CustomFormatId) -> Result<(), String> {
+    store
+        .delete_custom_format(id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Adds the format in a format file to the database.
+pub async fn import_custom_format(store: &Store, path: &Path) -> Result<CustomFormat, String> {
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    let rules = read_format_file(&bytes).map_err(|e| e.to_string())?;
+    create_custom_format(store, rules).await
+}
+
+/// Writes a custom format to a format file at `path`.
+pub async fn export_custom_format(
+    store: &Store,
+    id: &CustomFormatId,
+    path: &Path,
+) -> Result<(), String> {
+    let format = store
+        .get_custom_format(id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let bytes = write_format_file(&format.rules).map_err(|e| e.to_string())?;
+    std::fs::write(path, bytes).map_err(|e| e.to_string())
+}
+
+/// The rules of an official or custom format.
+pub(crate) async fn format_rules(store: &Store, format: &FormatRef) -> Result<FormatRules, String> {
+    match format {
+        FormatRef::Official(format) => Ok(format.rules()),
+        FormatRef::Custom(id) => store
+            .get_custom_format(id)
+            .await
+            .map(|format| format.rules)
+            .map_err(|e| e.to_string()),
+    }
+}
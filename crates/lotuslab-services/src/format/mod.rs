@@ -0,0 +1,100 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+mod commander;
+mod custom;
+mod points;
+mod validate;
+
+use std::collections::HashMap;
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    CardCoreId, CardPrintingDetail, CardPrintingReadRepo, DeckValidation, Format, FormatRef,
+    FormatRules, ListItemDetail, ListItemReadRepo, ListReadRepo, ListRole, ProjectId,
+    ProjectReadRepo,
+};
+
+pub use self::commander::color_identity;
+pub use self::custom::*;
+pub use self::points::*;
+pub use self::validate::*;
+
+pub fn list_formats() -> Vec<FormatRules> {
+    Format::ALL.iter().map(|f| f.rules()).collect()
+}
+
+/// Checks a project against an official or custom format. Every list of the
+/// project is part of the main deck except the sideboard and maybeboard, by
+/// their [ListRole].
+///
+/// Nothing is cached, callers re-run this whenever one of the project's
+/// lists changes to keep the result up to date.
+pub async fn validate_project(
+    store: &Store,
+    project_id: &ProjectId,
+    format: &FormatRef,
+) -> Result<DeckValidation, String> {
+    let rules = format_rules(store, format).await?;
+    let project = store
+        .get_project(project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let (main, sideboard) = project_deck(store, project_id).await?;
+
+    let mut printings: HashMap<CardCoreId, Vec<CardPrintingDetail>> = HashMap::new();
+    if rules.restricts_printings() {
+        let ids: Vec<CardCoreId> = main
+            .iter()
+            .chain(&sideboard)
+            .map(|item| item.card_core_id.clone())
+            .collect();
+        for printing in store
+            .card_printings_for_cards(&ids)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            printings
+                .entry(printing.card_core.clone())
+                .or_default()
+                .push(printing);
+        }
+    }
+
+    Ok(validate_deck(
+        &rules,
+        &main,
+        &sideboard,
+        &project.commanders,
+        &printings,
+    ))
+}
+
+/// Reads the items of a project's lists, split into main deck and sideboard.
+pub(crate) async fn project_deck(
+    store: &Store,
+    project_id: &ProjectId,
+) -> Result<(Vec<ListItemDetail>, Vec<ListItemDetail>), String> {
+    let lists = store
+        .lists_for_project(project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut main = Vec::new();
+    let mut sideboard = Vec::new();
+    for list in lists {
+        let target = match list.role {
+            ListRole::Main => &mut main,
+            ListRole::Sideboard => &mut sideboard,
+            ListRole::Maybeboard => continue,
+        };
+        let items = store
+            .list_item_details_for_list(&list.id)
+            .await
+            .map_err(|e| e.to_string())?;
+        target.extend(items);
+    }
+
+    Ok((main, sideboard))
+}
@@ -0,0 +1,110 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use lotuslab_db::Store;
+use lotuslab_import::parse_points_csv;
+use lotuslab_types::{
+    CardPoints, CustomFormat, CustomFormatId, CustomFormatReadRepo, CustomFormatWriteRepo,
+    FormatRef, ListItemReadRepo, ListPoints, ListReadRepo, ListRole, PointsTable, ProjectId,
+    ProjectPoints,
+};
+
+use super::{custom::format_rules, validate::quantity};
+
+/// Running point totals of a project's lists under a points format.
+pub async fn project_points(
+    store: &Store,
+    project_id: &ProjectId,
+    format: &FormatRef,
+) -> Result<ProjectPoints, String> {
+    let rules = format_rules(store, format).await?;
+    let Some(table) = rules.points else {
+        return Err(format!("{} has no points table", rules.name));
+    };
+    let lists = store
+        .lists_for_project(project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut total = 0;
+    let mut list_points = Vec::with_capacity(lists.len());
+    for list in lists {
+        let items = store
+            .list_item_details_for_list(&list.id)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        // The same card can sit in a list more than once with different
+        // printings, it's counted as one entry.
+        let mut cards: BTreeMap<&str, CardPoints> = BTreeMap::new();
+        for item in &items {
+            let card = &item.card_core_details;
+            let points = table.cost(&card.name);
+            if points == 0 {
+                continue;
+            }
+            cards
+                .entry(card.name.as_str())
+                .or_insert_with(|| CardPoints {
+                    card_core_id: card.id.clone(),
+                    name: card.name.clone(),
+                    quantity: 0,
+                    points,
+                })
+                .quantity += quantity(item);
+        }
+
+        let mut cards: Vec<CardPoints> = cards.into_values().collect();
+        cards.sort_by(|a, b| b.points.cmp(&a.points).then_with(|| a.name.cmp(&b.name)));
+        let points = cards.iter().map(|c| c.points * c.quantity).sum();
+        if list.role != ListRole::Maybeboard {
+            total += points;
+        }
+
+        list_points.push(ListPoints {
+            list_id: list.id,
+            name: list.name,
+            points,
+            cards,
+        });
+    }
+
+    Ok(ProjectPoints {
+        format: rules.name,
+        cap: table.cap,
+        total,
+        lists: list_points,
+    })
+}
+
+/// Replaces a custom format's points with a `Card Name,points` CSV. The cap
+/// stays as it is unless `cap` is given, formats without a points table yet
+/// need one.
+pub async fn import_points_csv(
+    store: &Store,
+    id: &CustomFormatId,
+    path: &Path,
+    cap: Option<u32>,
+) -> Result<CustomFormat, String> {
+    let input = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let points = parse_points_csv(&input).map_err(|e| e.to_string())?;
+
+    let mut rules = store
+        .get_custom_format(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .rules;
+    let Some(cap) = cap.or(rules.points.as_ref().map(|table| table.cap)) else {
+        return Err(format!("{} needs a points cap", rules.name));
+    };
+    rules.points = Some(PointsTable { cap, points });
+
+    store
+        .update_custom_format(id, rules)
+        .await
+        .map_err(|e| e.to_string())
+}
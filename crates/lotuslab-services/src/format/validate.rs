@@ -0,0 +1,544 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeMap, HashMap};
+
+use lotuslab_types::{
+    CardCoreDetail, CardCoreId, CardPrintingDetail, DeckValidation, DeckViolation, FormatRules,
+    Legality, ListItemDetail, ListItemId,
+};
+
+use crate::format::commander::check_commander_deck;
+
+/// Checks a deck against a format's construction rules. `commanders` are the
+/// ids of the commander items in `main`, only looked at by commander formats.
+/// `printings` holds every printing of the deck's cards when the rules
+/// [restrict printings](FormatRules::restricts_printings).
+pub fn validate_deck(
+    rules: &FormatRules,
+    main: &[ListItemDetail],
+    sideboard: &[ListItemDetail],
+    commanders: &[ListItemId],
+    printings: &HashMap<CardCoreId, Vec<CardPrintingDetail>>,
+) -> DeckValidation {
+    let sideboard = match rules.max_sideboard_size {
+        Some(_) => sideboard,
+        None => &[],
+    };
+    let count = |items: &[ListItemDetail]| items.iter().map(quantity).sum::<u32>();
+    let deck_size = count(main);
+    let sideboard_size = count(sideboard);
+
+    let mut violations = Vec::new();
+    if let Some(min) = rules.min_deck_size.filter(|min| deck_size < *min) {
+        violations.push(DeckViolation::DeckTooSmall {
+            min,
+            actual: deck_size,
+        });
+    }
+    if let Some(max) = rules.max_deck_size.filter(|max| deck_size > *max) {
+        violations.push(DeckViolation::DeckTooLarge {
+            max,
+            actual: deck_size,
+        });
+    }
+    if let Some(max) = rules.max_sideboard_size.filter(|max| sideboard_size > *max) {
+        violations.push(DeckViolation::SideboardTooLarge {
+            max,
+            actual: sideboard_size,
+        });
+    }
+
+    let points = rules.points.as_ref().map(|table| {
+        let costly: Vec<&ListItemDetail> = main
+            .iter()
+            .chain(sideboard)
+            .filter(|item| table.cost(&item.card_core_details.name) > 0)
+            .collect();
+        let spent = costly
+            .iter()
+            .map(|item| table.cost(&item.card_core_details.name) * quantity(item))
+            .sum::<u32>();
+        if spent > table.cap {
+            violations.push(DeckViolation::OverPointsCap {
+                cap: table.cap,
+                actual: spent,
+                items: costly.iter().map(|item| item.id.clone()).collect(),
+            });
+        }
+        spent
+    });
+
+    // Copies count across main deck and sideboard, grouped by name so the
+    // violations come out in a stable order.
+    let mut cards: BTreeMap<&str, (&CardCoreDetail, u32, Vec<ListItemId>)> = BTreeMap::new();
+    for item in main.iter().chain(sideboard) {
+        let card = &item.card_core_details;
+        let entry = cards
+            .entry(card.name.as_str())
+            .or_insert_with(|| (card, 0, Vec::new()));
+        entry.1 += quantity(item);
+        entry.2.push(item.id.clone());
+    }
+
+    for (card, actual, items) in cards.into_values() {
+        let (card_core_id, name) = (card.id.clone(), card.name.clone());
+
+        let legality = match legality(rules, card) {
+            None => {
+                violations.push(DeckViolation::NotLegal {
+                    card_core_id,
+                    name,
+                    items,
+                });
+                continue;
+            }
+            Some(Legality::Banned) => {
+                violations.push(DeckViolation::Banned {
+                    card_core_id,
+                    name,
+                    items,
+                });
+                continue;
+            }
+            Some(legality) => legality,
+        };
+
+        // Restricted cards need an allowed printing as much as legal ones.
+        if !has_allowed_printing(rules, card, printings) {
+            violations.push(DeckViolation::NoAllowedPrinting {
+                card_core_id: card_core_id.clone(),
+                name: name.clone(),
+                items: items.clone(),
+            });
+        }
+
+        match legality {
+            Legality::Restricted if actual > 1 => violations.push(DeckViolation::Restricted {
+                card_core_id,
+                name,
+                actual,
+                items,
+            }),
+            Legality::Restricted => {}
+            _ => {
+                if let Some(max) = copy_limit(card, rules.max_copies).filter(|max| actual > *max) {
+                    violations.push(DeckViolation::TooManyCopies {
+                        card_core_id,
+                        name,
+                        max,
+                        actual,
+                        items,
+                    });
+                }
+            }
+        }
+    }
+
+    if rules.commander {
+        violations.extend(check_commander_deck(rules, main, sideboard, commanders));
+    }
+
+    DeckValidation {
+        format: rules.name.clone(),
+        deck_size,
+        sideboard_size,
+        points,
+        violations,
+    }
+}
+
+/// A card's legality under `rules`, the format's own ban and restricted
+/// lists applied on top of the legality key. `None` means not legal.
+fn legality(rules: &FormatRules, card: &CardCoreDetail) -> Option<Legality> {
+    let listed = |names: &[String]| names.iter().any(|n| n.eq_ignore_ascii_case(&card.name));
+
+    let legality = match &rules.legality_key {
+        Some(key) => card.legalities.get(key).copied(),
+        None => Some(Legality::Legal),
+    };
+    match legality {
+        Some(_) if listed(&rules.banned) => Some(Legality::Banned),
+        Some(Legality::Legal) if listed(&rules.restricted) => Some(Legality::Restricted),
+        legality => legality,
+    }
+}
+
+/// Whether any printing of `card` is in an allowed set at an allowed rarity.
+fn has_allowed_printing(
+    rules: &FormatRules,
+    card: &CardCoreDetail,
+    printings: &HashMap<CardCoreId, Vec<CardPrintingDetail>>,
+) -> bool {
+    if !rules.restricts_printings() {
+        return true;
+    }
+
+    printings
+        .get(&card.id)
+        .into_iter()
+        .flatten()
+        .any(|printing| {
+            let set_allowed = rules.allowed_sets.as_ref().is_none_or(|sets| {
+                sets.iter()
+                    .any(|set| set.eq_ignore_ascii_case(&printing.set_code))
+            });
+            let rarity_allowed = rules
+                .allowed_rarities
+                .as_ref()
+                .is_none_or(|rarities| printing.rarity.is_some_and(|r| rarities.contains(&r)));
+            set_allowed && rarity_allowed
+        })
+}
+
+pub(crate) fn quantity(item: &ListItemDetail) -> u32 {
+    item.quantity.max(0) as u32
+}
+
+/// How many copies of `card` a deck may hold when the format allows
+/// `max_copies`. `None` means any number: basic lands and cards like
+/// Relentless Rats. Cards like Seven Dwarves bring their own limit.
+pub(crate) fn copy_limit(card: &CardCoreDetail, max_copies: u32) -> Option<u32> {
+    let supertypes = card.type_line.split('—').next().unwrap_or_default();
+    if supertypes.split_whitespace().any(|word| word == "Basic") {
+        return None;
+    }
+
+    let text = card.oracle_text.as_deref().unwrap_or_default();
+    if text.contains("A deck can have any number of cards named") {
+        return None;
+    }
+    if let Some(rest) = text.split("A deck can have up to ").nth(1) {
+        let number = rest.split_whitespace().next().unwrap_or_default();
+        if let Some(limit) = number_word(number) {
+            return Some(limit);
+        }
+    }
+
+    Some(max_copies)
+}
+
+fn number_word(word: &str) -> Option<u32> {
+    const WORDS: [&str; 10] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    WORDS
+        .iter()
+        .position(|w| *w == word)
+        .map(|i| i as u32 + 1)
+        .or_else(|| word.parse().ok())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use chrono::Utc;
+    use lotuslab_types::{CardCoreDetail, Format, PointsTable, Rarity};
+
+    use super::*;
+
+    pub(crate) fn card(name: &str, type_line: &str, text: &str) -> CardCoreDetail {
+        CardCoreDetail {
+            id: format!("card_core:{}", name.to_lowercase().replace(' ', "-")).into(),
+            name: name.into(),
+            mana_cost: None,
+            back_mana_costs: Vec::new(),
+            color_indicator: Vec::new(),
+            type_line: type_line.into(),
+            oracle_text: Some(text.into()),
+            legalities: ["commander", "modern", "vintage"]
+                .into_iter()
+                .map(|format| (format.to_string(), Legality::Legal))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn item(card: &CardCoreDetail, quantity: i32) -> ListItemDetail {
+        ListItemDetail {
+            id: format!("list_contains:{}", card.id.as_ref().replace(':', "-")).into(),
+            list_id: "list:main".into(),
+            card_core_id: card.id.clone(),
+            selected_printing: None,
+            quantity,
+            notes: None,
+            custom_image_uri: None,
+            tags: Vec::new(),
+            auto_tags: Vec::new(),
+            dismissed_tags: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            card_core_details: card.clone(),
+            card_printing_details: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_deck() {
+        let bolt = card("Lightning Bolt", "Instant", "Deal 3 damage.");
+        let mut ponder = card("Ponder", "Sorcery", "Look at the top three cards.");
+        ponder.legalities.remove("modern");
+        let mut lotus = card("Black Lotus", "Artifact", "Add three mana.");
+        lotus.legalities.insert("modern".into(), Legality::Banned);
+        let mountain = card("Mountain", "Basic Land — Mountain", "({T}: Add {R}.)");
+
+        let main = vec![
+            item(&bolt, 3),
+            item(&ponder, 1),
+            item(&lotus, 1),
+            item(&mountain, 40),
+        ];
+        let sideboard = vec![item(&bolt, 2), item(&mountain, 14)];
+
+        let validation = validate_deck(
+            &Format::Modern.rules(),
+            &main,
+            &sideboard,
+            &[],
+            &HashMap::new(),
+        );
+
+        assert_eq!(validation.deck_size, 45);
+        assert_eq!(validation.sideboard_size, 16);
+        assert!(!validation.is_legal());
+        assert_eq!(
+            validation.violations,
+            vec![
+                DeckViolation::DeckTooSmall {
+                    min: 60,
+                    actual: 45
+                },
+                DeckViolation::SideboardTooLarge {
+                    max: 15,
+                    actual: 16
+                },
+                DeckViolation::Banned {
+                    card_core_id: lotus.id.clone(),
+                    name: lotus.name.clone(),
+                    items: vec![main[2].id.clone()],
+                },
+                DeckViolation::TooManyCopies {
+                    card_core_id: bolt.id.clone(),
+                    name: bolt.name.clone(),
+                    max: 4,
+                    actual: 5,
+                    items: vec![main[0].id.clone(), sideboard[0].id.clone()],
+                },
+                DeckViolation::NotLegal {
+                    card_core_id: ponder.id.clone(),
+                    name: ponder.name.clone(),
+                    items: vec![main[1].id.clone()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_deck_restricted() {
+        let mut lotus = card("Black Lotus", "Artifact", "Add three mana.");
+        lotus
+            .legalities
+            .insert("vintage".into(), Legality::Restricted);
+        let island = card("Island", "Basic Land — Island", "({T}: Add {U}.)");
+
+        let legal = validate_deck(
+            &Format::Vintage.rules(),
+            &[item(&lotus, 1), item(&island, 59)],
+            &[],
+            &[],
+            &HashMap::new(),
+        );
+        assert!(legal.is_legal());
+
+        let too_many = validate_deck(
+            &Format::Vintage.rules(),
+            &[item(&lotus, 2), item(&island, 58)],
+            &[],
+            &[],
+            &HashMap::new(),
+        );
+        assert!(matches!(
+            too_many.violations.as_slice(),
+            [DeckViolation::Restricted { actual: 2, .. }]
+        ));
+    }
+
+    #[test]
+    fn test_copy_limit() {
+        let rats = card(
+            "Relentless Rats",
+            "Creature — Rat",
+            "A deck can have any number of cards named Relentless Rats.",
+        );
+        let dwarves = card(
+            "Seven Dwarves",
+            "Creature — Dwarf",
+            "A deck can have up to seven cards named Seven Dwarves.",
+        );
+        let wastes = card("Wastes", "Basic Land", "({T}: Add {C}.)");
+        let forest = card("Snow-Covered Forest", "Basic Snow Land — Forest", "");
+        let bolt = card("Lightning Bolt", "Instant", "Deal 3 damage.");
+
+        assert_eq!(copy_limit(&rats, 1), None);
+        assert_eq!(copy_limit(&dwarves, 1), Some(7));
+        assert_eq!(copy_limit(&wastes, 4), None);
+        assert_eq!(copy_limit(&forest, 1), None);
+        assert_eq!(copy_limit(&bolt, 4), Some(4));
+    }
+
+    #[test]
+    fn test_validate_deck_commander() {
+        let mut rats = card(
+            "Relentless Rats",
+            "Creature — Rat",
+            "A deck can have any number of cards named Relentless Rats.",
+        );
+        rats.mana_cost = Some("{1}{B}{B}".into());
+        let mut sheoldred = card(
+            "Sheoldred, the Apocalypse",
+            "Legendary Creature — Phyrexian Praetor",
+            "Deathtouch",
+        );
+        sheoldred.mana_cost = Some("{2}{B}{B}".into());
+        let swamp = card("Swamp", "Basic Land — Swamp", "({T}: Add {B}.)");
+
+        let main = [item(&sheoldred, 1), item(&rats, 59), item(&swamp, 40)];
+        let validation = validate_deck(
+            &Format::Commander.rules(),
+            &main,
+            &[],
+            &[main[0].id.clone()],
+            &HashMap::new(),
+        );
+        assert_eq!(validation.deck_size, 100);
+        assert!(validation.is_legal());
+
+        let validation =
+            validate_deck(&Format::Commander.rules(), &main, &[], &[], &HashMap::new());
+        assert_eq!(validation.violations, vec![DeckViolation::MissingCommander]);
+    }
+
+    #[test]
+    fn test_validate_deck_custom_format() {
+        let bolt = card("Lightning Bolt", "Instant", "Deal 3 damage.");
+        let ponder = card("Ponder", "Sorcery", "Look at the top three cards.");
+        let ring = card("Sol Ring", "Artifact", "Add two mana.");
+        let island = card("Island", "Basic Land — Island", "({T}: Add {U}.)");
+        let brainstorm = card("Brainstorm", "Instant", "Draw three cards.");
+
+        let printing = |card: &CardCoreDetail, set_code: &str, rarity| CardPrintingDetail {
+            id: format!("card_printing:{}-{}", set_code, card.name).into(),
+            card_core: card.id.clone(),
+            set_code: set_code.into(),
+            collector_number: "1".into(),
+            scryfall_id: None,
+            rarity: Some(rarity),
+            price_usd: None,
+        };
+        let printings = HashMap::from([
+            (
+                bolt.id.clone(),
+                vec![printing(&bolt, "M10", Rarity::Common)],
+            ),
+            (
+                ponder.id.clone(),
+                vec![
+                    printing(&ponder, "LRW", Rarity::Common),
+                    printing(&ponder, "M12", Rarity::Common),
+                ],
+            ),
+            (
+                ring.id.clone(),
+                vec![printing(&ring, "M10", Rarity::Uncommon)],
+            ),
+            (
+                island.id.clone(),
+                vec![printing(&island, "M10", Rarity::Common)],
+            ),
+            (
+                brainstorm.id.clone(),
+                vec![printing(&brainstorm, "ICE", Rarity::Common)],
+            ),
+        ]);
+
+        let rules = FormatRules {
+            name: "Core Pauper".into(),
+            legality_key: Some("vintage".into()),
+            banned: vec!["lightning bolt".into()],
+            restricted: vec!["Ponder".into(), "Brainstorm".into()],
+            allowed_sets: Some(vec!["m10".into(), "m12".into()]),
+            allowed_rarities: Some(vec![Rarity::Common]),
+            ..Format::Vintage.rules()
+        };
+        let main = vec![
+            item(&bolt, 1),
+            item(&ponder, 2),
+            item(&ring, 1),
+            item(&island, 55),
+            item(&brainstorm, 1),
+        ];
+
+        let validation = validate_deck(&rules, &main, &[], &[], &printings);
+
+        assert_eq!(
+            validation.violations,
+            vec![
+                DeckViolation::NoAllowedPrinting {
+                    card_core_id: brainstorm.id.clone(),
+                    name: brainstorm.name.clone(),
+                    items: vec![main[4].id.clone()],
+                },
+                DeckViolation::Banned {
+                    card_core_id: bolt.id.clone(),
+                    name: bolt.name.clone(),
+                    items: vec![main[0].id.clone()],
+                },
+                DeckViolation::Restricted {
+                    card_core_id: ponder.id.clone(),
+                    name: ponder.name.clone(),
+                    actual: 2,
+                    items: vec![main[1].id.clone()],
+                },
+                DeckViolation::NoAllowedPrinting {
+                    card_core_id: ring.id.clone(),
+                    name: ring.name.clone(),
+                    items: vec![main[2].id.clone()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_deck_points() {
+        let recall = card("Ancestral Recall", "Instant", "Draw three cards.");
+        let walk = card("Time Walk", "Sorcery", "Take an extra turn.");
+        let island = card("Island", "Basic Land — Island", "({T}: Add {U}.)");
+        let rules = FormatRules {
+            name: "Highlander".into(),
+            max_copies: 1,
+            points: Some(PointsTable {
+                cap: 10,
+                points: BTreeMap::from([
+                    ("Ancestral Recall".to_string(), 7),
+                    ("time walk".to_string(), 6),
+                ]),
+            }),
+            ..FormatRules::default()
+        };
+
+        let main = vec![item(&recall, 1), item(&walk, 1), item(&island, 40)];
+        let validation = validate_deck(&rules, &main, &[], &[], &HashMap::new());
+        assert_eq!(validation.points, Some(13));
+        assert_eq!(
+            validation.violations,
+            vec![DeckViolation::OverPointsCap {
+                cap: 10,
+                actual: 13,
+                items: vec![main[0].id.clone(), main[1].id.clone()],
+            }]
+        );
+
+        let legal = validate_deck(&rules, &main[1..], &[], &[], &HashMap::new());
+        assert_eq!(legal.points, Some(6));
+        assert!(legal.is_legal());
+    }
+}
@@ -0,0 +1,189 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use lotuslab_db::Store;
+use lotuslab_import::{BundleItem, read_project_bundle};
+use lotuslab_types::{
+    CardCoreId, CardCoreReadRepo, CardPrintingId, CardPrintingReadRepo, FolderId,
+    ListItemWriteRepo, ListWriteRepo, NewList, NewListItem, NewProject, NewTag, Patch,
+    PatchProject, Project, ProjectWriteRepo, RepoError, TagId, TagReadRepo, TagWriteRepo,
+    UpdateListItem,
+};
+
+use crate::user::tag_imported_items;
+
+/// Imports a `.lotus` bundle as a new project in `folder`.
+///
+/// Everything in the bundle gets a fresh id. Cards are matched by oracle id
+/// and fall back to their name, printings that aren't in the catalog are
+/// dropped. Tags are matched by name and created when missing, then tag
+/// rules run over the new lists. Custom images are copied into `images_dir`.
+pub async fn import_project_bundle(
+    store: &Store,
+    path: &Path,
+    folder: Option<FolderId>,
+    images_dir: &Path,
+) -> Result<Project, String> {
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    let archive = read_project_bundle(&bytes).map_err(|e| e.to_string())?;
+    let bundle = archive.bundle;
+
+    // Match every card before writing anything, like decklist imports.
+    let mut cards: HashMap<CardCoreId, (CardCoreId, Option<CardPrintingId>)> = HashMap::new();
+    let mut missing = Vec::new();
+    for item in bundle.lists.iter().flat_map(|l| &l.items) {
+        match resolve_bundle_card(store, item).await {
+            Ok(card) => {
+                cards.insert(item.card_core_id.clone(), card);
+            }
+            Err(RepoError::NotFound) => missing.push(item.card_name.clone()),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(format!(
+            "Cards missing from the card catalog: {}",
+            missing.join(", ")
+        ));
+    }
+
+    let mut tags: HashMap<String, TagId> = HashMap::new();
+    for tag in bundle.tags {
+        let id = match store.find_tag_by_name(&tag.name).await {
+            Ok(existing) => existing.id,
+            Err(RepoError::NotFound) => {
+                store
+                    .create_tag(NewTag {
+                        name: tag.name.clone(),
+                        color: tag.color,
+                    })
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .id
+            }
+            Err(e) => return Err(e.to_string()),
+        };
+        tags.insert(tag.name, id);
+    }
+
+    let project = store
+        .create_project(NewProject {
+            name: bundle.name,
+            folder,
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut images: HashMap<String, String> = HashMap::new();
+    if !archive.images.is_empty() {
+        std::fs::create_dir_all(images_dir).map_err(|e| e.to_string())?;
+    }
+    let prefix = project.id.as_ref().replace(':', "_");
+    for image in archive.images {
+        let file_name = image.path.rsplit('/').next().unwrap_or_default();
+        let target = images_dir.join(format!("{}_{}", prefix, file_name));
+        std::fs::write(&target, image.bytes).map_err(|e| e.to_string())?;
+        images.insert(image.path, target.to_string_lossy().into_owned());
+    }
+
+    let mut commanders = Vec::new();
+    for list in bundle.lists {
+        let created = store
+            .create_list(NewList {
+                name: list.name,
+                project: project.id.clone(),
+                role: list.role,
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+
+        for item in list.items {
+            let (card_core_id, selected_printing) = cards[&item.card_core_id].clone();
+            let created_item = store
+                .create_list_item(NewListItem {
+                    list_id: created.id.clone(),
+                    card_core_id,
+                    selected_printing,
+                    quantity: Some(item.quantity),
+                    notes: item.notes,
+                    tags: item
+                        .tags
+                        .iter()
+                        .filter_map(|name| tags.get(name).cloned())
+                        .collect(),
+                })
+                .await
+                .map_err(|e| e.to_string())?;
+
+            let custom_image_uri = item
+                .custom_image
+                .and_then(|path| images.get(&path).cloned())
+                .or(item.custom_image_url);
+            if let Some(uri) = custom_image_uri {
+                store
+                    .update_list_item(
+                        &created_item.id,
+                        UpdateListItem {
+                            selected_printing: Patch::Ignore,
+                            quantity: Patch::Ignore,
+                            notes: Patch::Ignore,
+                            custom_image_uri: Patch::Set(uri),
+                            tags: Patch::Ignore,
+                        },
+                    )
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+
+            if item.commander {
+                commanders.push(created_item.id);
+            }
+        }
+
+        // The bundle's tags come in as the user's own, rules add theirs on
+        // top like for any other import.
+        tag_imported_items(store, &created.id).await;
+    }
+
+    if commanders.is_empty() {
+        return Ok(project);
+    }
+
+    store
+        .update_project(
+            &project.id,
+            PatchProject {
+                name: Patch::Ignore,
+                folder: Patch::Ignore,
+                commanders: Patch::Set(commanders),
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn resolve_bundle_card(
+    store: &Store,
+    item: &BundleItem,
+) -> Result<(CardCoreId, Option<CardPrintingId>), RepoError> {
+    let card_core_id = match store.get_card_core(&item.card_core_id).await {
+        Ok(card) => card.id,
+        Err(RepoError::NotFound) => store.find_card_core_by_name(&item.card_name).await?.id,
+        Err(e) => return Err(e),
+    };
+
+    let selected_printing = match &item.selected_printing {
+        Some(id) => match store.get_card_printing(id).await {
+            Ok(printing) if printing.card_core == card_core_id => Some(printing.id),
+            Ok(_) | Err(RepoError::NotFound) => None,
+            Err(e) => return Err(e),
+        },
+        None => None,
+    };
+
+    Ok((card_core_id, selected_printing))
+}
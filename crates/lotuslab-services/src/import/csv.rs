@@ -0,0 +1,171 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    CollectionWriteRepo, CsvColumns, CsvImportReport, CsvPreset, CsvRowError, ListId,
+    ListItemWriteRepo, ListReadRepo, NewCollectionEntry, NewListItem, RepoError,
+};
+
+use super::resolve_card;
+use crate::user::tag_imported_items;
+
+pub fn get_csv_preset_columns(preset: CsvPreset) -> CsvColumns {
+    lotuslab_import::csv_preset_columns(preset)
+}
+
+/// Adds every matched row of a card CSV to a list.
+///
+/// Finish, condition and language only matter for owned cards and are
+/// dropped here, [import_csv_into_collection] keeps them. Rows that don't parse or don't match a card are returned in
+/// the report instead of aborting the import. Rows for the same card and
+/// printing are merged into one item, and the items are written together so
+/// a failed write leaves the list as it was. Tag rules run over the list
+/// afterwards.
+pub async fn import_csv_into_list(
+    store: &Store,
+    list_id: &ListId,
+    input: &str,
+    columns: &CsvColumns,
+) -> Result<CsvImportReport, String> {
+    store.get_list(list_id).await.map_err(|e| e.to_string())?;
+
+    let parsed = lotuslab_import::parse_card_csv(input, columns).map_err(|e| e.to_string())?;
+    let mut report = CsvImportReport {
+        imported: 0,
+        errors: parsed.errors,
+    };
+
+    let mut items: Vec<NewListItem> = Vec::new();
+    for row in parsed.rows {
+        let resolved = resolve_card(
+            store,
+            &row.name,
+            row.set_code.as_deref(),
+            row.collector_number.as_deref(),
+        )
+        .await;
+
+        let (card_core_id, selected_printing) = match resolved {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                report.errors.push(CsvRowError {
+                    line: row.line,
+                    raw: row.name.clone(),
+                    reason: match e {
+                        RepoError::NotFound => format!("no card named '{}'", row.name),
+                        e => e.to_string(),
+                    },
+                });
+                continue;
+            }
+        };
+
+        match items
+            .iter_mut()
+            .find(|i| i.card_core_id == card_core_id && i.selected_printing == selected_printing)
+        {
+            Some(item) => item.quantity = Some(item.quantity.unwrap_or(1) + row.quantity),
+            None => items.push(NewListItem {
+                list_id: list_id.clone(),
+                card_core_id,
+                selected_printing,
+                quantity: Some(row.quantity),
+                notes: None,
+                tags: Vec::new(),
+            }),
+        }
+        report.imported += 1;
+    }
+
+    store
+        .create_list_items(items)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tag_imported_items(store, list_id).await;
+    Ok(report)
+}
+
+/// Adds every matched row of a card CSV to the collection.
+///
+/// Collection entries are for one printing, so rows need a set and collector
+/// number that match the catalog. Rows without a condition get the default
+/// one. Rows that don't parse or don't match are returned in the report, as
+/// for [import_csv_into_list]. Rows for the same printing, finish, condition
+/// and language are merged into one entry, and the entries are written
+/// together so a failed write leaves the collection as it was.
+pub async fn import_csv_into_collection(
+    store: &Store,
+    input: &str,
+    columns: &CsvColumns,
+) -> Result<CsvImportReport, String> {
+    let parsed = lotuslab_import::parse_card_csv(input, columns).map_err(|e| e.to_string())?;
+    let mut report = CsvImportReport {
+        imported: 0,
+        errors: parsed.errors,
+    };
+
+    let mut entries: Vec<NewCollectionEntry> = Vec::new();
+    for row in parsed.rows {
+        let resolved = resolve_card(
+            store,
+            &row.name,
+            row.set_code.as_deref(),
+            row.collector_number.as_deref(),
+        )
+        .await;
+
+        let card_printing_id = match resolved {
+            Ok((_, Some(printing))) => printing,
+            Ok((_, None)) => {
+                report.errors.push(CsvRowError {
+                    line: row.line,
+                    raw: row.name.clone(),
+                    reason: format!("no printing of '{}' matches the set and number", row.name),
+                });
+                continue;
+            }
+            Err(e) => {
+                report.errors.push(CsvRowError {
+                    line: row.line,
+                    raw: row.name.clone(),
+                    reason: match e {
+                        RepoError::NotFound => format!("no card named '{}'", row.name),
+                        e => e.to_string(),
+                    },
+                });
+                continue;
+            }
+        };
+        let condition = row.condition.unwrap_or_default();
+
+        match entries.iter_mut().find(|e| {
+            e.card_printing_id == card_printing_id
+                && e.finish == row.finish
+                && e.condition == condition
+                && e.language == row.language
+        }) {
+            Some(entry) => entry.quantity = Some(entry.quantity.unwrap_or(1) + row.quantity),
+            None => entries.push(NewCollectionEntry {
+                card_printing_id,
+                quantity: Some(row.quantity),
+                finish: row.finish,
+                condition,
+                language: row.language,
+                purchase_price: None,
+                acquired_on: None,
+                location: None,
+            }),
+        }
+        report.imported += 1;
+    }
+
+    store
+        .create_collection_entries(entries)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(report)
+}
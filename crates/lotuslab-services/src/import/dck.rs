@@ -0,0 +1,29 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::Path;
+
+use lotuslab_db::Store;
+use lotuslab_types::{DckFormat, FolderId, Project};
+
+use super::create_project_from_decklist;
+
+/// Imports a Forge or XMage `.dck` file as a new project in `folder`, named
+/// after the deck or, when the file has no name, after the file.
+pub async fn import_dck(
+    store: &Store,
+    path: &Path,
+    format: DckFormat,
+    folder: Option<FolderId>,
+) -> Result<Project, String> {
+    let input = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let deck = lotuslab_import::parse_dck(&input, format).map_err(|e| e.to_string())?;
+    let name = deck.name.clone().unwrap_or_else(|| {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Imported Deck".to_string())
+    });
+
+    create_project_from_decklist(store, deck, name, folder).await
+}
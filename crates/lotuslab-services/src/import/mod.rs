@@ -0,0 +1,152 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+mod bundle;
+mod csv;
+mod dck;
+mod precon;
+pub use self::bundle::*;
+pub use self::csv::*;
+pub use self::dck::*;
+pub use self::precon::*;
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    CardCoreId, CardCoreReadRepo, CardPrintingId, CardPrintingReadRepo, Decklist, DecklistSection,
+    FolderId, ListId, ListItemWriteRepo, ListRole, ListWriteRepo, NewList, NewListItem, NewProject,
+    Patch, PatchProject, Project, ProjectWriteRepo, RepoError,
+};
+
+use crate::user::tag_imported_items;
+
+/// Matches a card from an imported file against the catalog.
+///
+/// An exact printing wins when the file names one, otherwise this falls back
+/// to the card name and leaves the printing unselected.
+pub(crate) async fn resolve_card(
+    store: &Store,
+    name: &str,
+    set_code: Option<&str>,
+    collector_number: Option<&str>,
+) -> Result<(CardCoreId, Option<CardPrintingId>), RepoError> {
+    if let (Some(set_code), Some(collector_number)) = (set_code, collector_number) {
+        match store.find_card_printing(set_code, collector_number).await {
+            Ok(printing) => return Ok((printing.card_core, Some(printing.id))),
+            Err(RepoError::NotFound) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    let card = store.find_card_core_by_name(name).await?;
+    Ok((card.id, None))
+}
+
+/// Creates a project holding a `Main` list, plus a `Sideboard` list when the
+/// deck has one. Commanders go in the main list and are set as the project's
+/// commanders.
+///
+/// Every entry is matched before anything is written, so a deck with unknown
+/// cards doesn't leave a half built project behind. Tag rules run over the
+/// new lists.
+pub(crate) async fn create_project_from_decklist(
+    store: &Store,
+    deck: Decklist,
+    name: String,
+    folder: Option<FolderId>,
+) -> Result<Project, String> {
+    let mut resolved = Vec::with_capacity(deck.entries.len());
+    let mut missing = Vec::new();
+    for entry in deck.entries {
+        match resolve_card(
+            store,
+            &entry.name,
+            entry.set_code.as_deref(),
+            entry.collector_number.as_deref(),
+        )
+        .await
+        {
+            Ok(card) => resolved.push((entry, card)),
+            Err(RepoError::NotFound) => missing.push(entry.name),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(format!(
+            "Cards missing from the card catalog: {}",
+            missing.join(", ")
+        ));
+    }
+
+    let project = store
+        .create_project(NewProject { name, folder })
+        .await
+        .map_err(|e| e.to_string())?;
+    let main = store
+        .create_list(NewList {
+            name: "Main".to_string(),
+            project: project.id.clone(),
+            role: ListRole::Main,
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut sideboard: Option<ListId> = None;
+    let mut commanders = Vec::new();
+    for (entry, (card_core_id, selected_printing)) in resolved {
+        let list_id = match entry.section {
+            DecklistSection::Sideboard => match &sideboard {
+                Some(id) => id.clone(),
+                None => {
+                    let list = store
+                        .create_list(NewList {
+                            name: "Sideboard".to_string(),
+                            project: project.id.clone(),
+                            role: ListRole::Sideboard,
+                        })
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    sideboard.insert(list.id).clone()
+                }
+            },
+            DecklistSection::Main | DecklistSection::Commander => main.id.clone(),
+        };
+
+        let item = store
+            .create_list_item(NewListItem {
+                list_id,
+                card_core_id,
+                selected_printing,
+                quantity: Some(entry.quantity),
+                notes: None,
+                tags: Vec::new(),
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if entry.section == DecklistSection::Commander {
+            commanders.push(item.id);
+        }
+    }
+
+    tag_imported_items(store, &main.id).await;
+    if let Some(sideboard) = &sideboard {
+        tag_imported_items(store, sideboard).await;
+    }
+
+    if commanders.is_empty() {
+        return Ok(project);
+    }
+
+    store
+        .update_project(
+            &project.id,
+            PatchProject {
+                name: Patch::Ignore,
+                folder: Patch::Ignore,
+                commanders: Patch::Set(commanders),
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
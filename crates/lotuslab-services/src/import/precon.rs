@@ -0,0 +1,51 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use lotuslab_db::Store;
+use lotuslab_types::{FolderId, PreconSet, Project};
+
+use super::create_project_from_decklist;
+
+/// Lists the precons in an MTGJSON `AllDeckFiles` directory, grouped by set
+/// with the newest decks first within each set.
+pub fn list_precons(decks_dir: &Path) -> Result<Vec<PreconSet>, String> {
+    let index = lotuslab_import::read_precon_index(decks_dir).map_err(|e| e.to_string())?;
+
+    let mut sets: BTreeMap<String, PreconSet> = BTreeMap::new();
+    for deck in index {
+        sets.entry(deck.set_code.clone())
+            .or_insert_with(|| PreconSet {
+                set_code: deck.set_code.clone(),
+                decks: Vec::new(),
+            })
+            .decks
+            .push(deck);
+    }
+
+    let mut sets: Vec<PreconSet> = sets.into_values().collect();
+    for set in &mut sets {
+        set.decks.sort_by(|a, b| {
+            b.release_date
+                .cmp(&a.release_date)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+    }
+
+    Ok(sets)
+}
+
+pub async fn import_precon(
+    store: &Store,
+    decks_dir: &Path,
+    file_name: &str,
+    folder: Option<FolderId>,
+) -> Result<Project, String> {
+    let deck = lotuslab_import::read_precon(decks_dir, file_name).map_err(|e| e.to_string())?;
+    let name = deck.name.clone().unwrap_or_else(|| file_name.to_string());
+
+    create_project_from_decklist(store, deck, name, folder).await
+}
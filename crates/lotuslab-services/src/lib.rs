@@ -2,5 +2,19 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+mod backup;
+mod cards;
+mod catalog;
+mod export;
+mod format;
+mod import;
+mod stats;
 mod user;
+pub use crate::backup::*;
+pub use crate::cards::*;
+pub use crate::catalog::*;
+pub use crate::export::*;
+pub use crate::format::*;
+pub use crate::import::*;
+pub use crate::stats::*;
 pub use crate::user::*;
@@ -0,0 +1,347 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Hypergeometric draw odds.
+//!
+//! The library splits into groups of cards that count for the same
+//! requirements, and the odds come from counting the hands of each split. A
+//! card in the categories of several requirements counts for any one of
+//! them, never for two at once, so a hand meets the requirements when its
+//! cards can be shared out to cover them all. London mulligans put cards on
+//! the bottom after seeing seven, the cards bottomed are always the ones the
+//! requirements need least.
+
+use std::collections::HashSet;
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    CardCategory, CardCoreId, CardQuery, CardSearchRepo, DrawOdds, DrawQuestion, ListId,
+    ListItemDetail, ListItemId, ListItemReadRepo, TurnOdds,
+};
+
+use crate::format::quantity;
+
+const HAND_SIZE: u32 = 7;
+/// Every set of requirements is checked for each hand, see [covered].
+const MAX_REQUIREMENTS: usize = 8;
+
+/// Odds of meeting the requirements of `question` with the cards of a list,
+/// for each of the first [DrawOdds::TURNS] turns.
+pub async fn draw_odds(
+    store: &Store,
+    list_id: &ListId,
+    question: &DrawQuestion,
+) -> Result<DrawOdds, String> {
+    if question.requirements.len() > MAX_REQUIREMENTS {
+        return Err(format!(
+            "draw odds take at most {} requirements",
+            MAX_REQUIREMENTS
+        ));
+    }
+
+    let items = store
+        .list_item_details_for_list(list_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut categories = Vec::with_capacity(question.requirements.len());
+    for requirement in &question.requirements {
+        categories.push(category_items(store, &items, &requirement.category).await?);
+    }
+    Ok(compute_draw_odds(&items, &categories, question))
+}
+
+/// The items of a list that fall in `category`.
+async fn category_items(
+    store: &Store,
+    items: &[ListItemDetail],
+    category: &CardCategory,
+) -> Result<HashSet<ListItemId>, String> {
+    let cards: HashSet<CardCoreId> = match category {
+        CardCategory::Tag(tag) => {
+            return Ok(items
+                .iter()
+                .filter(|item| item.tags.contains(tag))
+                .map(|item| item.id.clone())
+                .collect());
+        }
+        CardCategory::Query(query) => {
+            let query = CardQuery::parse(query).map_err(|e| e.to_string())?;
+            let ids: Vec<CardCoreId> = items.iter().map(|i| i.card_core_id.clone()).collect();
+            store
+                .filter_cards(&query, &ids)
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .collect()
+        }
+        CardCategory::Cards(cards) => cards.iter().cloned().collect(),
+    };
+
+    Ok(items
+        .iter()
+        .filter(|item| cards.contains(&item.card_core_id))
+        .map(|item| item.id.clone())
+        .collect())
+}
+
+/// `categories` holds the items of each requirement of `question`, in
+/// order.
+pub(crate) fn compute_draw_odds(
+    items: &[ListItemDetail],
+    categories: &[HashSet<ListItemId>],
+    question: &DrawQuestion,
+) -> DrawOdds {
+    // Items counting for the same requirements, a bit for each, share a
+    // group.
+    let mut masks: Vec<u32> = Vec::new();
+    let mut counts: Vec<u32> = Vec::new();
+    let mut matching = vec![0; categories.len()];
+    let mut other = 0;
+    for item in items {
+        let copies = quantity(item);
+        let mut mask = 0;
+        for (index, category) in categories.iter().enumerate() {
+            if category.contains(&item.id) {
+                mask |= 1 << index;
+                matching[index] += copies;
+            }
+        }
+        if mask == 0 {
+            other += copies;
+            continue;
+        }
+        match masks.iter().position(|m| *m == mask) {
+            Some(group) => counts[group] += copies,
+            None => {
+                masks.push(mask);
+                counts.push(copies);
+            }
+        }
+    }
+
+    let deck_size = counts.iter().sum::<u32>() + other;
+    let at_least: Vec<u32> = question.requirements.iter().map(|r| r.at_least).collect();
+    let hand = HAND_SIZE.min(deck_size);
+    let mulligans = question.max_mulligans.min(hand.saturating_sub(1));
+    let odds = |bottomed: u32, draws: u32| {
+        success_odds(&masks, &counts, other, &at_least, hand - bottomed, draws)
+    };
+
+    // Hands mulliganed before the last one are only kept when they already
+    // meet the requirements, and then they still do on every later turn.
+    let mut reached = 1.0;
+    let mut kept_early = 0.0;
+    for bottomed in 0..mulligans {
+        let keep = odds(bottomed, 0);
+        kept_early += reached * keep;
+        reached *= 1.0 - keep;
+    }
+
+    let turns = (1..=DrawOdds::TURNS)
+        .map(|turn| {
+            let draws = (turn - 1 + question.on_the_draw as u32).min(deck_size - hand);
+            TurnOdds {
+                turn,
+                cards_seen: hand + draws,
+                probability: odds(0, draws),
+                probability_with_mulligans: kept_early + reached * odds(mulligans, draws),
+            }
+        })
+        .collect();
+
+    DrawOdds {
+        deck_size,
+        matching,
+        turns,
+    }
+}
+
+/// Chance that an opening hand of up to seven cards, `kept` of them kept
+/// after bottoming, plus the next `draws` cards hold `at_least` of every
+/// requirement. `counts` holds the cards of each group, `masks` the
+/// requirements they count for.
+fn success_odds(
+    masks: &[u32],
+    counts: &[u32],
+    other: u32,
+    at_least: &[u32],
+    kept: u32,
+    draws: u32,
+) -> f64 {
+    let deck_size = counts.iter().sum::<u32>() + other;
+    let hand = HAND_SIZE.min(deck_size);
+    let library = deck_size - hand;
+    let needed = at_least.iter().sum::<u32>();
+
+    let mut hits = 0.0;
+    for_each_split(counts, other, hand, &mut |opening, hands| {
+        let left: Vec<u32> = counts.iter().zip(opening).map(|(c, o)| c - o).collect();
+        let other_left = other - (hand - opening.iter().sum::<u32>());
+
+        let mut drawn_hits = 0.0;
+        for_each_split(&left, other_left, draws, &mut |drawn, ways| {
+            let seen: Vec<u32> = opening.iter().zip(drawn).map(|(o, d)| o + d).collect();
+            // Drawn cards cover what they can, the kept ones the rest.
+            if covered(masks, &seen, at_least) == needed
+                && needed - covered(masks, drawn, at_least) <= kept
+            {
+                drawn_hits += ways;
+            }
+        });
+        hits += hands * drawn_hits / binomial(library, draws);
+    });
+    hits / binomial(deck_size, hand)
+}
+
+/// How much of `at_least` the cards in `counts` cover at most, each card
+/// counting for one requirement. By Hall's theorem that's the least, over
+/// every set of requirements, of what the others ask for plus the cards
+/// counting for any requirement in the set.
+fn covered(masks: &[u32], counts: &[u32], at_least: &[u32]) -> u32 {
+    (0..1u32 << at_least.len())
+        .map(|set| {
+            let asked: u32 = at_least
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| set & 1 << index == 0)
+                .map(|(_, at_least)| at_least)
+                .sum();
+            let cards: u32 = masks
+                .iter()
+                .zip(counts)
+                .filter(|(mask, _)| *mask & set != 0)
+                .map(|(_, count)| count)
+                .sum();
+            asked + cards
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+/// Calls `f` with every way of drawing `drawn` cards out of the categories
+/// in `counts` and `other` other cards, and the number of draws that split
+/// the same way.
+fn for_each_split(counts: &[u32], other: u32, drawn: u32, f: &mut impl FnMut(&[u32], f64)) {
+    fn recurse(
+        counts: &[u32],
+        other: u32,
+        left: u32,
+        split: &mut Vec<u32>,
+        ways: f64,
+        f: &mut impl FnMut(&[u32], f64),
+    ) {
+        match counts.split_first() {
+            None if left <= other => f(split, ways * binomial(other, left)),
+            None => {}
+            Some((&count, rest)) => {
+                for n in 0..=count.min(left) {
+                    split.push(n);
+                    recurse(rest, other, left - n, split, ways * binomial(count, n), f);
+                    split.pop();
+                }
+            }
+        }
+    }
+
+    recurse(counts, other, drawn, &mut Vec::new(), 1.0, f);
+}
+
+fn binomial(n: u32, k: u32) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use lotuslab_types::DrawRequirement;
+
+    use super::*;
+    use crate::format::tests::{card, item};
+
+    fn question(at_least: &[u32], on_the_draw: bool, max_mulligans: u32) -> DrawQuestion {
+        DrawQuestion {
+            requirements: at_least
+                .iter()
+                .map(|&at_least| DrawRequirement {
+                    name: String::new(),
+                    category: CardCategory::Cards(Vec::new()),
+                    at_least,
+                })
+                .collect(),
+            on_the_draw,
+            max_mulligans,
+        }
+    }
+
+    #[test]
+    fn test_success_odds() {
+        // 1 - C(36,7)/C(60,7): at least one of 24 lands in seven cards.
+        let odds = success_odds(&[1], &[24], 36, &[1], 7, 0);
+        assert!((odds - 0.9784).abs() < 1e-4, "{odds}");
+
+        // At least 2 of 17 in 8 cards out of 40.
+        let odds = success_odds(&[1], &[17], 23, &[2], 7, 1);
+        assert!((odds - 0.9394).abs() < 1e-4, "{odds}");
+
+        // Only cards the requirements don't need go to the bottom.
+        assert_eq!(
+            success_odds(&[1, 2], &[24, 8], 28, &[2, 1], 4, 0),
+            success_odds(&[1, 2], &[24, 8], 28, &[2, 1], 7, 0)
+        );
+        assert_eq!(success_odds(&[1], &[24], 36, &[3], 2, 0), 0.0);
+
+        // A card counting for both requirements only covers one of them.
+        assert_eq!(
+            success_odds(&[0b11], &[24], 36, &[1, 1], 7, 0),
+            success_odds(&[1], &[24], 36, &[2], 7, 0)
+        );
+    }
+
+    #[test]
+    fn test_compute_draw_odds() {
+        let forest = card("Forest", "Basic Land — Forest", "({T}: Add {G}.)");
+        let growth = card("Rampant Growth", "Sorcery", "Search your library.");
+        let bear = card("Grizzly Bears", "Creature — Bear", "");
+        let items = vec![item(&forest, 24), item(&growth, 8), item(&bear, 28)];
+        let categories = vec![
+            HashSet::from([items[0].id.clone()]),
+            // A third land does as well as a Rampant Growth.
+            HashSet::from([items[0].id.clone(), items[1].id.clone()]),
+        ];
+
+        let play = compute_draw_odds(&items, &categories, &question(&[2, 1], false, 0));
+        assert_eq!((play.deck_size, play.matching.clone()), (60, vec![24, 32]));
+        let growth_only = vec![categories[0].clone(), HashSet::from([items[1].id.clone()])];
+        let without_lands = compute_draw_odds(&items, &growth_only, &question(&[2, 1], false, 0));
+        assert!(play.turns[0].probability > without_lands.turns[0].probability);
+        assert_eq!(play.turns.len(), 10);
+        assert_eq!(
+            (play.turns[0].cards_seen, play.turns[9].cards_seen),
+            (7, 16)
+        );
+        assert_eq!(
+            play.turns[0].probability,
+            play.turns[0].probability_with_mulligans
+        );
+        assert!(
+            play.turns
+                .windows(2)
+                .all(|t| t[0].probability < t[1].probability)
+        );
+
+        let draw = compute_draw_odds(&items, &categories, &question(&[2, 1], true, 0));
+        assert_eq!(draw.turns[0].cards_seen, 8);
+        assert_eq!(draw.turns[0].probability, play.turns[1].probability);
+
+        let mulligans = compute_draw_odds(&items, &categories, &question(&[2, 1], false, 2));
+        for (play, mulligans) in play.turns.iter().zip(&mulligans.turns) {
+            assert_eq!(play.probability, mulligans.probability);
+            assert!(mulligans.probability_with_mulligans > play.probability);
+        }
+    }
+}
@@ -0,0 +1,314 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Goldfish games: shuffle a list, mulligan, then play a land and as much
+//! mana as possible every turn against nobody.
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    GoldfishReport, GoldfishSettings, GoldfishTurn, ListId, ListItemDetail, ListItemId,
+    ListItemReadRepo, SampleHand,
+};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::cards::text::{mana_cost, type_line};
+use crate::format::quantity;
+
+const HAND_SIZE: usize = 7;
+
+/// The cards of a list to play [goldfish] games with, copied out so the
+/// games don't need the store.
+pub async fn goldfish_deck(store: &Store, list_id: &ListId) -> Result<Vec<ListItemDetail>, String> {
+    store
+        .list_item_details_for_list(list_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Plays `settings.runs` goldfish games with a list and averages them. This
+/// keeps a core busy for a while, async callers should hand it to a
+/// blocking thread.
+pub fn goldfish(
+    items: &[ListItemDetail],
+    settings: &GoldfishSettings,
+) -> Result<GoldfishReport, String> {
+    if settings.runs > GoldfishSettings::MAX_RUNS {
+        return Err(format!(
+            "goldfish plays at most {} games at a time",
+            GoldfishSettings::MAX_RUNS
+        ));
+    }
+    Ok(simulate_goldfish(items, settings))
+}
+
+/// Deals an opening hand from a list, with `mulligans` cards put on the
+/// bottom. A new seed is picked when none is given.
+pub async fn sample_hand(
+    store: &Store,
+    list_id: &ListId,
+    seed: Option<u32>,
+    mulligans: u32,
+) -> Result<SampleHand, String> {
+    let items = store
+        .list_item_details_for_list(list_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(deal_sample_hand(
+        &items,
+        seed.unwrap_or_else(rand::random),
+        mulligans,
+    ))
+}
+
+/// One copy of a card in the library.
+struct DeckCard {
+    item: usize,
+    is_land: bool,
+    mana_value: u32,
+}
+
+struct Opening {
+    hand: Vec<usize>,
+    bottomed: Vec<usize>,
+    library: Vec<usize>,
+    mulligans: u32,
+}
+
+fn deck_cards(items: &[ListItemDetail]) -> Vec<DeckCard> {
+    items
+        .iter()
+        .enumerate()
+        .flat_map(|(index, item)| {
+            let card = &item.card_core_details;
+            let is_land = type_line(card).has_card_type("Land");
+            let mana_value = mana_cost(card).mana_value();
+            (0..quantity(item)).map(move |_| DeckCard {
+                item: index,
+                is_land,
+                mana_value,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn deal_sample_hand(items: &[ListItemDetail], seed: u32, mulligans: u32) -> SampleHand {
+    let deck = deck_cards(items);
+    let mut order: Vec<usize> = (0..deck.len()).collect();
+    order.shuffle(&mut StdRng::seed_from_u64(seed.into()));
+    let opening = deal(&deck, order, mulligans);
+
+    let ids = |cards: &[usize]| -> Vec<ListItemId> {
+        cards
+            .iter()
+            .map(|&c| items[deck[c].item].id.clone())
+            .collect()
+    };
+    SampleHand {
+        seed,
+        hand: ids(&opening.hand),
+        bottomed: ids(&opening.bottomed),
+    }
+}
+
+pub(crate) fn simulate_goldfish(
+    items: &[ListItemDetail],
+    settings: &GoldfishSettings,
+) -> GoldfishReport {
+    let deck = deck_cards(items);
+    let mut rng = StdRng::seed_from_u64(settings.seed.into());
+    let mut mulligans = vec![0; settings.max_mulligans as usize + 1];
+    let mut turns: Vec<GoldfishTurn> = (1..=settings.turns)
+        .map(|turn| GoldfishTurn {
+            turn,
+            average_lands: 0.0,
+            land_drop_rate: 0.0,
+            on_curve_rate: 0.0,
+            average_mana_spent: 0.0,
+        })
+        .collect();
+
+    for _ in 0..settings.runs {
+        let opening = open(&deck, &mut rng, settings);
+        mulligans[opening.mulligans as usize] += 1;
+        play(&deck, opening, settings.on_the_draw, &mut turns);
+    }
+
+    let runs = settings.runs.max(1) as f64;
+    for turn in &mut turns {
+        turn.average_lands /= runs;
+        turn.land_drop_rate /= runs;
+        turn.on_curve_rate /= runs;
+        turn.average_mana_spent /= runs;
+    }
+
+    GoldfishReport {
+        runs: settings.runs,
+        mulligans,
+        turns,
+    }
+}
+
+/// Shuffles and mulligans until a hand is kept.
+fn open(deck: &[DeckCard], rng: &mut StdRng, settings: &GoldfishSettings) -> Opening {
+    let mut order: Vec<usize> = (0..deck.len()).collect();
+    let mut mulligans = 0;
+    loop {
+        order.shuffle(rng);
+        let lands = order
+            .iter()
+            .take(HAND_SIZE)
+            .filter(|&&c| deck[c].is_land)
+            .count() as u32;
+        if (settings.min_lands..=settings.max_lands).contains(&lands)
+            || mulligans == settings.max_mulligans
+        {
+            return deal(deck, order, mulligans);
+        }
+        mulligans += 1;
+    }
+}
+
+/// Draws seven off the top of `order` and puts `mulligans` of them on the
+/// bottom: lands while they're more than half the hand, otherwise the most
+/// expensive spell.
+fn deal(deck: &[DeckCard], mut order: Vec<usize>, mulligans: u32) -> Opening {
+    let mut library = order.split_off(HAND_SIZE.min(order.len()));
+    let mut hand = order;
+
+    let mut bottomed = Vec::new();
+    for _ in 0..(mulligans as usize).min(hand.len()) {
+        let lands = hand.iter().filter(|&&c| deck[c].is_land).count();
+        let index = match lands * 2 > hand.len() {
+            true => hand.iter().position(|&c| deck[c].is_land),
+            false => hand
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| !deck[**c].is_land)
+                .max_by_key(|(_, c)| deck[**c].mana_value)
+                .map(|(index, _)| index),
+        };
+        bottomed.push(hand.remove(index.unwrap_or_default()));
+    }
+    library.extend(&bottomed);
+
+    Opening {
+        hand,
+        bottomed,
+        library,
+        mulligans,
+    }
+}
+
+/// Plays out one game, adding its numbers to `turns`.
+fn play(deck: &[DeckCard], opening: Opening, on_the_draw: bool, turns: &mut [GoldfishTurn]) {
+    let Opening {
+        mut hand, library, ..
+    } = opening;
+    let mut library = library.into_iter();
+    let mut lands = 0;
+    let mut missed_drop = false;
+
+    for totals in turns {
+        if totals.turn > 1 || on_the_draw {
+            hand.extend(library.next());
+        }
+        match hand.iter().position(|&c| deck[c].is_land) {
+            Some(index) => {
+                hand.remove(index);
+                lands += 1;
+            }
+            None => missed_drop = true,
+        }
+
+        // Biggest spells first, as long as there's mana left.
+        hand.sort_by_key(|&c| std::cmp::Reverse(deck[c].mana_value));
+        let mut mana = lands;
+        let mut on_curve = false;
+        hand.retain(|&c| {
+            let card = &deck[c];
+            if card.is_land || card.mana_value > mana {
+                return true;
+            }
+            mana -= card.mana_value;
+            on_curve |= card.mana_value == totals.turn;
+            false
+        });
+
+        totals.average_lands += lands as f64;
+        totals.land_drop_rate += !missed_drop as u32 as f64;
+        totals.on_curve_rate += on_curve as u32 as f64;
+        totals.average_mana_spent += (lands - mana) as f64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::tests::{card, item};
+
+    fn items() -> Vec<ListItemDetail> {
+        let forest = card("Forest", "Basic Land — Forest", "({T}: Add {G}.)");
+        let mut bear = card("Grizzly Bears", "Creature — Bear", "");
+        bear.mana_cost = Some("{1}{G}".into());
+        let mut giant = card("Hill Giant", "Creature — Giant", "");
+        giant.mana_cost = Some("{3}{R}".into());
+        vec![item(&forest, 24), item(&bear, 20), item(&giant, 16)]
+    }
+
+    #[test]
+    fn test_simulate_goldfish() {
+        let items = items();
+        let settings = GoldfishSettings {
+            runs: 500,
+            turns: 4,
+            ..GoldfishSettings::default()
+        };
+
+        let report = simulate_goldfish(&items, &settings);
+        assert_eq!(report, simulate_goldfish(&items, &settings));
+        assert_eq!(report.mulligans.iter().sum::<u32>(), 500);
+        assert_eq!(report.turns.len(), 4);
+
+        let first = &report.turns[0];
+        assert_eq!(first.average_lands, first.land_drop_rate);
+        assert_eq!(first.on_curve_rate, 0.0);
+        assert!(report.turns[1].on_curve_rate > 0.5);
+        assert!(
+            report
+                .turns
+                .windows(2)
+                .all(|t| t[0].land_drop_rate >= t[1].land_drop_rate)
+        );
+
+        let other_seed = simulate_goldfish(
+            &items,
+            &GoldfishSettings {
+                seed: 1,
+                ..settings.clone()
+            },
+        );
+        assert_ne!(report, other_seed);
+
+        let too_many = GoldfishSettings {
+            runs: GoldfishSettings::MAX_RUNS + 1,
+            ..settings
+        };
+        assert!(goldfish(&items, &too_many).is_err());
+    }
+
+    #[test]
+    fn test_deal_sample_hand() {
+        let items = items();
+
+        let hand = deal_sample_hand(&items, 42, 0);
+        assert_eq!((hand.hand.len(), hand.bottomed.len()), (7, 0));
+        assert_eq!(hand, deal_sample_hand(&items, 42, 0));
+
+        let mulliganed = deal_sample_hand(&items, 42, 2);
+        assert_eq!((mulliganed.hand.len(), mulliganed.bottomed.len()), (5, 2));
+        assert!(mulliganed.hand.iter().all(|i| hand.hand.contains(i)));
+    }
+}
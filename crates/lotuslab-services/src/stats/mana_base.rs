@@ -0,0 +1,306 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    BasicLandCount, Color, ColorSources, LandKind, LandSources, ListId, ListItemDetail,
+    ListItemReadRepo, ManaBaseReport,
+};
+
+use crate::cards::text::{mana_cost, oracle_text, produced_colors, type_line};
+use crate::format::quantity;
+
+/// Sources needed to cast a spell on curve 90% of the time, from Frank
+/// Karsten's 2022 tables. Rows are one to three colored pips of the same
+/// color, columns mana values one to six. Cells with fewer mana than pips
+/// are never read.
+const LIMITED_SOURCES: [[u32; 6]; 3] = [
+    [9, 9, 8, 7, 6, 6],
+    [0, 14, 12, 11, 10, 9],
+    [0, 0, 16, 14, 13, 12],
+];
+const CONSTRUCTED_SOURCES: [[u32; 6]; 3] = [
+    [14, 13, 12, 10, 9, 9],
+    [0, 20, 18, 16, 15, 14],
+    [0, 0, 23, 21, 19, 18],
+];
+const COMMANDER_SOURCES: [[u32; 6]; 3] = [
+    [19, 19, 18, 16, 15, 14],
+    [0, 30, 28, 26, 23, 22],
+    [0, 0, 36, 33, 30, 28],
+];
+
+/// Colored sources a list needs, what its lands provide and how to split
+/// its basics.
+pub async fn mana_base(store: &Store, list_id: &ListId) -> Result<ManaBaseReport, String> {
+    let items = store
+        .list_item_details_for_list(list_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(recommend_mana_base(&items))
+}
+
+pub(crate) fn recommend_mana_base(items: &[ListItemDetail]) -> ManaBaseReport {
+    let deck_size: u32 = items.iter().map(quantity).sum();
+    let mut colors: Vec<ColorSources> = Color::ALL
+        .into_iter()
+        .map(|color| ColorSources {
+            color,
+            required: 0,
+            hardest_card: None,
+            sources: 0,
+            under_supported: false,
+        })
+        .collect();
+    let mut pips = [0; 5];
+    let mut basic_sources = [0; 5];
+    let mut lands = Vec::new();
+
+    for item in items {
+        let card = &item.card_core_details;
+        let copies = quantity(item);
+
+        if type_line(card).has_card_type("Land") {
+            let produced = produced_colors(card);
+            let kind = land_kind(item, produced.len());
+            for &color in &produced {
+                colors[color as usize].sources += copies;
+                if kind == LandKind::Basic {
+                    basic_sources[color as usize] += copies;
+                }
+            }
+            lands.push(LandSources {
+                card_core_id: card.id.clone(),
+                name: card.name.clone(),
+                quantity: copies,
+                kind,
+                colors: produced.into_iter().collect(),
+            });
+            continue;
+        }
+
+        // Hybrid and Phyrexian symbols can be paid some other way, only plain
+        // colored symbols need a source of their color.
+        let cost = mana_cost(card);
+        for color in Color::ALL {
+            let count = cost.pips(color);
+            if count == 0 {
+                continue;
+            }
+            pips[color as usize] += count * copies;
+            let required = sources_needed(deck_size, count, cost.mana_value());
+            let sources = &mut colors[color as usize];
+            if required > sources.required {
+                sources.required = required;
+                sources.hardest_card = Some(card.name.clone());
+            }
+        }
+    }
+
+    let basic_split = split_basics(&colors, &basic_sources, &pips);
+    for sources in &mut colors {
+        sources.under_supported = sources.sources < sources.required;
+    }
+    colors.retain(|c| c.required > 0 || c.sources > 0);
+
+    ManaBaseReport {
+        deck_size,
+        land_count: lands.iter().map(|l| l.quantity).sum(),
+        colors,
+        lands,
+        basic_split,
+    }
+}
+
+/// Karsten's number for the table closest to the deck size, scaled to it.
+/// Four pips or more read the three pip row and mana values above six the
+/// six column.
+fn sources_needed(deck_size: u32, pips: u32, mana_value: u32) -> u32 {
+    let (table_size, table) = match deck_size {
+        ..=50 => (40, &LIMITED_SOURCES),
+        51..=80 => (60, &CONSTRUCTED_SOURCES),
+        _ => (99, &COMMANDER_SOURCES),
+    };
+    let pips = pips.clamp(1, 3);
+    let needed = table[pips as usize - 1][mana_value.clamp(pips, 6) as usize - 1];
+    (needed * deck_size + table_size / 2) / table_size
+}
+
+fn land_kind(item: &ListItemDetail, color_count: usize) -> LandKind {
+    let card = &item.card_core_details;
+    let lines: Vec<String> = oracle_text(card)
+        .abilities
+        .into_iter()
+        .map(|a| a.text)
+        .collect();
+    let lines = lines.join("\n");
+    if type_line(card).has_supertype("Basic") {
+        LandKind::Basic
+    } else if lines.contains("Search your library for") {
+        LandKind::Fetch
+    } else if lines.contains("tapped unless you control two or fewer other lands") {
+        LandKind::FastLand
+    } else if (lines.contains("enters tapped") || lines.contains("enters the battlefield tapped"))
+        && !lines.contains("tapped unless")
+    {
+        LandKind::Tapped
+    } else {
+        match color_count {
+            0 => LandKind::Colorless,
+            1 => LandKind::Monocolored,
+            _ => LandKind::Dual,
+        }
+    }
+}
+
+/// Shares the deck's basics out by what each color still needs after its
+/// other lands, or by pips when nothing is missing. Leftover basics go to
+/// the largest remainders.
+fn split_basics(
+    colors: &[ColorSources],
+    basic_sources: &[u32; 5],
+    pips: &[u32; 5],
+) -> Vec<BasicLandCount> {
+    let basics: u32 = basic_sources.iter().sum();
+    let needs: Vec<(Color, u32)> = colors
+        .iter()
+        .filter(|c| c.required > 0)
+        .map(|c| {
+            let other_sources = c.sources - basic_sources[c.color as usize];
+            (c.color, c.required.saturating_sub(other_sources))
+        })
+        .collect();
+    let weights: Vec<(Color, u32)> = match needs.iter().all(|(_, need)| *need == 0) {
+        true => needs
+            .iter()
+            .map(|(color, _)| (*color, pips[*color as usize]))
+            .collect(),
+        false => needs,
+    };
+    let total: u32 = weights.iter().map(|(_, weight)| weight).sum();
+    if basics == 0 || total == 0 {
+        return Vec::new();
+    }
+
+    let mut split: Vec<(BasicLandCount, u32)> = weights
+        .iter()
+        .map(|&(color, weight)| {
+            let share = basics * weight;
+            (
+                BasicLandCount {
+                    color,
+                    count: share / total,
+                },
+                share % total,
+            )
+        })
+        .collect();
+    let left = basics - split.iter().map(|(b, _)| b.count).sum::<u32>();
+    let mut by_remainder: Vec<usize> = (0..split.len()).collect();
+    by_remainder.sort_by_key(|&i| std::cmp::Reverse(split[i].1));
+    for &i in by_remainder.iter().take(left as usize) {
+        split[i].0.count += 1;
+    }
+
+    split.into_iter().map(|(basic, _)| basic).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::tests::{card, item};
+
+    #[test]
+    fn test_sources_needed() {
+        assert_eq!(sources_needed(60, 1, 1), 14);
+        assert_eq!(sources_needed(60, 2, 2), 20);
+        assert_eq!(sources_needed(60, 4, 8), 18);
+        assert_eq!(sources_needed(40, 2, 4), 11);
+        assert_eq!(sources_needed(100, 1, 3), 18);
+    }
+
+    #[test]
+    fn test_recommend_mana_base() {
+        let mut bolt = card("Lightning Bolt", "Instant", "Deal 3 damage.");
+        bolt.mana_cost = Some("{R}".into());
+        let mut ball = card("Ball Lightning", "Creature — Elemental", "Trample, haste");
+        ball.mana_cost = Some("{R}{R}{R}".into());
+        let mut goyf = card("Tarmogoyf", "Creature — Lhurgoyf", "");
+        goyf.mana_cost = Some("{1}{G}".into());
+        let mut ram_gang = card("Boggart Ram-Gang", "Creature — Goblin Warrior", "Haste");
+        ram_gang.mana_cost = Some("{R/G}{R/G}{R/G}".into());
+        let mut bauble = card("Mishra's Bauble", "Artifact", "");
+        bauble.mana_cost = Some("{0}".into());
+        let mountain = card("Mountain", "Basic Land — Mountain", "({T}: Add {R}.)");
+        let forest = card("Forest", "Basic Land — Forest", "({T}: Add {G}.)");
+        let foothills = card(
+            "Wooded Foothills",
+            "Land",
+            "{T}, Pay 1 life, Sacrifice Wooded Foothills: Search your library for a Mountain or \
+             Forest card, put it onto the battlefield, then shuffle.",
+        );
+        let copperline = card(
+            "Copperline Gorge",
+            "Land",
+            "Copperline Gorge enters tapped unless you control two or fewer other lands.\n\
+             {T}: Add {R} or {G}.",
+        );
+
+        let items = vec![
+            item(&bolt, 4),
+            item(&ball, 4),
+            item(&goyf, 4),
+            item(&ram_gang, 4),
+            item(&bauble, 20),
+            item(&mountain, 16),
+            item(&forest, 4),
+            item(&foothills, 4),
+        ];
+        let report = recommend_mana_base(&items);
+
+        assert_eq!((report.deck_size, report.land_count), (60, 24));
+        assert_eq!(
+            report.colors,
+            vec![
+                ColorSources {
+                    color: Color::Red,
+                    required: 23,
+                    hardest_card: Some("Ball Lightning".into()),
+                    sources: 20,
+                    under_supported: true,
+                },
+                ColorSources {
+                    color: Color::Green,
+                    required: 13,
+                    hardest_card: Some("Tarmogoyf".into()),
+                    sources: 8,
+                    under_supported: true,
+                },
+            ]
+        );
+        assert_eq!(
+            report.lands.iter().map(|l| &l.kind).collect::<Vec<_>>(),
+            vec![&LandKind::Basic, &LandKind::Basic, &LandKind::Fetch]
+        );
+        assert_eq!(report.lands[2].colors, vec![Color::Red, Color::Green]);
+        // Red still needs 19 and green 9 past the fetches, 20 basics go 14/6.
+        assert_eq!(
+            report.basic_split,
+            vec![
+                BasicLandCount {
+                    color: Color::Red,
+                    count: 14
+                },
+                BasicLandCount {
+                    color: Color::Green,
+                    count: 6
+                },
+            ]
+        );
+
+        let fast = recommend_mana_base(&[item(&copperline, 4)]);
+        assert_eq!(fast.lands[0].kind, LandKind::FastLand);
+        assert!(fast.basic_split.is_empty());
+    }
+}
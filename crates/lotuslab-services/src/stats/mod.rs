@@ -0,0 +1,266 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+mod draw;
+mod goldfish;
+mod mana_base;
+
+use std::collections::HashMap;
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    CardCoreId, CardPrintingDetail, CardPrintingReadRepo, Color, ColorStats, CurveBucket,
+    DeckStats, ListId, ListItemDetail, ListItemReadRepo, RarityCount, TypeCount, TypeLine,
+};
+
+use crate::cards::text::{mana_cost, produced_colors, type_line};
+
+pub use self::draw::*;
+pub use self::goldfish::*;
+pub use self::mana_base::*;
+
+/// Mana curve, color requirements and the other numbers of a list.
+pub async fn deck_stats(store: &Store, list_id: &ListId) -> Result<DeckStats, String> {
+    let items = store
+        .list_item_details_for_list(list_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut ids: Vec<CardCoreId> = items
+        .iter()
+        .filter(|i| i.card_printing_details.is_none())
+        .map(|i| i.card_core_id.clone())
+        .collect();
+    ids.sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+    ids.dedup();
+    let printings = store
+        .card_printings_for_cards(&ids)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(compute_deck_stats(&items, &printings))
+}
+
+/// Items without a picked printing count at the rarity of the card's
+/// cheapest printing out of `printings`, or of its first one when none has
+/// a price.
+pub fn compute_deck_stats(items: &[ListItemDetail], printings: &[CardPrintingDetail]) -> DeckStats {
+    let mut curve: Vec<CurveBucket> = (0..=DeckStats::CURVE_MAX)
+        .map(|mana_value| CurveBucket {
+            mana_value,
+            creatures: 0,
+            noncreatures: 0,
+        })
+        .collect();
+    let mut colors: Vec<ColorStats> = Color::ALL
+        .into_iter()
+        .map(|color| ColorStats {
+            color,
+            pips: 0,
+            land_sources: 0,
+        })
+        .collect();
+    let mut types: Vec<TypeCount> = TypeLine::CARD_TYPES
+        .iter()
+        .map(|card_type| TypeCount {
+            card_type: card_type.to_string(),
+            count: 0,
+        })
+        .collect();
+    let mut rarities: Vec<RarityCount> = Vec::new();
+    // priced printings first, the cheapest of them
+    let price_order = |p: &CardPrintingDetail| (p.price_usd.is_none(), p.price_usd);
+    let mut fallback: HashMap<&CardCoreId, &CardPrintingDetail> = HashMap::new();
+    for printing in printings {
+        match fallback.get(&printing.card_core) {
+            Some(other) if price_order(other) <= price_order(printing) => {}
+            _ => {
+                fallback.insert(&printing.card_core, printing);
+            }
+        }
+    }
+    let (mut card_count, mut land_count, mut total_mana_value) = (0, 0, 0);
+
+    for item in items {
+        let card = &item.card_core_details;
+        let copies = item.quantity.max(0) as u32;
+        let type_line = type_line(card);
+        let is_land = type_line.has_card_type("Land");
+        card_count += copies;
+
+        for count in &mut types {
+            if type_line.has_card_type(&count.card_type) {
+                count.count += copies;
+            }
+        }
+
+        let rarity = item
+            .card_printing_details
+            .as_ref()
+            .or_else(|| fallback.get(&item.card_core_id).copied())
+            .and_then(|p| p.rarity);
+        match rarities.iter_mut().find(|r| r.rarity == rarity) {
+            Some(count) => count.count += copies,
+            None => rarities.push(RarityCount {
+                rarity,
+                count: copies,
+            }),
+        }
+
+        if is_land {
+            land_count += copies;
+            for color in produced_colors(card) {
+                colors[color as usize].land_sources += copies;
+            }
+            continue;
+        }
+
+        let cost = mana_cost(card);
+        let mana_value = cost.mana_value();
+        total_mana_value += mana_value * copies;
+        let bucket = &mut curve[mana_value.min(DeckStats::CURVE_MAX) as usize];
+        match type_line.has_card_type("Creature") {
+            true => bucket.creatures += copies,
+            false => bucket.noncreatures += copies,
+        }
+
+        for color in Color::ALL {
+            colors[color as usize].pips += cost.pips(color) * copies;
+        }
+    }
+
+    types.retain(|t| t.count > 0);
+    rarities.sort_by_key(|r| r.rarity.map(|r| r as u8));
+    let average = |total: u32, count: u32| match count {
+        0 => 0.0,
+        count => total as f64 / count as f64,
+    };
+
+    DeckStats {
+        card_count,
+        land_count,
+        curve,
+        colors,
+        types,
+        average_mana_value: average(total_mana_value, card_count),
+        average_mana_value_without_lands: average(total_mana_value, card_count - land_count),
+        rarities,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lotuslab_types::{CardPrintingDetail, Rarity};
+
+    use super::*;
+    use crate::format::tests::{card, item};
+
+    #[test]
+    fn test_compute_deck_stats() {
+        let mut bolt = card("Lightning Bolt", "Instant", "Deal 3 damage.");
+        bolt.mana_cost = Some("{R}".into());
+        let mut finks = card("Kitchen Finks", "Creature — Ouphe", "Persist");
+        finks.mana_cost = Some("{1}{G/W}{G/W}".into());
+        let mut emrakul = card(
+            "Emrakul, the Aeons Torn",
+            "Legendary Creature — Eldrazi",
+            "",
+        );
+        emrakul.mana_cost = Some("{15}".into());
+        let mountain = card("Mountain", "Basic Land — Mountain", "({T}: Add {R}.)");
+        let city = card(
+            "City of Brass",
+            "Land",
+            "{T}: Add one mana of any color. City of Brass deals 1 damage to you.",
+        );
+
+        let mut bolts = item(&bolt, 4);
+        bolts.card_printing_details = Some(CardPrintingDetail {
+            id: "card_printing:bolt".into(),
+            card_core: bolt.id.clone(),
+            set_code: "M10".into(),
+            collector_number: "146".into(),
+            scryfall_id: None,
+            rarity: Some(Rarity::Common),
+            price_usd: None,
+        });
+        let items = vec![
+            bolts,
+            item(&finks, 2),
+            item(&emrakul, 1),
+            item(&mountain, 10),
+            item(&city, 3),
+        ];
+        // no printing picked for the Finks, the cheapest one counts
+        let finks_printing =
+            |key: &str, rarity: Rarity, price_usd: Option<u32>| CardPrintingDetail {
+                id: format!("card_printing:{}", key).into(),
+                card_core: finks.id.clone(),
+                set_code: key.to_uppercase(),
+                collector_number: "1".into(),
+                scryfall_id: None,
+                rarity: Some(rarity),
+                price_usd,
+            };
+        let printings = vec![
+            finks_printing("sld", Rarity::Rare, None),
+            finks_printing("shm", Rarity::Uncommon, Some(50)),
+            finks_printing("pshm", Rarity::Special, Some(900)),
+        ];
+
+        let stats = compute_deck_stats(&items, &printings);
+
+        assert_eq!(stats.card_count, 20);
+        assert_eq!(stats.land_count, 13);
+        assert_eq!(
+            (stats.curve[1].noncreatures, stats.curve[3].creatures),
+            (4, 2)
+        );
+        assert_eq!(stats.curve[7].creatures, 1);
+        assert_eq!(stats.curve.len(), 8);
+
+        let red = &stats.colors[Color::Red as usize];
+        assert_eq!((red.pips, red.land_sources), (4, 13));
+        // hybrid symbols don't count, like for the mana base
+        let white = &stats.colors[Color::White as usize];
+        assert_eq!((white.pips, white.land_sources), (0, 3));
+
+        assert_eq!(
+            stats.types,
+            vec![
+                TypeCount {
+                    card_type: "Creature".into(),
+                    count: 3
+                },
+                TypeCount {
+                    card_type: "Instant".into(),
+                    count: 4
+                },
+                TypeCount {
+                    card_type: "Land".into(),
+                    count: 13
+                },
+            ]
+        );
+        assert_eq!(stats.average_mana_value, 25.0 / 20.0);
+        assert_eq!(stats.average_mana_value_without_lands, 25.0 / 7.0);
+        assert_eq!(
+            stats.rarities,
+            vec![
+                RarityCount {
+                    rarity: None,
+                    count: 14
+                },
+                RarityCount {
+                    rarity: Some(Rarity::Common),
+                    count: 4
+                },
+                RarityCount {
+                    rarity: Some(Rarity::Uncommon),
+                    count: 2
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::HashMap;
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    CardCoreId, CardUsage, CardUsageRow, Folder, FolderId, FolderReadRepo, ListCardUsage,
+    ListItemReadRepo, ProjectCardUsage,
+};
+
+/// Which projects use a card and how many copies each, for the card page.
+pub async fn get_card_usage(store: &Store, card_core_id: &CardCoreId) -> Result<CardUsage, String> {
+    let rows = store
+        .card_usage_rows(std::slice::from_ref(card_core_id))
+        .await
+        .map_err(|e| e.to_string())?;
+    let folders = match rows.is_empty() {
+        true => Vec::new(),
+        false => store.list_folders().await.map_err(|e| e.to_string())?,
+    };
+
+    Ok(group_card_usage(card_core_id, rows, folders))
+}
+
+/// Groups list items by project, projects sorted by name.
+fn group_card_usage(
+    card_core_id: &CardCoreId,
+    rows: Vec<CardUsageRow>,
+    folders: Vec<Folder>,
+) -> CardUsage {
+    let folders: HashMap<FolderId, Folder> = folders
+        .into_iter()
+        .map(|folder| (folder.id.clone(), folder))
+        .collect();
+
+    let mut projects: Vec<ProjectCardUsage> = Vec::new();
+    for row in rows {
+        let quantity = row.quantity.max(0) as u32;
+        let list = ListCardUsage {
+            list_id: row.list_id,
+            list_name: row.list_name,
+            list_item_id: row.list_item_id,
+            quantity,
+            printing: row.printing,
+        };
+
+        match projects.iter_mut().find(|p| p.project_id == row.project_id) {
+            Some(project) => {
+                project.quantity += quantity;
+                project.lists.push(list);
+            }
+            None => projects.push(ProjectCardUsage {
+                project_id: row.project_id,
+                project_name: row.project_name,
+                folder_path: folder_path(&folders, &row.folder_id),
+                quantity,
+                lists: vec![list],
+            }),
+        }
+    }
+    projects.sort_by(|a, b| a.project_name.cmp(&b.project_name));
+
+    CardUsage {
+        card_core_id: card_core_id.clone(),
+        quantity: projects.iter().map(|p| p.quantity).sum(),
+        projects,
+    }
+}
+
+/// The folder and its parents, root first.
+fn folder_path(folders: &HashMap<FolderId, Folder>, id: &FolderId) -> Vec<Folder> {
+    let mut path = Vec::new();
+    let mut next = Some(id);
+    while let Some(folder) = next.and_then(|id| folders.get(id)) {
+        // Folders can't be moved into themselves, this only guards against
+        // a broken database.
+        if path.len() > folders.len() {
+            break;
+        }
+        path.push(folder.clone());
+        next = folder.parent.as_ref();
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use lotuslab_types::ListRole;
+
+    use super::*;
+
+    fn folder(id: &str, parent: Option<&str>) -> Folder {
+        Folder {
+            id: id.into(),
+            name: id.trim_start_matches("folder:").into(),
+            parent: parent.map(FolderId::from),
+        }
+    }
+
+    fn row(item: &str, list: &str, project: &str, folder: &str, quantity: i32) -> CardUsageRow {
+        CardUsageRow {
+            list_item_id: item.into(),
+            card_core_id: "card_core:sol-ring".into(),
+            list_id: list.into(),
+            list_name: list.trim_start_matches("list:").into(),
+            list_role: ListRole::Main,
+            project_id: project.into(),
+            project_name: project.trim_start_matches("project:").into(),
+            folder_id: folder.into(),
+            quantity,
+            printing: None,
+        }
+    }
+
+    #[test]
+    fn test_group_card_usage() {
+        let folders = vec![
+            folder("folder:root", None),
+            folder("folder:commander", Some("folder:root")),
+            folder("folder:mono-red", Some("folder:commander")),
+        ];
+        let rows = vec![
+            row(
+                "list_contains:1",
+                "list:main",
+                "project:zada",
+                "folder:mono-red",
+                1,
+            ),
+            row(
+                "list_contains:2",
+                "list:main-2",
+                "project:atraxa",
+                "folder:root",
+                1,
+            ),
+            row(
+                "list_contains:3",
+                "list:maybe",
+                "project:zada",
+                "folder:mono-red",
+                2,
+            ),
+        ];
+
+        let usage = group_card_usage(&"card_core:sol-ring".into(), rows, folders);
+        assert_eq!(usage.quantity, 4);
+        assert_eq!(
+            usage
+                .projects
+                .iter()
+                .map(|p| (p.project_name.as_str(), p.quantity, p.lists.len()))
+                .collect::<Vec<_>>(),
+            vec![("atraxa", 1, 1), ("zada", 3, 2)]
+        );
+        assert_eq!(
+            usage.projects[1]
+                .folder_path
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["root", "commander", "mono-red"]
+        );
+    }
+}
@@ -0,0 +1,44 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    CollectionEntry, CollectionEntryId, CollectionReadRepo, CollectionWriteRepo,
+    NewCollectionEntry, UpdateCollectionEntry,
+};
+
+pub async fn list_collection(store: &Store) -> Result<Vec<CollectionEntry>, String> {
+    store
+        .list_collection_entries()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn create_collection_entry(
+    store: &Store,
+    new: NewCollectionEntry,
+) -> Result<CollectionEntry, String> {
+    store
+        .create_collection_entry(new)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn update_collection_entry(
+    store: &Store,
+    id: &CollectionEntryId,
+    patch: UpdateCollectionEntry,
+) -> Result<CollectionEntry, String> {
+    store
+        .update_collection_entry(id, patch)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn delete_collection_entry(store: &Store, id: &CollectionEntryId) -> Result<(), String> {
+    store
+        .delete_collection_entry(id)
+        .await
+        .map_err(|e| e.to_string())
+}
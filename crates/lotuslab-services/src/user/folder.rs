@@ -39,7 +39,7 @@ pub async fn rename_folder(store: &Store, id: &FolderId, name: String) -> Result
 
     assert!(folder.parent.is_some());
     let peers = store
-        .get_folder_children(&folder.parent.as_ref().unwrap())
+        .get_folder_children(folder.parent.as_ref().unwrap())
         .await
         .map_err(|e| e.to_string())?;
     if peers.folders.iter().any(|peer| peer.name == name) {
@@ -99,6 +99,6 @@ pub async fn move_folder(
     Ok(moved)
 }
 
-pub async fn delete_folder(store: &Store, id: &FolderId) -> Result<(), String> {
+pub async fn delete_folder(_store: &Store, _id: &FolderId) -> Result<(), String> {
     unimplemented!();
 }
@@ -0,0 +1,269 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::HashMap;
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    BuyListFormat, CardAllocation, CardCoreId, CardPrintingDetail, CardPrintingReadRepo,
+    CardUsageRow, CollectionEntry, CollectionReadRepo, HaveNeedCard, HaveNeedReport,
+    ListItemDetail, ListItemReadRepo, ListRole, ProjectId,
+};
+
+use crate::format::{project_deck, quantity};
+
+/// Checks a project's main deck and sideboard against the collection.
+pub async fn get_have_need(
+    store: &Store,
+    project_id: &ProjectId,
+) -> Result<HaveNeedReport, String> {
+    let (main, sideboard) = project_deck(store, project_id).await?;
+    let items: Vec<ListItemDetail> = main.into_iter().chain(sideboard).collect();
+
+    let mut ids: Vec<CardCoreId> = items.iter().map(|i| i.card_core_id.clone()).collect();
+    ids.sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+    ids.dedup();
+
+    let entries = store
+        .collection_entries_for_cards(&ids)
+        .await
+        .map_err(|e| e.to_string())?;
+    let usage = store
+        .card_usage_rows(&ids)
+        .await
+        .map_err(|e| e.to_string())?;
+    let printings = store
+        .card_printings_for_cards(&ids)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(have_need(project_id, &items, &entries, usage, &printings))
+}
+
+/// The missing cards of a project as a list to paste into a store.
+pub async fn export_buy_list(
+    store: &Store,
+    project_id: &ProjectId,
+    format: BuyListFormat,
+) -> Result<String, String> {
+    let report = get_have_need(store, project_id).await?;
+    Ok(lotuslab_import::write_buy_list(&report, format))
+}
+
+fn have_need(
+    project_id: &ProjectId,
+    items: &[ListItemDetail],
+    entries: &[CollectionEntry],
+    usage: Vec<CardUsageRow>,
+    printings: &[CardPrintingDetail],
+) -> HaveNeedReport {
+    let mut owned: HashMap<&CardCoreId, u32> = HashMap::new();
+    for entry in entries {
+        *owned.entry(&entry.card_core_id).or_default() += entry.quantity.max(0) as u32;
+    }
+
+    // Maybeboards are never built, so they don't hold on to copies.
+    let mut allocations: HashMap<CardCoreId, Vec<CardAllocation>> = HashMap::new();
+    for row in usage {
+        if row.project_id == *project_id || row.list_role == ListRole::Maybeboard {
+            continue;
+        }
+        let quantity = row.quantity.max(0) as u32;
+        let projects = allocations.entry(row.card_core_id).or_default();
+        match projects.iter_mut().find(|p| p.project_id == row.project_id) {
+            Some(project) => project.quantity += quantity,
+            None => projects.push(CardAllocation {
+                project_id: row.project_id,
+                project_name: row.project_name,
+                quantity,
+            }),
+        }
+    }
+
+    let mut cheapest: HashMap<&CardCoreId, &CardPrintingDetail> = HashMap::new();
+    for printing in printings.iter().filter(|p| p.price_usd.is_some()) {
+        match cheapest.get(&printing.card_core) {
+            Some(other) if other.price_usd <= printing.price_usd => {}
+            _ => {
+                cheapest.insert(&printing.card_core, printing);
+            }
+        }
+    }
+
+    let mut cards: Vec<HaveNeedCard> = Vec::new();
+    for item in items {
+        match cards
+            .iter_mut()
+            .find(|c| c.card_core_id == item.card_core_id)
+        {
+            Some(card) => {
+                card.wanted += quantity(item);
+                if card.buy_printing.is_none() {
+                    card.buy_printing = item.card_printing_details.clone();
+                }
+            }
+            None => cards.push(HaveNeedCard {
+                card_core_id: item.card_core_id.clone(),
+                name: item.card_core_details.name.clone(),
+                wanted: quantity(item),
+                owned: 0,
+                allocated: 0,
+                have: 0,
+                missing: 0,
+                allocations: Vec::new(),
+                buy_printing: item.card_printing_details.clone(),
+                unit_price: None,
+            }),
+        }
+    }
+    cards.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut report = HaveNeedReport {
+        project_id: project_id.clone(),
+        cards: Vec::with_capacity(cards.len()),
+        wanted: 0,
+        have: 0,
+        missing: 0,
+        cost_to_complete: 0,
+        unpriced: 0,
+    };
+    for mut card in cards {
+        let mut projects = allocations.remove(&card.card_core_id).unwrap_or_default();
+        projects.sort_by(|a, b| a.project_name.cmp(&b.project_name));
+
+        card.owned = owned.get(&card.card_core_id).copied().unwrap_or_default();
+        card.allocated = card
+            .owned
+            .min(projects.iter().map(|p| p.quantity).sum::<u32>());
+        card.have = card.wanted.min(card.owned - card.allocated);
+        card.missing = card.wanted - card.have;
+        card.allocations = projects;
+
+        // The printing the project picked, unless it has no price and
+        // another printing does.
+        let priced = card
+            .buy_printing
+            .as_ref()
+            .is_some_and(|p| p.price_usd.is_some());
+        if let Some(printing) = cheapest.get(&card.card_core_id).filter(|_| !priced) {
+            card.buy_printing = Some((*printing).clone());
+        }
+        card.unit_price = card.buy_printing.as_ref().and_then(|p| p.price_usd);
+
+        report.wanted += card.wanted;
+        report.have += card.have;
+        report.missing += card.missing;
+        match card.unit_price {
+            Some(price) => report.cost_to_complete += price * card.missing,
+            None => report.unpriced += card.missing,
+        }
+        report.cards.push(card);
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use lotuslab_types::{CardCondition, Finish};
+
+    use super::*;
+    use crate::format::tests::{card, item};
+
+    fn printing(card_core: &str, set_code: &str, price_usd: Option<u32>) -> CardPrintingDetail {
+        CardPrintingDetail {
+            id: format!("card_printing:{}-{}", card_core, set_code).into(),
+            card_core: format!("card_core:{}", card_core).into(),
+            set_code: set_code.into(),
+            collector_number: "1".into(),
+            scryfall_id: None,
+            rarity: None,
+            price_usd,
+        }
+    }
+
+    fn entry(card_core: &str, quantity: i32) -> CollectionEntry {
+        CollectionEntry {
+            id: format!("collection_entry:{}", card_core).into(),
+            card_core_id: format!("card_core:{}", card_core).into(),
+            card_printing_id: Some(format!("card_printing:{}", card_core).into()),
+            quantity,
+            finish: Finish::Nonfoil,
+            condition: CardCondition::NearMint,
+            language: "en".into(),
+            purchase_price: None,
+            acquired_on: None,
+            location: None,
+        }
+    }
+
+    fn usage(card_core: &str, list_role: ListRole, project: &str, quantity: i32) -> CardUsageRow {
+        CardUsageRow {
+            list_item_id: format!("list_contains:{}-{}", project, card_core).into(),
+            card_core_id: format!("card_core:{}", card_core).into(),
+            list_id: format!("list:{}-{}", project, list_role.as_str()).into(),
+            list_name: list_role.as_str().into(),
+            list_role,
+            project_id: format!("project:{}", project).into(),
+            project_name: project.into(),
+            folder_id: "folder:root".into(),
+            quantity,
+            printing: None,
+        }
+    }
+
+    #[test]
+    fn test_have_need() {
+        let sol_ring = card("Sol Ring", "Artifact", "{T}: Add {C}{C}.");
+        let bolt = card("Lightning Bolt", "Instant", "Deal 3 damage.");
+        let brainstorm = card("Brainstorm", "Instant", "Draw three cards.");
+
+        let mut sol_rings = item(&sol_ring, 1);
+        sol_rings.card_printing_details = Some(printing("sol-ring", "C21", Some(150)));
+        let items = vec![
+            sol_rings,
+            item(&bolt, 4),
+            item(&brainstorm, 1),
+            item(&bolt, 1),
+        ];
+        let entries = vec![entry("sol-ring", 2), entry("lightning-bolt", 3)];
+        let usage = vec![
+            usage("sol-ring", ListRole::Main, "atraxa", 1),
+            usage("sol-ring", ListRole::Main, "zada", 1),
+            usage("lightning-bolt", ListRole::Maybeboard, "atraxa", 4),
+            usage("lightning-bolt", ListRole::Main, "own", 4),
+        ];
+        let printings = vec![
+            printing("lightning-bolt", "M10", Some(125)),
+            printing("lightning-bolt", "2XM", Some(99)),
+            printing("brainstorm", "ICE", None),
+        ];
+
+        let report = have_need(&"project:own".into(), &items, &entries, usage, &printings);
+        assert_eq!(
+            report
+                .cards
+                .iter()
+                .map(|c| (c.name.as_str(), c.wanted, c.owned, c.allocated, c.have))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Brainstorm", 1, 0, 0, 0),
+                ("Lightning Bolt", 5, 3, 0, 3),
+                ("Sol Ring", 1, 2, 2, 0),
+            ]
+        );
+        assert_eq!(
+            report.cards[1]
+                .buy_printing
+                .as_ref()
+                .map(|p| p.set_code.as_str()),
+            Some("2XM")
+        );
+        assert_eq!(report.cards[2].unit_price, Some(150));
+        assert_eq!(report.cards[2].allocations.len(), 2);
+        assert_eq!((report.wanted, report.have, report.missing), (7, 3, 4));
+        assert_eq!(report.cost_to_complete, 2 * 99 + 150);
+        assert_eq!(report.unpriced, 1);
+    }
+}
@@ -2,5 +2,13 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+mod card_usage;
+mod collection;
 mod folder;
+mod have_need;
+mod tag_rule;
+pub use self::card_usage::*;
+pub use self::collection::*;
 pub use self::folder::*;
+pub use self::have_need::*;
+pub use self::tag_rule::*;
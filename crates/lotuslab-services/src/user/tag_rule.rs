@@ -0,0 +1,247 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::HashMap;
+
+use lotuslab_db::Store;
+use lotuslab_types::{
+    AutoTagReport, CardCoreId, CardQuery, CardSearchRepo, ListId, ListItemReadRepo,
+    ListItemSummary, ListItemWriteRepo, NewTag, NewTagRule, RepoError, TagId, TagReadRepo, TagRule,
+    TagRuleId, TagRuleReadRepo, TagRuleWriteRepo, TagWriteRepo, UpdateTagRule,
+};
+
+/// Tag names and queries of the rules [add_default_tag_rules] sets up, the
+/// usual roles of a Commander deck.
+const DEFAULT_TAG_RULES: &[(&str, &str)] = &[
+    (
+        "Ramp",
+        r#"-t:land mv<=3 (o:"add {" or o:"add one mana" or o:"search your library for a basic land" or o:"search your library for up to two basic land")"#,
+    ),
+    (
+        "Card Draw",
+        r#"o:"draw a card" or o:"draw two cards" or o:"draw three cards" or o:"draws a card" or o:"draw cards equal""#,
+    ),
+    (
+        "Removal",
+        r#"-t:land (o:"destroy target" or o:"exile target" or o:"damage to target creature" or o:"damage to any target" or o:"return target nonland permanent")"#,
+    ),
+    (
+        "Board Wipe",
+        r#"o:"destroy all" or o:"exile all" or o:"damage to each creature" or o:"return all nonland permanents""#,
+    ),
+    ("Counterspell", r#"o:"counter target""#),
+    (
+        "Tutor",
+        r#"o:"search your library for" -o:"basic land" -o:"land card""#,
+    ),
+    (
+        "Recursion",
+        r#"o:"from your graveyard to your hand" or o:"from your graveyard to the battlefield" or o:"from your graveyard onto the battlefield""#,
+    ),
+    (
+        "Protection",
+        r#"o:"gain hexproof" or o:"gains hexproof" or o:"gain indestructible" or o:"gains indestructible" or o:"phase out""#,
+    ),
+];
+
+pub async fn list_tag_rules(store: &Store) -> Result<Vec<TagRule>, String> {
+    store.list_tag_rules().await.map_err(|e| e.to_string())
+}
+
+pub async fn create_tag_rule(store: &Store, new: NewTagRule) -> Result<TagRule, String> {
+    store.create_tag_rule(new).await.map_err(|e| e.to_string())
+}
+
+pub async fn update_tag_rule(
+    store: &Store,
+    id: &TagRuleId,
+    patch: UpdateTagRule,
+) -> Result<TagRule, String> {
+    store
+        .update_tag_rule(id, patch)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn delete_tag_rule(store: &Store, id: &TagRuleId) -> Result<(), String> {
+    store.delete_tag_rule(id).await.map_err(|e| e.to_string())
+}
+
+/// Adds the default Commander rules, creating their tags when missing. Rules
+/// that are already there aren't added twice.
+pub async fn add_default_tag_rules(store: &Store) -> Result<Vec<TagRule>, String> {
+    let existing = store.list_tag_rules().await.map_err(|e| e.to_string())?;
+    let mut added = Vec::new();
+
+    for (name, query) in DEFAULT_TAG_RULES {
+        let tag = match store.find_tag_by_name(name).await {
+            Ok(tag) => tag,
+            Err(RepoError::NotFound) => store
+                .create_tag(NewTag {
+                    name: name.to_string(),
+                    color: None,
+                })
+                .await
+                .map_err(|e| e.to_string())?,
+            Err(e) => return Err(e.to_string()),
+        };
+        if existing
+            .iter()
+            .any(|rule| rule.tag == tag.id && rule.query == *query)
+        {
+            continue;
+        }
+
+        let rule = store
+            .create_tag_rule(NewTagRule {
+                query: query.to_string(),
+                tag: tag.id,
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+        added.push(rule);
+    }
+
+    Ok(added)
+}
+
+/// [run_tag_rules] for a list an import just filled. The cards are in by
+/// then, so a failing rule only leaves them untagged instead of failing the
+/// import.
+pub(crate) async fn tag_imported_items(store: &Store, list_id: &ListId) {
+    if let Err(e) = run_tag_rules(store, list_id).await {
+        log::warn!("failed to run tag rules on {}: {}", list_id, e);
+    }
+}
+
+/// Runs every enabled tag rule over a list. Auto tags of rules that no
+/// longer match come off, tags the user set or dismissed are left alone.
+pub async fn run_tag_rules(store: &Store, list_id: &ListId) -> Result<AutoTagReport, String> {
+    let rules: Vec<TagRule> = store
+        .list_tag_rules()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|rule| rule.enabled)
+        .collect();
+    let items = store
+        .list_items_for_list(list_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut cards: Vec<CardCoreId> = items.iter().map(|i| i.card_core_id.clone()).collect();
+    cards.sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+    cards.dedup();
+
+    let mut matches: HashMap<CardCoreId, Vec<TagId>> = HashMap::new();
+    for rule in &rules {
+        let query = CardQuery::parse(&rule.query).map_err(|e| e.to_string())?;
+        let matching = store
+            .filter_cards(&query, &cards)
+            .await
+            .map_err(|e| e.to_string())?;
+        for card in matching {
+            matches.entry(card).or_default().push(rule.tag.clone());
+        }
+    }
+
+    let mut report = AutoTagReport {
+        items_checked: items.len() as u32,
+        ..AutoTagReport::default()
+    };
+    for item in &items {
+        let matched = matches
+            .get(&item.card_core_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let auto_tags = auto_tags(item, matched);
+
+        let added = auto_tags
+            .iter()
+            .filter(|t| !item.auto_tags.contains(t))
+            .count() as u32;
+        let removed = item
+            .auto_tags
+            .iter()
+            .filter(|t| !auto_tags.contains(t))
+            .count() as u32;
+        if added == 0 && removed == 0 {
+            continue;
+        }
+
+        store
+            .set_auto_tags(&item.id, auto_tags)
+            .await
+            .map_err(|e| e.to_string())?;
+        report.tags_added += added;
+        report.tags_removed += removed;
+    }
+
+    Ok(report)
+}
+
+/// The auto tags an item gets from the tags of the rules its card matched.
+/// Tags the user dismissed or set by hand are skipped, a hand set tag stays
+/// the user's even when a rule agrees.
+fn auto_tags(item: &ListItemSummary, matched: &[TagId]) -> Vec<TagId> {
+    let mut auto_tags: Vec<TagId> = Vec::new();
+    for tag in matched {
+        let manual = item.tags.contains(tag) && !item.auto_tags.contains(tag);
+        if !manual && !item.dismissed_tags.contains(tag) && !auto_tags.contains(tag) {
+            auto_tags.push(tag.clone());
+        }
+    }
+    auto_tags
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    #[test]
+    fn test_default_tag_rules_parse() {
+        for (name, query) in DEFAULT_TAG_RULES {
+            assert!(
+                CardQuery::parse(query).is_ok(),
+                "rule for {name} should parse"
+            );
+        }
+    }
+
+    #[test]
+    fn test_auto_tags() {
+        let tag = |name: &str| TagId(format!("tag:{name}"));
+        let item = ListItemSummary {
+            id: "list_contains:1".into(),
+            list_id: "list:main".into(),
+            card_core_id: "card_core:sol-ring".into(),
+            selected_printing: None,
+            quantity: 1,
+            notes: None,
+            custom_image_uri: None,
+            tags: vec![tag("ramp"), tag("staple"), tag("old")],
+            auto_tags: vec![tag("ramp"), tag("old")],
+            dismissed_tags: vec![tag("artifact")],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        assert_eq!(
+            auto_tags(
+                &item,
+                &[
+                    tag("ramp"),
+                    tag("staple"),
+                    tag("artifact"),
+                    tag("ramp"),
+                    tag("combo")
+                ]
+            ),
+            vec![tag("ramp"), tag("combo")]
+        );
+        assert!(auto_tags(&item, &[]).is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// What a backup holds. Card data can always be imported again, so user data
+/// alone makes for much smaller backups.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub enum BackupScope {
+    #[default]
+    User,
+    UserAndCards,
+}
+
+impl BackupScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackupScope::User => "user",
+            BackupScope::UserAndCards => "user_and_cards",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "user" => Some(BackupScope::User),
+            "user_and_cards" => Some(BackupScope::UserAndCards),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct BackupInfo {
+    pub path: String,
+    pub scope: BackupScope,
+    pub schema_version: u32,
+    /// RFC 3339 timestamp of when the backup was taken.
+    pub created_at: String,
+    pub automatic: bool,
+    pub size_bytes: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct BackupSettings {
+    pub enabled: bool,
+    pub interval_hours: u32,
+    /// How many automatic backups to keep, older ones are deleted.
+    pub keep: u32,
+    pub scope: BackupScope,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        BackupSettings {
+            enabled: true,
+            interval_hours: 24,
+            keep: 7,
+            scope: BackupScope::User,
+        }
+    }
+}
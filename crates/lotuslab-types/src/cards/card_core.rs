@@ -2,7 +2,63 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{CardCoreId, Color, RepoError};
+
+/// Oracle-level card data shared by every printing of a card.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CardCoreDetail {
+    pub id: CardCoreId,
+    pub name: String,
+    /// The front face's mana cost.
+    pub mana_cost: Option<String>,
+    /// Mana costs of the other faces of a multi-faced card, in order. Faces
+    /// without a mana cost are left out.
+    #[serde(default)]
+    pub back_mana_costs: Vec<String>,
+    /// Colors of the color indicators on any face.
+    #[serde(default)]
+    pub color_indicator: Vec<Color>,
+    pub type_line: String,
+    pub oracle_text: Option<String>,
+    /// Keyed by format name as MTGJSON spells it, e.g. `"commander"`. Formats
+    /// the card isn't legal in are left out.
+    #[serde(default)]
+    pub legalities: BTreeMap<String, Legality>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub enum Legality {
+    Legal,
+    Restricted,
+    Banned,
+}
+
+impl Legality {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Legality::Legal => "legal",
+            Legality::Restricted => "restricted",
+            Legality::Banned => "banned",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "legal" => Some(Legality::Legal),
+            "restricted" => Some(Legality::Restricted),
+            "banned" => Some(Legality::Banned),
+            _ => None,
+        }
+    }
+}
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct CardCoreDetail {}
+#[async_trait::async_trait]
+pub trait CardCoreReadRepo: Send + Sync {
+    async fn get_card_core(&self, id: &CardCoreId) -> Result<CardCoreDetail, RepoError>;
+    async fn find_card_core_by_name(&self, name: &str) -> Result<CardCoreDetail, RepoError>;
+}
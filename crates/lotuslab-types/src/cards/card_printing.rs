@@ -3,6 +3,101 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use serde::{Deserialize, Serialize};
+use specta::Type;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct CardPrintingDetail {}
+use crate::{CardCoreId, CardPrintingId, RepoError};
+
+/// Data specific to one printing of a card.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CardPrintingDetail {
+    pub id: CardPrintingId,
+    pub card_core: CardCoreId,
+    pub set_code: String,
+    pub collector_number: String,
+    pub scryfall_id: Option<String>,
+    /// `None` for printings imported before rarities were tracked.
+    #[serde(default)]
+    pub rarity: Option<Rarity>,
+    /// Market price in US cents, the nonfoil one unless the printing only
+    /// comes in foil. Only Scryfall catalogs have prices.
+    #[serde(default)]
+    pub price_usd: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Mythic,
+    Special,
+    Bonus,
+}
+
+impl Rarity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rarity::Common => "common",
+            Rarity::Uncommon => "uncommon",
+            Rarity::Rare => "rare",
+            Rarity::Mythic => "mythic",
+            Rarity::Special => "special",
+            Rarity::Bonus => "bonus",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "common" => Some(Rarity::Common),
+            "uncommon" => Some(Rarity::Uncommon),
+            "rare" => Some(Rarity::Rare),
+            "mythic" => Some(Rarity::Mythic),
+            "special" => Some(Rarity::Special),
+            "bonus" => Some(Rarity::Bonus),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub enum Finish {
+    #[default]
+    Nonfoil,
+    Foil,
+    Etched,
+}
+
+impl Finish {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Finish::Nonfoil => "nonfoil",
+            Finish::Foil => "foil",
+            Finish::Etched => "etched",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "nonfoil" => Some(Finish::Nonfoil),
+            "foil" => Some(Finish::Foil),
+            "etched" => Some(Finish::Etched),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+pub trait CardPrintingReadRepo: Send + Sync {
+    async fn get_card_printing(&self, id: &CardPrintingId)
+    -> Result<CardPrintingDetail, RepoError>;
+    async fn find_card_printing(
+        &self,
+        set_code: &str,
+        collector_number: &str,
+    ) -> Result<CardPrintingDetail, RepoError>;
+    /// Every printing of the given cards.
+    async fn card_printings_for_cards(
+        &self,
+        ids: &[CardCoreId],
+    ) -> Result<Vec<CardPrintingDetail>, RepoError>;
+}
@@ -0,0 +1,143 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{
+    CardCoreDetail, CardCoreId, CardPrintingDetail, CardPrintingId, CardRulings, CatalogUpdateId,
+    Legality, RepoError,
+};
+
+/// The full contents of the card catalog, as read from an external source or
+/// from the database.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CatalogData {
+    pub cards: Vec<CardCoreDetail>,
+    pub printings: Vec<CardPrintingDetail>,
+    /// `None` when the source doesn't carry rulings, the stored rulings are
+    /// kept as they are then.
+    pub rulings: Option<Vec<CardRulings>>,
+}
+
+/// The writes needed to bring the stored catalog in line with new data.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CatalogChanges {
+    pub upsert_cards: Vec<CardCoreDetail>,
+    pub delete_cards: Vec<CardCoreId>,
+    pub upsert_printings: Vec<CardPrintingDetail>,
+    pub delete_printings: Vec<CardPrintingId>,
+    /// Printings that got a new id, e.g. when the catalog switched sources.
+    /// References to `from` move to `to`, `from` is in `delete_printings`.
+    pub remap_printings: Vec<PrintingRemap>,
+    /// Each entry replaces all stored rulings of its card, an empty entry
+    /// removes them.
+    pub replace_rulings: Vec<CardRulings>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrintingRemap {
+    pub from: CardPrintingId,
+    pub to: CardPrintingId,
+}
+
+impl CatalogChanges {
+    pub fn is_empty(&self) -> bool {
+        self.upsert_cards.is_empty()
+            && self.delete_cards.is_empty()
+            && self.upsert_printings.is_empty()
+            && self.delete_printings.is_empty()
+            && self.remap_printings.is_empty()
+            && self.replace_rulings.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CatalogCardChange {
+    pub card_core_id: CardCoreId,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct OracleErrata {
+    pub card_core_id: CardCoreId,
+    pub name: String,
+    pub old_text: Option<String>,
+    pub new_text: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct LegalityChange {
+    pub card_core_id: CardCoreId,
+    pub name: String,
+    pub format: String,
+    /// `None` means not legal in the format.
+    pub old: Option<Legality>,
+    pub new: Option<Legality>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct RemovedPrinting {
+    pub card_printing_id: CardPrintingId,
+    pub name: String,
+    pub set_code: String,
+    pub collector_number: String,
+}
+
+/// What changed in one catalog update, as shown to the user.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct CatalogChangeReport {
+    pub new_cards: Vec<CatalogCardChange>,
+    pub removed_cards: Vec<CatalogCardChange>,
+    pub oracle_errata: Vec<OracleErrata>,
+    pub legality_changes: Vec<LegalityChange>,
+    pub new_printings: u32,
+    pub removed_printings: Vec<RemovedPrinting>,
+    /// Cards whose data changed in ways not listed above.
+    pub other_updates: u32,
+    /// Cards with new or changed rulings.
+    #[serde(default)]
+    pub ruling_updates: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CatalogUpdateSummary {
+    pub id: CatalogUpdateId,
+    /// RFC 3339 timestamp of when the update was applied.
+    pub applied_at: String,
+    /// The source's own version, e.g. MTGJSON's `meta.version`.
+    pub source_version: Option<String>,
+    pub new_cards: u32,
+    pub oracle_errata: u32,
+    pub legality_changes: u32,
+    pub removed_printings: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CatalogUpdate {
+    pub summary: CatalogUpdateSummary,
+    pub report: CatalogChangeReport,
+}
+
+#[async_trait::async_trait]
+pub trait CatalogReadRepo: Send + Sync {
+    /// Reads every card, printing and ruling in the catalog.
+    async fn catalog_snapshot(&self) -> Result<CatalogData, RepoError>;
+}
+
+#[async_trait::async_trait]
+pub trait CatalogWriteRepo: Send + Sync {
+    async fn apply_catalog_changes(&self, changes: CatalogChanges) -> Result<(), RepoError>;
+}
+
+#[async_trait::async_trait]
+pub trait CatalogUpdateRepo: Send + Sync {
+    async fn record_catalog_update(
+        &self,
+        source_version: Option<String>,
+        report: CatalogChangeReport,
+    ) -> Result<CatalogUpdateSummary, RepoError>;
+    async fn list_catalog_updates(&self) -> Result<Vec<CatalogUpdateSummary>, RepoError>;
+    async fn get_catalog_update(&self, id: &CatalogUpdateId) -> Result<CatalogUpdate, RepoError>;
+}
@@ -0,0 +1,56 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// The five colors of Magic, ordered WUBRG.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Type,
+)]
+pub enum Color {
+    White,
+    Blue,
+    Black,
+    Red,
+    Green,
+}
+
+impl Color {
+    pub const ALL: [Color; 5] = [
+        Color::White,
+        Color::Blue,
+        Color::Black,
+        Color::Red,
+        Color::Green,
+    ];
+
+    /// The letter used in mana symbols, e.g. `'U'` for blue.
+    pub fn symbol(&self) -> char {
+        match self {
+            Color::White => 'W',
+            Color::Blue => 'U',
+            Color::Black => 'B',
+            Color::Red => 'R',
+            Color::Green => 'G',
+        }
+    }
+
+    pub fn from_symbol(symbol: char) -> Option<Self> {
+        Color::ALL
+            .into_iter()
+            .find(|c| c.symbol() == symbol.to_ascii_uppercase())
+    }
+
+    /// The basic land type that taps for this color.
+    pub fn basic_land_type(&self) -> &'static str {
+        match self {
+            Color::White => "Plains",
+            Color::Blue => "Island",
+            Color::Black => "Swamp",
+            Color::Red => "Mountain",
+            Color::Green => "Forest",
+        }
+    }
+}
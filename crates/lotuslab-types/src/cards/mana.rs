@@ -0,0 +1,306 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Mana costs and mana symbols, as written in braces: `{2}{W/U}{B/P}{X}`.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use thiserror::Error;
+
+use crate::Color;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub enum ManaSymbol {
+    /// `{3}`
+    Generic(u32),
+    /// `{W}`
+    Colored(Color),
+    /// `{C}`, paid with colorless mana only.
+    Colorless,
+    /// `{S}`, paid with mana from a snow source.
+    Snow,
+    /// `{X}`, `{Y}` or `{Z}`.
+    Variable(char),
+    /// `{W/U}`
+    Hybrid(Color, Color),
+    /// `{2/W}`, two generic or one of the color.
+    TwoHybrid(Color),
+    /// `{C/W}`, colorless or the color.
+    ColorlessHybrid(Color),
+    /// `{W/P}`, the color or 2 life.
+    Phyrexian(Color),
+    /// `{G/U/P}`
+    HybridPhyrexian(Color, Color),
+    /// `{½}`, or `{HW}` for half a white mana.
+    Half(Option<Color>),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ManaCostError {
+    #[error("unknown mana symbol '{{{0}}}'")]
+    UnknownSymbol(String),
+    #[error("unexpected '{0}' in mana cost")]
+    UnexpectedText(String),
+    #[error("unclosed mana symbol")]
+    UnclosedSymbol,
+}
+
+impl ManaSymbol {
+    /// Parses the text between the braces of a symbol.
+    pub fn parse(symbol: &str) -> Result<Self, ManaCostError> {
+        let unknown = || ManaCostError::UnknownSymbol(symbol.to_string());
+        let color = |part: &str| {
+            let mut chars = part.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Color::from_symbol(c),
+                _ => None,
+            }
+        };
+
+        let upper = symbol.to_ascii_uppercase();
+        let parts: Vec<&str> = upper.split('/').collect();
+        let parsed = match parts.as_slice() {
+            [generic] if generic.chars().all(|c| c.is_ascii_digit()) => {
+                ManaSymbol::Generic(generic.parse().map_err(|_| unknown())?)
+            }
+            ["X"] => ManaSymbol::Variable('X'),
+            ["Y"] => ManaSymbol::Variable('Y'),
+            ["Z"] => ManaSymbol::Variable('Z'),
+            ["C"] => ManaSymbol::Colorless,
+            ["S"] => ManaSymbol::Snow,
+            ["½"] => ManaSymbol::Half(None),
+            [half] if half.starts_with('H') => {
+                ManaSymbol::Half(Some(color(&half[1..]).ok_or_else(unknown)?))
+            }
+            [colored] => ManaSymbol::Colored(color(colored).ok_or_else(unknown)?),
+            ["2", other] => ManaSymbol::TwoHybrid(color(other).ok_or_else(unknown)?),
+            ["C", other] => ManaSymbol::ColorlessHybrid(color(other).ok_or_else(unknown)?),
+            [colored, "P"] => ManaSymbol::Phyrexian(color(colored).ok_or_else(unknown)?),
+            [first, second] => ManaSymbol::Hybrid(
+                color(first).ok_or_else(unknown)?,
+                color(second).ok_or_else(unknown)?,
+            ),
+            [first, second, "P"] => ManaSymbol::HybridPhyrexian(
+                color(first).ok_or_else(unknown)?,
+                color(second).ok_or_else(unknown)?,
+            ),
+            _ => return Err(unknown()),
+        };
+        Ok(parsed)
+    }
+
+    /// Every mana symbol in rules text. Other symbols such as `{T}` or `{E}`
+    /// are skipped.
+    pub fn scan(text: &str) -> impl Iterator<Item = ManaSymbol> + '_ {
+        text.split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}'))
+            .filter_map(|(symbol, _)| ManaSymbol::parse(symbol).ok())
+    }
+
+    /// Colors that can pay for the symbol.
+    pub fn colors(&self) -> Vec<Color> {
+        match *self {
+            ManaSymbol::Colored(color)
+            | ManaSymbol::TwoHybrid(color)
+            | ManaSymbol::ColorlessHybrid(color)
+            | ManaSymbol::Phyrexian(color)
+            | ManaSymbol::Half(Some(color)) => vec![color],
+            ManaSymbol::Hybrid(first, second) | ManaSymbol::HybridPhyrexian(first, second) => {
+                vec![first, second]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// What the symbol adds to mana value in half mana, so `{½}` adds up.
+    /// Hybrid symbols count their largest part.
+    fn half_mana(&self) -> u32 {
+        match *self {
+            ManaSymbol::Generic(amount) => amount * 2,
+            ManaSymbol::TwoHybrid(_) => 4,
+            ManaSymbol::Variable(_) => 0,
+            ManaSymbol::Half(_) => 1,
+            _ => 2,
+        }
+    }
+}
+
+impl fmt::Display for ManaSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManaSymbol::Generic(amount) => write!(f, "{{{amount}}}"),
+            ManaSymbol::Colored(color) => write!(f, "{{{}}}", color.symbol()),
+            ManaSymbol::Colorless => write!(f, "{{C}}"),
+            ManaSymbol::Snow => write!(f, "{{S}}"),
+            ManaSymbol::Variable(variable) => write!(f, "{{{variable}}}"),
+            ManaSymbol::Hybrid(first, second) => {
+                write!(f, "{{{}/{}}}", first.symbol(), second.symbol())
+            }
+            ManaSymbol::TwoHybrid(color) => write!(f, "{{2/{}}}", color.symbol()),
+            ManaSymbol::ColorlessHybrid(color) => write!(f, "{{C/{}}}", color.symbol()),
+            ManaSymbol::Phyrexian(color) => write!(f, "{{{}/P}}", color.symbol()),
+            ManaSymbol::HybridPhyrexian(first, second) => {
+                write!(f, "{{{}/{}/P}}", first.symbol(), second.symbol())
+            }
+            ManaSymbol::Half(None) => write!(f, "{{½}}"),
+            ManaSymbol::Half(Some(color)) => write!(f, "{{H{}}}", color.symbol()),
+        }
+    }
+}
+
+/// A parsed mana cost, symbols in printed order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct ManaCost {
+    pub symbols: Vec<ManaSymbol>,
+}
+
+impl ManaCost {
+    /// Parses a cost such as `{2}{W/U}`. The faces of a split card's cost
+    /// may be separated by `//`, their symbols are all kept.
+    pub fn parse(cost: &str) -> Result<Self, ManaCostError> {
+        let mut symbols = Vec::new();
+        let mut rest = cost.trim();
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('{') {
+                let (symbol, after) = after.split_once('}').ok_or(ManaCostError::UnclosedSymbol)?;
+                symbols.push(ManaSymbol::parse(symbol)?);
+                rest = after.trim_start();
+            } else if let Some(after) = rest.strip_prefix("//") {
+                rest = after.trim_start();
+            } else {
+                let text = rest.split('{').next().unwrap_or(rest);
+                return Err(ManaCostError::UnexpectedText(text.trim().to_string()));
+            }
+        }
+        Ok(ManaCost { symbols })
+    }
+
+    /// Half symbols add up in pairs, a lone one rounds down.
+    pub fn mana_value(&self) -> u32 {
+        self.symbols.iter().map(|s| s.half_mana()).sum::<u32>() / 2
+    }
+
+    pub fn colors(&self) -> BTreeSet<Color> {
+        self.symbols.iter().flat_map(|s| s.colors()).collect()
+    }
+
+    /// Plain symbols of `color`, the ones only mana of that color pays for.
+    /// Hybrid and Phyrexian symbols can be paid some other way and don't
+    /// count.
+    pub fn pips(&self, color: Color) -> u32 {
+        self.symbols
+            .iter()
+            .filter(|s| **s == ManaSymbol::Colored(color))
+            .count() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+impl FromIterator<ManaSymbol> for ManaCost {
+    fn from_iter<T: IntoIterator<Item = ManaSymbol>>(iter: T) -> Self {
+        ManaCost {
+            symbols: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for ManaCost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.symbols.iter().try_for_each(|s| write!(f, "{s}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mana_cost() {
+        let cost = ManaCost::parse("{2}{W/U}{B/P}{X}").expect("cost should parse");
+        assert_eq!(
+            cost.symbols,
+            vec![
+                ManaSymbol::Generic(2),
+                ManaSymbol::Hybrid(Color::White, Color::Blue),
+                ManaSymbol::Phyrexian(Color::Black),
+                ManaSymbol::Variable('X'),
+            ]
+        );
+        assert_eq!(cost.mana_value(), 4);
+        assert_eq!(
+            cost.colors(),
+            BTreeSet::from([Color::White, Color::Blue, Color::Black])
+        );
+        assert_eq!(cost.to_string(), "{2}{W/U}{B/P}{X}");
+
+        assert_eq!(
+            ManaCost::parse("{G/U/P}{2/R}{C/W}{C}{S}").map(|c| c.symbols),
+            Ok(vec![
+                ManaSymbol::HybridPhyrexian(Color::Green, Color::Blue),
+                ManaSymbol::TwoHybrid(Color::Red),
+                ManaSymbol::ColorlessHybrid(Color::White),
+                ManaSymbol::Colorless,
+                ManaSymbol::Snow,
+            ])
+        );
+        assert_eq!(
+            ManaCost::parse("{2/R}{2/R}{2/R}").map(|c| c.mana_value()),
+            Ok(6)
+        );
+        assert_eq!(
+            ManaCost::parse("{HW}{½}{½}").map(|c| (c.mana_value(), c.colors())),
+            Ok((1, BTreeSet::from([Color::White])))
+        );
+        assert_eq!(
+            ManaCost::parse("{1}{R} // {2}{U}").map(|c| c.mana_value()),
+            Ok(5)
+        );
+        assert_eq!(ManaCost::parse(""), Ok(ManaCost::default()));
+    }
+
+    #[test]
+    fn test_mana_cost_pips() {
+        let cost = ManaCost::parse("{1}{G/W}{G/W}{G}{G}{B/P}").expect("cost should parse");
+        assert_eq!(cost.pips(Color::Green), 2);
+        assert_eq!(cost.pips(Color::White), 0);
+        assert_eq!(cost.pips(Color::Black), 0);
+    }
+
+    #[test]
+    fn test_parse_mana_cost_errors() {
+        assert_eq!(
+            ManaCost::parse("{2}{Q}"),
+            Err(ManaCostError::UnknownSymbol("Q".into()))
+        );
+        assert_eq!(
+            ManaCost::parse("{W/U/R}"),
+            Err(ManaCostError::UnknownSymbol("W/U/R".into()))
+        );
+        assert_eq!(ManaCost::parse("{2"), Err(ManaCostError::UnclosedSymbol));
+        assert_eq!(
+            ManaCost::parse("2W"),
+            Err(ManaCostError::UnexpectedText("2W".into()))
+        );
+    }
+
+    #[test]
+    fn test_scan_mana_symbols() {
+        let symbols: Vec<ManaSymbol> =
+            ManaSymbol::scan("{T}, Pay {E}: Add {W} or {U/P}. ({Q} untaps.)").collect();
+        assert_eq!(
+            symbols,
+            vec![
+                ManaSymbol::Colored(Color::White),
+                ManaSymbol::Phyrexian(Color::Blue),
+            ]
+        );
+    }
+}
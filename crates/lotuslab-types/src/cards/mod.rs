@@ -5,13 +5,23 @@
 mod artist;
 mod card_core;
 mod card_printing;
+mod catalog;
+mod color;
+mod mana;
+mod oracle;
+mod ruling;
+mod search;
 mod set;
 mod token_core;
 mod token_printing;
+mod type_line;
 
-pub use self::artist::*;
 pub use self::card_core::*;
 pub use self::card_printing::*;
-pub use self::set::*;
-pub use self::token_core::*;
-pub use self::token_printing::*;
+pub use self::catalog::*;
+pub use self::color::*;
+pub use self::mana::*;
+pub use self::oracle::*;
+pub use self::ruling::*;
+pub use self::search::*;
+pub use self::type_line::*;
@@ -0,0 +1,374 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Oracle text split into abilities, one per line.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{Color, ManaSymbol};
+
+/// Keyword abilities, longer names before the shorter ones they start with.
+pub const KEYWORD_ABILITIES: &[&str] = &[
+    "Affinity",
+    "Afflict",
+    "Afterlife",
+    "Aftermath",
+    "Annihilator",
+    "Backup",
+    "Banding",
+    "Bargain",
+    "Battle cry",
+    "Bestow",
+    "Blitz",
+    "Bushido",
+    "Buyback",
+    "Cascade",
+    "Casualty",
+    "Changeling",
+    "Choose a Background",
+    "Cleave",
+    "Companion",
+    "Compleated",
+    "Convoke",
+    "Craft",
+    "Crew",
+    "Cumulative upkeep",
+    "Cycling",
+    "Dash",
+    "Daybound",
+    "Deathtouch",
+    "Defender",
+    "Delve",
+    "Devour",
+    "Disguise",
+    "Disturb",
+    "Doctor's companion",
+    "Double strike",
+    "Dredge",
+    "Echo",
+    "Embalm",
+    "Emerge",
+    "Enchant",
+    "Encore",
+    "Enlist",
+    "Entwine",
+    "Equip",
+    "Escape",
+    "Eternalize",
+    "Evoke",
+    "Evolve",
+    "Exalted",
+    "Exploit",
+    "Extort",
+    "Fabricate",
+    "Fading",
+    "Fear",
+    "First strike",
+    "Flanking",
+    "Flash",
+    "Flashback",
+    "Flying",
+    "Forecast",
+    "Foretell",
+    "Fortify",
+    "Friends forever",
+    "Fuse",
+    "Haste",
+    "Hexproof",
+    "Hideaway",
+    "Horsemanship",
+    "Improvise",
+    "Indestructible",
+    "Infect",
+    "Intimidate",
+    "Jump-start",
+    "Kicker",
+    "Landwalk",
+    "Level up",
+    "Lifelink",
+    "Living weapon",
+    "Madness",
+    "Menace",
+    "Mentor",
+    "Miracle",
+    "Modular",
+    "Morph",
+    "Multikicker",
+    "Mutate",
+    "Myriad",
+    "Nightbound",
+    "Ninjutsu",
+    "Offspring",
+    "Outlast",
+    "Overload",
+    "Partner with",
+    "Partner",
+    "Persist",
+    "Phasing",
+    "Plot",
+    "Poisonous",
+    "Protection",
+    "Prowess",
+    "Rampage",
+    "Ravenous",
+    "Reach",
+    "Read ahead",
+    "Rebound",
+    "Reconfigure",
+    "Reinforce",
+    "Renown",
+    "Replicate",
+    "Retrace",
+    "Riot",
+    "Saddle",
+    "Scavenge",
+    "Shadow",
+    "Shroud",
+    "Skulk",
+    "Soulbond",
+    "Spectacle",
+    "Splice",
+    "Split second",
+    "Squad",
+    "Storm",
+    "Sunburst",
+    "Surge",
+    "Suspend",
+    "Toxic",
+    "Training",
+    "Trample",
+    "Transfigure",
+    "Transmute",
+    "Undying",
+    "Unearth",
+    "Vanishing",
+    "Vigilance",
+    "Ward",
+    "Wither",
+];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct OracleText {
+    pub abilities: Vec<Ability>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct Ability {
+    /// The line without its reminder text.
+    pub text: String,
+    /// What comes before the colon of an activated or loyalty ability.
+    pub cost: Option<String>,
+    /// Set when the whole line is a list of keyword abilities, e.g. `Flying,
+    /// vigilance`. Holds the names from [KEYWORD_ABILITIES], landwalk as
+    /// printed.
+    pub keywords: Vec<String>,
+    /// Mana the line adds, for mana abilities and rituals alike.
+    pub produces: Option<ManaProduction>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct ManaProduction {
+    pub colors: Vec<Color>,
+    pub colorless: bool,
+}
+
+impl OracleText {
+    pub fn parse(text: &str) -> Self {
+        let abilities = strip_reminder_text(text)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(Ability::parse)
+            .collect();
+        OracleText { abilities }
+    }
+
+    pub fn keywords(&self) -> impl Iterator<Item = &str> {
+        self.abilities
+            .iter()
+            .flat_map(|a| a.keywords.iter().map(String::as_str))
+    }
+
+    pub fn has_keyword(&self, keyword: &str) -> bool {
+        self.keywords().any(|k| k.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Colors of mana any ability adds.
+    pub fn produced_colors(&self) -> BTreeSet<Color> {
+        self.abilities
+            .iter()
+            .filter_map(|a| a.produces.as_ref())
+            .flat_map(|p| p.colors.iter().copied())
+            .collect()
+    }
+
+    pub fn produces_colorless(&self) -> bool {
+        self.abilities
+            .iter()
+            .any(|a| a.produces.as_ref().is_some_and(|p| p.colorless))
+    }
+}
+
+impl Ability {
+    fn parse(line: &str) -> Self {
+        let cost = line.split_once(':').and_then(|(cost, _)| {
+            let is_trigger = ["When", "Whenever", "At "]
+                .iter()
+                .any(|word| cost.starts_with(word));
+            (!is_trigger && !cost.contains(['.', '"'])).then(|| cost.trim().to_string())
+        });
+
+        Ability {
+            text: line.to_string(),
+            keywords: keywords(line),
+            produces: produces(line),
+            cost,
+        }
+    }
+}
+
+/// Keywords followed by words rather than a cost or number, such as
+/// `Protection from red`.
+const WORDED_KEYWORDS: &[&str] = &[
+    "Affinity",
+    "Craft",
+    "Enchant",
+    "Hexproof",
+    "Partner with",
+    "Protection",
+    "Splice",
+];
+
+/// The keywords of a line made of nothing but keywords.
+fn keywords(line: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    for part in line.trim_end_matches('.').split([',', ';']) {
+        let part = part.trim();
+        let lower = part.to_lowercase();
+        let matched = KEYWORD_ABILITIES.iter().find_map(|keyword| {
+            let rest = lower.strip_prefix(&keyword.to_lowercase())?;
+            let parameter = rest.trim_start();
+            let matches = rest.is_empty()
+                || parameter.starts_with(['—', '{'])
+                || parameter.starts_with(|c: char| c.is_ascii_digit() || c == 'x')
+                || (rest.starts_with(' ') && WORDED_KEYWORDS.contains(keyword));
+            matches.then_some((*keyword, parameter.starts_with('—')))
+        });
+        // Landcycling and landwalk are printed with the land type.
+        let first = lower.split_whitespace().next().unwrap_or_default();
+        let printed = ((first == lower && first.ends_with("walk")) || first.ends_with("cycling"))
+            .then(|| part.split_whitespace().next().unwrap_or_default());
+
+        match (matched, printed) {
+            // Everything after a dash is the keyword's cost, and a partner's
+            // name can have a comma in it.
+            (Some((keyword, true)), _) | (Some((keyword @ "Partner with", _)), _) => {
+                found.push(keyword.to_string());
+                break;
+            }
+            (Some((keyword, false)), _) => found.push(keyword.to_string()),
+            (None, Some(printed)) => found.push(printed.to_string()),
+            (None, None) => return Vec::new(),
+        }
+    }
+    found
+}
+
+fn produces(line: &str) -> Option<ManaProduction> {
+    let (_, added) = line.split_once("Add ")?;
+    let any_color = ["any color", "any one color", "the chosen color", "any type"]
+        .iter()
+        .any(|phrase| added.contains(phrase));
+
+    let symbols: Vec<ManaSymbol> = ManaSymbol::scan(added).collect();
+    let mut colors: BTreeSet<Color> = symbols.iter().flat_map(|s| s.colors()).collect();
+    if any_color {
+        colors.extend(Color::ALL);
+    }
+    let colorless = symbols.contains(&ManaSymbol::Colorless);
+
+    (!colors.is_empty() || colorless).then(|| ManaProduction {
+        colors: colors.into_iter().collect(),
+        colorless,
+    })
+}
+
+/// Removes everything in parentheses, nested ones included.
+pub fn strip_reminder_text(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            c if depth == 0 => stripped.push(c),
+            _ => {}
+        }
+    }
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_oracle_text() {
+        let oracle = OracleText::parse(
+            "Flying, first strike (This creature deals combat damage first.)\n\
+             Protection from red\n\
+             {2}{W}, {T}: Exile target creature. Its controller gains life equal to its power.\n\
+             Whenever this creature attacks, you may pay {1}: draw a card.",
+        );
+
+        assert_eq!(oracle.abilities.len(), 4);
+        assert_eq!(oracle.abilities[0].text, "Flying, first strike");
+        assert_eq!(
+            oracle.keywords().collect::<Vec<_>>(),
+            vec!["Flying", "First strike", "Protection"]
+        );
+        assert_eq!(oracle.abilities[2].cost.as_deref(), Some("{2}{W}, {T}"));
+        assert!(oracle.abilities[2].keywords.is_empty());
+        assert_eq!(oracle.abilities[3].cost, None);
+        assert!(oracle.produced_colors().is_empty());
+    }
+
+    #[test]
+    fn test_parse_oracle_keywords() {
+        let keywords = |text: &str| {
+            OracleText::parse(text)
+                .keywords()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(keywords("Equip {2}"), vec!["Equip"]);
+        assert_eq!(keywords("Ward—Pay 3 life."), vec!["Ward"]);
+        assert_eq!(
+            keywords("Partner with Pir, Imaginative Rascal"),
+            vec!["Partner with"]
+        );
+        assert_eq!(keywords("Swampwalk, trample"), vec!["Swampwalk", "Trample"]);
+        assert_eq!(keywords("Plainscycling {2}"), vec!["Plainscycling"]);
+        assert!(keywords("Flash back to the start of the turn.").is_empty());
+        assert!(keywords("Exile target creature.").is_empty());
+    }
+
+    #[test]
+    fn test_parse_oracle_mana_abilities() {
+        let oracle = OracleText::parse("({T}: Add {G}.)\n{T}: Add {C}.\n{T}: Add {W} or {U}.");
+        assert_eq!(
+            oracle.produced_colors(),
+            BTreeSet::from([Color::White, Color::Blue])
+        );
+        assert!(oracle.produces_colorless());
+
+        let city = OracleText::parse("{T}: Add one mana of any color.");
+        assert_eq!(city.produced_colors().len(), 5);
+        assert_eq!(city.abilities[0].cost.as_deref(), Some("{T}"));
+    }
+}
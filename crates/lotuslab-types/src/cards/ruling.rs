@@ -0,0 +1,57 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{CardCoreId, RepoError};
+
+/// A ruling or note on how a card works.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+pub struct CardRuling {
+    /// Publication date as `YYYY-MM-DD`.
+    pub date: String,
+    pub text: String,
+    pub source: RulingSource,
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Type,
+)]
+pub enum RulingSource {
+    /// Official rulings from Wizards of the Coast.
+    Wotc,
+    /// Notes added by Scryfall.
+    Scryfall,
+}
+
+impl RulingSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RulingSource::Wotc => "wotc",
+            RulingSource::Scryfall => "scryfall",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "wotc" => Some(RulingSource::Wotc),
+            "scryfall" => Some(RulingSource::Scryfall),
+            _ => None,
+        }
+    }
+}
+
+/// Every ruling of one card.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CardRulings {
+    pub card_core: CardCoreId,
+    pub rulings: Vec<CardRuling>,
+}
+
+#[async_trait::async_trait]
+pub trait CardRulingReadRepo: Send + Sync {
+    /// Rulings of a card, oldest first.
+    async fn get_card_rulings(&self, id: &CardCoreId) -> Result<Vec<CardRuling>, RepoError>;
+}
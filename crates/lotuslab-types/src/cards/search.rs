@@ -0,0 +1,447 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! The card search language.
+//!
+//! Queries follow Scryfall's syntax: whitespace separated terms that must all
+//! match, `or` between alternatives, `-` in front of a term to negate it and
+//! parentheses for grouping. A term is either a bare word matched against the
+//! card name or a `key:value` filter. Values with spaces go in double quotes.
+//! Numeric keys also take a comparison in place of the colon, `mv<=3`.
+//!
+//! ```text
+//! t:creature o:"draw a card" -t:legendary
+//! (t:instant or t:sorcery) rul:copy
+//! kw:flying produces:wu
+//! -t:land mv<=2 o:"add {"
+//! ```
+
+use thiserror::Error;
+
+use crate::{CardCoreDetail, CardCoreId, Color, ManaProduction, RepoError};
+
+/// A parsed card search.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CardQuery {
+    And(Vec<CardQuery>),
+    Or(Vec<CardQuery>),
+    Not(Box<CardQuery>),
+    Filter(CardFilter),
+}
+
+/// A single search term. Text is matched case-insensitively and anywhere in
+/// the field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CardFilter {
+    /// Bare words, `name:` or `n:`.
+    Name(String),
+    /// `type:` or `t:`. Every word must be one of the card's supertypes, card
+    /// types or subtypes, on any face.
+    Type(String),
+    /// `oracle:` or `o:`.
+    Oracle(String),
+    /// `keyword:` or `kw:`, a keyword ability the card has, e.g. `kw:flying`.
+    Keyword(String),
+    /// `produces:`, mana symbols such as `wu` or `c`. The card must be able
+    /// to produce all of them.
+    Produces(ManaProduction),
+    /// `manavalue:`, `mv:` or `cmc:`, e.g. `mv>=5`.
+    ManaValue(Comparison, u32),
+    /// `ruling:` or `rul:`, a full-text search over the card's rulings.
+    Ruling(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    /// Splits the comparison off the front of a value, `:` and `=` are both
+    /// equality.
+    fn split(value: &str) -> (Comparison, &str) {
+        let comparisons = [
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("!=", Comparison::NotEqual),
+            ("<", Comparison::Less),
+            (">", Comparison::Greater),
+            ("=", Comparison::Equal),
+        ];
+        comparisons
+            .into_iter()
+            .find_map(|(op, comparison)| Some((comparison, value.strip_prefix(op)?)))
+            .unwrap_or((Comparison::Equal, value))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Equal => "=",
+            Comparison::NotEqual => "!=",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Greater => ">",
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum QueryError {
+    #[error("search is empty")]
+    Empty,
+    #[error("unknown search key '{0}'")]
+    UnknownKey(String),
+    #[error("'{0}' needs a value")]
+    MissingValue(String),
+    #[error("unclosed quote")]
+    UnclosedQuote,
+    #[error("unmatched parenthesis")]
+    UnmatchedParen,
+    #[error("'or' needs a term on both sides")]
+    DanglingOr,
+    #[error("'{1}' isn't a valid value for '{0}'")]
+    InvalidValue(String, String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Or,
+    Not,
+    Term { key: Option<String>, value: String },
+}
+
+impl CardQuery {
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(QueryError::Empty);
+        }
+
+        let mut parser = Parser { tokens, pos: 0 };
+        let query = parser.or()?;
+        match parser.pos < parser.tokens.len() {
+            true => Err(QueryError::UnmatchedParen),
+            false => Ok(query),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '-' => tokens.push(Token::Not),
+            c => {
+                let mut key = None;
+                let mut word = String::new();
+                let mut quoted = false;
+                let mut next = Some(c);
+                while let Some(c) = next {
+                    match c {
+                        '"' => {
+                            quoted = true;
+                            loop {
+                                match chars.next() {
+                                    Some('"') => break,
+                                    Some(c) => word.push(c),
+                                    None => return Err(QueryError::UnclosedQuote),
+                                }
+                            }
+                        }
+                        ':' if key.is_none() && !quoted && !word.is_empty() => {
+                            key = Some(std::mem::take(&mut word).to_ascii_lowercase());
+                        }
+                        // The comparison stays in the value, `mv<=3` reads
+                        // like `mv:<=3`.
+                        '<' | '>' | '=' | '!'
+                            if key.is_none()
+                                && !quoted
+                                && !word.is_empty()
+                                && (c != '!' || chars.peek() == Some(&'=')) =>
+                        {
+                            key = Some(std::mem::take(&mut word).to_ascii_lowercase());
+                            word.push(c);
+                        }
+                        c => word.push(c),
+                    }
+                    next = chars.next_if(|c| !c.is_whitespace() && *c != '(' && *c != ')');
+                }
+
+                match key {
+                    None if !quoted && word.eq_ignore_ascii_case("or") => tokens.push(Token::Or),
+                    None if !quoted && word.eq_ignore_ascii_case("and") => {}
+                    key => tokens.push(Token::Term { key, value: word }),
+                }
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn or(&mut self) -> Result<CardQuery, QueryError> {
+        let mut alternatives = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            alternatives.push(self.and()?);
+        }
+
+        Ok(match alternatives.len() {
+            1 => alternatives.remove(0),
+            _ => CardQuery::Or(alternatives),
+        })
+    }
+
+    fn and(&mut self) -> Result<CardQuery, QueryError> {
+        let mut terms = Vec::new();
+        loop {
+            match self.peek() {
+                None | Some(Token::Close) => break,
+                Some(Token::Or) if terms.is_empty() => return Err(QueryError::DanglingOr),
+                Some(Token::Or) => break,
+                Some(_) => terms.push(self.term()?),
+            }
+        }
+
+        match terms.len() {
+            0 => Err(QueryError::DanglingOr),
+            1 => Ok(terms.remove(0)),
+            _ => Ok(CardQuery::And(terms)),
+        }
+    }
+
+    fn term(&mut self) -> Result<CardQuery, QueryError> {
+        let Some(token) = self.tokens.get(self.pos).cloned() else {
+            return Err(QueryError::MissingValue("-".into()));
+        };
+        self.pos += 1;
+
+        match token {
+            Token::Open => {
+                let query = self.or()?;
+                match self.peek() {
+                    Some(Token::Close) => {
+                        self.pos += 1;
+                        Ok(query)
+                    }
+                    _ => Err(QueryError::UnmatchedParen),
+                }
+            }
+            Token::Close => Err(QueryError::UnmatchedParen),
+            Token::Or => Err(QueryError::DanglingOr),
+            Token::Not => Ok(CardQuery::Not(Box::new(self.term()?))),
+            Token::Term { key, value } => Ok(CardQuery::Filter(filter(key, value)?)),
+        }
+    }
+}
+
+fn filter(key: Option<String>, value: String) -> Result<CardFilter, QueryError> {
+    let Some(key) = key else {
+        return Ok(CardFilter::Name(value));
+    };
+    if value.is_empty() {
+        return Err(QueryError::MissingValue(key));
+    }
+
+    match key.as_str() {
+        "name" | "n" => Ok(CardFilter::Name(value)),
+        "type" | "t" => Ok(CardFilter::Type(value)),
+        "oracle" | "o" => Ok(CardFilter::Oracle(value)),
+        "keyword" | "kw" => Ok(CardFilter::Keyword(value)),
+        "produces" => produces(&value)
+            .map(CardFilter::Produces)
+            .ok_or(QueryError::InvalidValue(key, value)),
+        "manavalue" | "mv" | "cmc" => {
+            let (comparison, number) = Comparison::split(&value);
+            match number.parse() {
+                Ok(number) => Ok(CardFilter::ManaValue(comparison, number)),
+                Err(_) => Err(QueryError::InvalidValue(key, value)),
+            }
+        }
+        "ruling" | "rul" => Ok(CardFilter::Ruling(value)),
+        _ => Err(QueryError::UnknownKey(key)),
+    }
+}
+
+/// Mana symbols written together without braces, `wu` or `{W}{U}` alike.
+fn produces(value: &str) -> Option<ManaProduction> {
+    let mut produces = ManaProduction::default();
+    for symbol in value.chars().filter(|c| !matches!(c, '{' | '}')) {
+        match symbol.to_ascii_uppercase() {
+            'C' => produces.colorless = true,
+            c => {
+                let color = Color::from_symbol(c)?;
+                if !produces.colors.contains(&color) {
+                    produces.colors.push(color);
+                }
+            }
+        }
+    }
+    (!produces.colors.is_empty() || produces.colorless).then_some(produces)
+}
+
+#[async_trait::async_trait]
+pub trait CardSearchRepo: Send + Sync {
+    /// Cards matching `query`, sorted by name.
+    async fn search_cards(
+        &self,
+        query: &CardQuery,
+        limit: u32,
+    ) -> Result<Vec<CardCoreDetail>, RepoError>;
+
+    /// The cards out of `ids` that match `query`.
+    async fn filter_cards(
+        &self,
+        query: &CardQuery,
+        ids: &[CardCoreId],
+    ) -> Result<Vec<CardCoreId>, RepoError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(value: &str) -> CardQuery {
+        CardQuery::Filter(CardFilter::Name(value.into()))
+    }
+
+    #[test]
+    fn test_parse_card_query() {
+        assert_eq!(
+            CardQuery::parse(r#"t:creature o:"draw a card" -T:Legendary"#),
+            Ok(CardQuery::And(vec![
+                CardQuery::Filter(CardFilter::Type("creature".into())),
+                CardQuery::Filter(CardFilter::Oracle("draw a card".into())),
+                CardQuery::Not(Box::new(CardQuery::Filter(CardFilter::Type(
+                    "Legendary".into()
+                )))),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_parse_card_query_grouping() {
+        assert_eq!(
+            CardQuery::parse("(lotus or mox) rul:copy"),
+            Ok(CardQuery::And(vec![
+                CardQuery::Or(vec![name("lotus"), name("mox")]),
+                CardQuery::Filter(CardFilter::Ruling("copy".into())),
+            ]))
+        );
+        assert_eq!(
+            CardQuery::parse(r#""or" and sol"#),
+            Ok(CardQuery::And(vec![name("or"), name("sol")]))
+        );
+        assert_eq!(
+            CardQuery::parse(r#"-(t:land or "ob:nix")"#),
+            Ok(CardQuery::Not(Box::new(CardQuery::Or(vec![
+                CardQuery::Filter(CardFilter::Type("land".into())),
+                name("ob:nix"),
+            ]))))
+        );
+    }
+
+    #[test]
+    fn test_parse_card_query_keywords_and_produces() {
+        assert_eq!(
+            CardQuery::parse(r#"kw:"first strike" produces:{G}u"#),
+            Ok(CardQuery::And(vec![
+                CardQuery::Filter(CardFilter::Keyword("first strike".into())),
+                CardQuery::Filter(CardFilter::Produces(ManaProduction {
+                    colors: vec![Color::Green, Color::Blue],
+                    colorless: false,
+                })),
+            ]))
+        );
+        assert_eq!(
+            CardQuery::parse("produces:c"),
+            Ok(CardQuery::Filter(CardFilter::Produces(ManaProduction {
+                colors: Vec::new(),
+                colorless: true,
+            })))
+        );
+    }
+
+    #[test]
+    fn test_parse_card_query_mana_value() {
+        assert_eq!(
+            CardQuery::parse("mv<=3 cmc:2 -mv>5"),
+            Ok(CardQuery::And(vec![
+                CardQuery::Filter(CardFilter::ManaValue(Comparison::LessOrEqual, 3)),
+                CardQuery::Filter(CardFilter::ManaValue(Comparison::Equal, 2)),
+                CardQuery::Not(Box::new(CardQuery::Filter(CardFilter::ManaValue(
+                    Comparison::Greater,
+                    5
+                )))),
+            ]))
+        );
+        assert_eq!(
+            CardQuery::parse("mv>x"),
+            Err(QueryError::InvalidValue("mv".into(), ">x".into()))
+        );
+        assert_eq!(CardQuery::parse("ach!"), Ok(name("ach!")));
+        assert_eq!(
+            CardQuery::parse(r#"o:"x=1""#),
+            Ok(CardQuery::Filter(CardFilter::Oracle("x=1".into())))
+        );
+    }
+
+    #[test]
+    fn test_parse_card_query_errors() {
+        assert_eq!(CardQuery::parse("  "), Err(QueryError::Empty));
+        assert_eq!(
+            CardQuery::parse("foo:bar"),
+            Err(QueryError::UnknownKey("foo".into()))
+        );
+        assert_eq!(
+            CardQuery::parse("t:"),
+            Err(QueryError::MissingValue("t".into()))
+        );
+        assert_eq!(
+            CardQuery::parse(r#"o:"draw"#),
+            Err(QueryError::UnclosedQuote)
+        );
+        assert_eq!(
+            CardQuery::parse("(t:creature"),
+            Err(QueryError::UnmatchedParen)
+        );
+        assert_eq!(
+            CardQuery::parse("t:creature)"),
+            Err(QueryError::UnmatchedParen)
+        );
+        assert_eq!(CardQuery::parse("or lotus"), Err(QueryError::DanglingOr));
+        assert_eq!(CardQuery::parse("lotus or"), Err(QueryError::DanglingOr));
+        assert_eq!(
+            CardQuery::parse("produces:q"),
+            Err(QueryError::InvalidValue("produces".into(), "q".into()))
+        );
+        assert_eq!(
+            CardQuery::parse("lotus -"),
+            Err(QueryError::MissingValue("-".into()))
+        );
+    }
+}
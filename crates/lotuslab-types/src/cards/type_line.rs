@@ -0,0 +1,168 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::Color;
+
+/// One face of a type line such as `Legendary Creature — Elf Druid`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct TypeLine {
+    pub supertypes: Vec<String>,
+    /// Words before the dash that aren't supertypes, so unusual types like
+    /// `Conspiracy` are kept too.
+    pub card_types: Vec<String>,
+    pub subtypes: Vec<String>,
+}
+
+impl TypeLine {
+    pub const SUPERTYPES: &[&str] = &["Basic", "Legendary", "Ongoing", "Snow", "World"];
+    pub const CARD_TYPES: &[&str] = &[
+        "Artifact",
+        "Battle",
+        "Creature",
+        "Enchantment",
+        "Instant",
+        "Kindred",
+        "Land",
+        "Planeswalker",
+        "Sorcery",
+        "Tribal",
+    ];
+    pub const PERMANENT_TYPES: &[&str] = &[
+        "Artifact",
+        "Battle",
+        "Creature",
+        "Enchantment",
+        "Land",
+        "Planeswalker",
+    ];
+    /// Subtypes written with a space, kept as one subtype.
+    const MULTI_WORD_SUBTYPES: &[&str] = &["Time Lord"];
+
+    /// Parses a single face, see [TypeLine::faces] for the whole line.
+    pub fn parse(face: &str) -> Self {
+        let (types, subtypes) = face.split_once('—').unwrap_or((face, ""));
+        let (supertypes, card_types) = types
+            .split_whitespace()
+            .map(str::to_string)
+            .partition(|word| TypeLine::SUPERTYPES.contains(&word.as_str()));
+
+        let mut rest = subtypes.trim();
+        let mut parsed_subtypes = Vec::new();
+        while !rest.is_empty() {
+            let subtype = TypeLine::MULTI_WORD_SUBTYPES
+                .iter()
+                .copied()
+                .find(|s| {
+                    rest.strip_prefix(s)
+                        .is_some_and(|after| after.is_empty() || after.starts_with(' '))
+                })
+                .unwrap_or_else(|| rest.split_whitespace().next().unwrap_or_default());
+            parsed_subtypes.push(subtype.to_string());
+            rest = rest[subtype.len()..].trim_start();
+        }
+
+        TypeLine {
+            supertypes,
+            card_types,
+            subtypes: parsed_subtypes,
+        }
+    }
+
+    /// Every face of a type line, faces are separated by `//`.
+    pub fn faces(type_line: &str) -> Vec<Self> {
+        type_line.split("//").map(TypeLine::parse).collect()
+    }
+
+    /// The front face, the one that counts outside of the stack and the
+    /// battlefield.
+    pub fn front(type_line: &str) -> Self {
+        TypeLine::parse(type_line.split("//").next().unwrap_or_default())
+    }
+
+    pub fn has_supertype(&self, supertype: &str) -> bool {
+        contains(&self.supertypes, supertype)
+    }
+
+    pub fn has_card_type(&self, card_type: &str) -> bool {
+        contains(&self.card_types, card_type)
+    }
+
+    pub fn has_subtype(&self, subtype: &str) -> bool {
+        contains(&self.subtypes, subtype)
+    }
+
+    pub fn is_permanent(&self) -> bool {
+        TypeLine::PERMANENT_TYPES
+            .iter()
+            .any(|card_type| self.has_card_type(card_type))
+    }
+
+    /// Colors of the face's basic land types, which grant their mana
+    /// abilities.
+    pub fn basic_land_colors(&self) -> impl Iterator<Item = Color> + '_ {
+        Color::ALL
+            .into_iter()
+            .filter(|c| self.has_subtype(c.basic_land_type()))
+    }
+
+    /// Every type in lowercase, for matching search terms.
+    pub fn words(&self) -> impl Iterator<Item = String> + '_ {
+        self.supertypes
+            .iter()
+            .chain(&self.card_types)
+            .chain(&self.subtypes)
+            .map(|word| word.to_lowercase())
+    }
+}
+
+fn contains(words: &[String], word: &str) -> bool {
+    words.iter().any(|w| w.eq_ignore_ascii_case(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_type_line() {
+        let line = TypeLine::parse("Legendary Snow Creature — Elf Druid");
+        assert_eq!(line.supertypes, words(&["Legendary", "Snow"]));
+        assert_eq!(line.card_types, words(&["Creature"]));
+        assert_eq!(line.subtypes, words(&["Elf", "Druid"]));
+        assert!(line.has_card_type("creature") && line.is_permanent());
+
+        let doctor = TypeLine::parse("Legendary Creature — Time Lord Doctor");
+        assert_eq!(doctor.subtypes, words(&["Time Lord", "Doctor"]));
+
+        let instant = TypeLine::parse("Kindred Instant — Goblin");
+        assert!(!instant.is_permanent());
+        let dual = TypeLine::parse("Land — Forest Island");
+        assert_eq!(
+            dual.basic_land_colors().collect::<Vec<_>>(),
+            vec![Color::Blue, Color::Green]
+        );
+        assert_eq!(
+            TypeLine::parse("Conspiracy").card_types,
+            words(&["Conspiracy"])
+        );
+    }
+
+    #[test]
+    fn test_type_line_faces() {
+        let faces = TypeLine::faces("Creature — Human Werewolf // Creature — Werewolf");
+        assert_eq!(faces.len(), 2);
+        assert_eq!(faces[1].subtypes, words(&["Werewolf"]));
+        assert_eq!(
+            TypeLine::front("Sorcery // Land").card_types,
+            words(&["Sorcery"])
+        );
+    }
+}
@@ -16,6 +16,14 @@ pub struct ListId(pub String);
 pub struct ListItemId(pub String);
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
 pub struct TagId(pub String);
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub struct CatalogUpdateId(pub String);
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub struct CustomFormatId(pub String);
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub struct TagRuleId(pub String);
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub struct CollectionEntryId(pub String);
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
 pub struct CardCoreId(pub String);
@@ -259,3 +267,107 @@ impl From<ArtistId> for String {
         value.0
     }
 }
+
+impl From<String> for CatalogUpdateId {
+    fn from(value: String) -> Self {
+        CatalogUpdateId(value)
+    }
+}
+impl From<&str> for CatalogUpdateId {
+    fn from(value: &str) -> Self {
+        CatalogUpdateId(value.to_string())
+    }
+}
+impl fmt::Display for CatalogUpdateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl AsRef<str> for CatalogUpdateId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+impl From<CatalogUpdateId> for String {
+    fn from(value: CatalogUpdateId) -> Self {
+        value.0
+    }
+}
+
+impl From<String> for CustomFormatId {
+    fn from(value: String) -> Self {
+        CustomFormatId(value)
+    }
+}
+impl From<&str> for CustomFormatId {
+    fn from(value: &str) -> Self {
+        CustomFormatId(value.to_string())
+    }
+}
+impl fmt::Display for CustomFormatId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl AsRef<str> for CustomFormatId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+impl From<CustomFormatId> for String {
+    fn from(value: CustomFormatId) -> Self {
+        value.0
+    }
+}
+
+impl From<String> for TagRuleId {
+    fn from(value: String) -> Self {
+        TagRuleId(value)
+    }
+}
+impl From<&str> for TagRuleId {
+    fn from(value: &str) -> Self {
+        TagRuleId(value.to_string())
+    }
+}
+impl fmt::Display for TagRuleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl AsRef<str> for TagRuleId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+impl From<TagRuleId> for String {
+    fn from(value: TagRuleId) -> Self {
+        value.0
+    }
+}
+
+impl From<String> for CollectionEntryId {
+    fn from(value: String) -> Self {
+        CollectionEntryId(value)
+    }
+}
+impl From<&str> for CollectionEntryId {
+    fn from(value: &str) -> Self {
+        CollectionEntryId(value.to_string())
+    }
+}
+impl fmt::Display for CollectionEntryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl AsRef<str> for CollectionEntryId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+impl From<CollectionEntryId> for String {
+    fn from(value: CollectionEntryId) -> Self {
+        value.0
+    }
+}
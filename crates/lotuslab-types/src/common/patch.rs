@@ -17,19 +17,14 @@ use specta::Type;
 /// and `Option<Option<T>>` to provide an idiomatic JSON representation for
 /// the javascript frontend, where absent fields mean ignore, null means clear,
 /// and any value means set.
-#[derive(Clone, Debug, Type)]
+#[derive(Clone, Debug, Default, Type)]
 pub enum Patch<T> {
+    #[default]
     Ignore,
     Clear,
     Set(T),
 }
 
-impl<T> Default for Patch<T> {
-    fn default() -> Self {
-        return Patch::Ignore;
-    }
-}
-
 // Serialize Patch<T> as Option<Option<T>>:
 //   Ignore => None
 //   Clear  => Some(None)
@@ -42,11 +37,11 @@ where
     where
         S: Serializer,
     {
-        return match self {
+        match self {
             Patch::Ignore => Option::<Option<&T>>::None.serialize(serializer),
             Patch::Clear => Some::<Option<&T>>(None).serialize(serializer),
             Patch::Set(v) => Some::<Option<&T>>(Some(v)).serialize(serializer),
-        };
+        }
     }
 }
 
@@ -59,10 +54,10 @@ where
         D: Deserializer<'de>,
     {
         let opt: Option<Option<T>> = Option::deserialize(deserializer)?;
-        return Ok(match opt {
+        Ok(match opt {
             None => Patch::Ignore,
             Some(None) => Patch::Clear,
             Some(Some(v)) => Patch::Set(v),
-        });
+        })
     }
 }
@@ -0,0 +1,272 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{CardCoreId, Color, CustomFormatId, ListId, ListItemId, Rarity};
+
+/// Formats LotusLab knows the deck construction rules of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub enum Format {
+    Standard,
+    Pioneer,
+    Modern,
+    Legacy,
+    Vintage,
+    Pauper,
+    Commander,
+}
+
+impl Format {
+    pub const ALL: [Format; 7] = [
+        Format::Standard,
+        Format::Pioneer,
+        Format::Modern,
+        Format::Legacy,
+        Format::Vintage,
+        Format::Pauper,
+        Format::Commander,
+    ];
+
+    pub fn rules(&self) -> FormatRules {
+        let constructed = |name: &str, legality_key: &str| FormatRules {
+            name: name.to_string(),
+            legality_key: Some(legality_key.to_string()),
+            min_deck_size: Some(60),
+            max_deck_size: None,
+            max_copies: 4,
+            max_sideboard_size: Some(15),
+            commander: false,
+            ..FormatRules::default()
+        };
+
+        match self {
+            Format::Standard => constructed("Standard", "standard"),
+            Format::Pioneer => constructed("Pioneer", "pioneer"),
+            Format::Modern => constructed("Modern", "modern"),
+            Format::Legacy => constructed("Legacy", "legacy"),
+            Format::Vintage => constructed("Vintage", "vintage"),
+            Format::Pauper => constructed("Pauper", "pauper"),
+            Format::Commander => FormatRules {
+                name: "Commander".to_string(),
+                legality_key: Some("commander".to_string()),
+                min_deck_size: Some(100),
+                max_deck_size: Some(100),
+                max_copies: 1,
+                // Room for a companion, nothing else.
+                max_sideboard_size: Some(1),
+                commander: true,
+                ..FormatRules::default()
+            },
+        }
+    }
+}
+
+/// Picks the format to validate a project against.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub enum FormatRef {
+    Official(Format),
+    Custom(CustomFormatId),
+}
+
+/// Deck construction rules to validate a project against. Official formats
+/// and the user's own formats are both described by these.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct FormatRules {
+    pub name: String,
+    /// Key into [crate::CardCoreDetail::legalities]. `None` skips the banned
+    /// and restricted checks.
+    pub legality_key: Option<String>,
+    pub min_deck_size: Option<u32>,
+    pub max_deck_size: Option<u32>,
+    /// Copies of a card allowed across main deck and sideboard, 1 for
+    /// singleton formats. Basic lands and cards that say otherwise are exempt.
+    pub max_copies: u32,
+    /// `None` for formats without a sideboard, the sideboard isn't looked at
+    /// at all then.
+    pub max_sideboard_size: Option<u32>,
+    /// Decks are led by the project's commanders and bound by their color
+    /// identity. The sideboard may only hold a companion.
+    #[serde(default)]
+    pub commander: bool,
+    /// Card names banned on top of what the legality key says.
+    #[serde(default)]
+    pub banned: Vec<String>,
+    /// Card names limited to a single copy on top of what the legality key
+    /// says.
+    #[serde(default)]
+    pub restricted: Vec<String>,
+    /// Set codes a card must have been printed in. `None` allows every set.
+    #[serde(default)]
+    pub allowed_sets: Option<Vec<String>>,
+    /// Rarities a card must have been printed at, in one of the allowed sets.
+    /// `None` allows every rarity.
+    #[serde(default)]
+    pub allowed_rarities: Option<Vec<Rarity>>,
+    #[serde(default)]
+    pub points: Option<PointsTable>,
+}
+
+impl FormatRules {
+    /// Whether cards have to be checked against their printings.
+    pub fn restricts_printings(&self) -> bool {
+        self.allowed_sets.is_some() || self.allowed_rarities.is_some()
+    }
+}
+
+/// Point costs of cards in a points format like Canadian Highlander.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct PointsTable {
+    /// The most points a deck may spend.
+    pub cap: u32,
+    /// Keyed by card name. Cards left out cost nothing.
+    pub points: BTreeMap<String, u32>,
+}
+
+impl PointsTable {
+    /// What one copy of the card named `name` costs. Names are matched
+    /// ignoring case, so hand written tables still line up with the catalog.
+    pub fn cost(&self, name: &str) -> u32 {
+        self.points.get(name).copied().unwrap_or_else(|| {
+            self.points
+                .iter()
+                .find(|(card, _)| card.eq_ignore_ascii_case(name))
+                .map_or(0, |(_, points)| *points)
+        })
+    }
+}
+
+/// Points a project spends under a points format.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct ProjectPoints {
+    pub format: String,
+    pub cap: u32,
+    /// Points spent by the main deck and sideboard, the maybeboard aside.
+    pub total: u32,
+    pub lists: Vec<ListPoints>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct ListPoints {
+    pub list_id: ListId,
+    pub name: String,
+    pub points: u32,
+    /// The list's cards that cost points, most expensive first.
+    pub cards: Vec<CardPoints>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CardPoints {
+    pub card_core_id: CardCoreId,
+    pub name: String,
+    pub quantity: u32,
+    /// Points of a single copy.
+    pub points: u32,
+}
+
+/// A rule a deck breaks. Card specific violations list every item of that
+/// card so the UI can highlight them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub enum DeckViolation {
+    DeckTooSmall {
+        min: u32,
+        actual: u32,
+    },
+    DeckTooLarge {
+        max: u32,
+        actual: u32,
+    },
+    SideboardTooLarge {
+        max: u32,
+        actual: u32,
+    },
+    TooManyCopies {
+        card_core_id: CardCoreId,
+        name: String,
+        max: u32,
+        actual: u32,
+        items: Vec<ListItemId>,
+    },
+    NotLegal {
+        card_core_id: CardCoreId,
+        name: String,
+        items: Vec<ListItemId>,
+    },
+    Banned {
+        card_core_id: CardCoreId,
+        name: String,
+        items: Vec<ListItemId>,
+    },
+    /// Restricted cards are limited to a single copy.
+    Restricted {
+        card_core_id: CardCoreId,
+        name: String,
+        actual: u32,
+        items: Vec<ListItemId>,
+    },
+    /// The card was never printed in an allowed set at an allowed rarity.
+    NoAllowedPrinting {
+        card_core_id: CardCoreId,
+        name: String,
+        items: Vec<ListItemId>,
+    },
+    /// The deck spends more points than the format's cap. Lists the items of
+    /// every card that costs points.
+    OverPointsCap {
+        cap: u32,
+        actual: u32,
+        items: Vec<ListItemId>,
+    },
+    MissingCommander,
+    /// A commander that can't lead a deck, or can't alongside the others.
+    NotACommander {
+        card_core_id: CardCoreId,
+        name: String,
+        items: Vec<ListItemId>,
+    },
+    /// More than one commander without a partner ability that allows it.
+    InvalidCommanderPair {
+        names: Vec<String>,
+        items: Vec<ListItemId>,
+    },
+    OutsideColorIdentity {
+        card_core_id: CardCoreId,
+        name: String,
+        /// The card's colors the commanders don't have.
+        colors: Vec<Color>,
+        items: Vec<ListItemId>,
+    },
+    /// A card in a commander sideboard that isn't a companion.
+    NotACompanion {
+        card_core_id: CardCoreId,
+        name: String,
+        items: Vec<ListItemId>,
+    },
+    CompanionConditionNotMet {
+        card_core_id: CardCoreId,
+        name: String,
+        condition: String,
+        /// The items breaking the condition.
+        items: Vec<ListItemId>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct DeckValidation {
+    pub format: String,
+    pub deck_size: u32,
+    pub sideboard_size: u32,
+    /// Points spent, `None` if the format has no points table.
+    pub points: Option<u32>,
+    pub violations: Vec<DeckViolation>,
+}
+
+impl DeckValidation {
+    pub fn is_legal(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
@@ -0,0 +1,98 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::Finish;
+
+/// Header names to read each field from. Matching is case-insensitive.
+///
+/// Only `quantity` and `name` are required, a missing optional column leaves
+/// the field unset on every row.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CsvColumns {
+    pub quantity: String,
+    pub name: String,
+    pub set_code: Option<String>,
+    pub collector_number: Option<String>,
+    pub finish: Option<String>,
+    pub condition: Option<String>,
+    pub language: Option<String>,
+}
+
+/// Tools with a built-in [CsvColumns] preset matching their default export.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub enum CsvPreset {
+    Moxfield,
+    Archidekt,
+    ManaBox,
+    DelverLens,
+    TcgPlayer,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub enum CardCondition {
+    #[default]
+    NearMint,
+    LightlyPlayed,
+    ModeratelyPlayed,
+    HeavilyPlayed,
+    Damaged,
+}
+
+impl CardCondition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CardCondition::NearMint => "near_mint",
+            CardCondition::LightlyPlayed => "lightly_played",
+            CardCondition::ModeratelyPlayed => "moderately_played",
+            CardCondition::HeavilyPlayed => "heavily_played",
+            CardCondition::Damaged => "damaged",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "near_mint" => Some(CardCondition::NearMint),
+            "lightly_played" => Some(CardCondition::LightlyPlayed),
+            "moderately_played" => Some(CardCondition::ModeratelyPlayed),
+            "heavily_played" => Some(CardCondition::HeavilyPlayed),
+            "damaged" => Some(CardCondition::Damaged),
+            _ => None,
+        }
+    }
+}
+
+/// One parsed row of a card CSV, before it is matched against the catalog.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CsvCardRow {
+    pub line: u32,
+    pub quantity: i32,
+    pub name: String,
+    pub set_code: Option<String>,
+    pub collector_number: Option<String>,
+    pub finish: Finish,
+    pub condition: Option<CardCondition>,
+    /// Language code, e.g. `"ja"`. Values that aren't a known language name
+    /// are kept as written.
+    pub language: Option<String>,
+}
+
+/// A row that could not be imported, kept so the user can review and fix it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CsvRowError {
+    pub line: u32,
+    pub raw: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Type)]
+pub struct CsvImportReport {
+    /// Rows imported. Rows for the same card and printing share a list item,
+    /// and in the collection also need the same finish, condition and
+    /// language to share an entry.
+    pub imported: u32,
+    pub errors: Vec<CsvRowError>,
+}
@@ -0,0 +1,49 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// A deck as read from or written to an external deck file.
+///
+/// Entries are identified by card name and, where the source format allows it,
+/// the printing (set code and collector number). Resolving entries to cards in
+/// the catalog happens after parsing.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct Decklist {
+    pub name: Option<String>,
+    pub entries: Vec<DecklistEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct DecklistEntry {
+    pub section: DecklistSection,
+    pub quantity: i32,
+    pub name: String,
+    pub set_code: Option<String>,
+    pub collector_number: Option<String>,
+    /// Forge's pick among the arts of a printing, counted from 1.
+    #[serde(default)]
+    pub art_index: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub enum DecklistSection {
+    Commander,
+    Main,
+    Sideboard,
+}
+
+/// Game engines whose `.dck` deck files can be imported and exported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub enum DckFormat {
+    Forge,
+    XMage,
+}
+
+impl Decklist {
+    pub fn section(&self, section: DecklistSection) -> impl Iterator<Item = &DecklistEntry> {
+        self.entries.iter().filter(move |e| e.section == section)
+    }
+}
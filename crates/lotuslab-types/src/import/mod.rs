@@ -0,0 +1,11 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+pub mod csv;
+pub mod decklist;
+pub mod precon;
+
+pub use self::csv::*;
+pub use self::decklist::*;
+pub use self::precon::*;
@@ -0,0 +1,22 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// A preconstructed deck available to import, without its card list.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct PreconSummary {
+    pub set_code: String,
+    pub file_name: String,
+    pub name: String,
+    pub release_date: Option<String>,
+    pub deck_type: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct PreconSet {
+    pub set_code: String,
+    pub decks: Vec<PreconSummary>,
+}
@@ -2,10 +2,18 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+mod backup;
 mod cards;
 mod common;
+mod format;
+mod import;
+mod stats;
 mod user;
 
+pub use crate::backup::*;
 pub use crate::cards::*;
 pub use crate::common::*;
+pub use crate::format::*;
+pub use crate::import::*;
+pub use crate::stats::*;
 pub use crate::user::*;
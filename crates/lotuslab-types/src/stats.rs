@@ -0,0 +1,233 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{CardCoreId, Color, ListItemId, Rarity, TagId};
+
+/// Numbers to chart a list by. Every count is in copies, so four copies of
+/// a card count four times.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct DeckStats {
+    pub card_count: u32,
+    pub land_count: u32,
+    /// Nonland cards by mana value, from 0 up to [DeckStats::CURVE_MAX]. The
+    /// last bucket holds everything at or above it.
+    pub curve: Vec<CurveBucket>,
+    /// One entry per color, in WUBRG order.
+    pub colors: Vec<ColorStats>,
+    /// Cards with more than one card type count once for each.
+    pub types: Vec<TypeCount>,
+    pub average_mana_value: f64,
+    pub average_mana_value_without_lands: f64,
+    /// Rarities of the selected printings, `None` for items without one.
+    pub rarities: Vec<RarityCount>,
+}
+
+impl DeckStats {
+    pub const CURVE_MAX: u32 = 7;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CurveBucket {
+    pub mana_value: u32,
+    pub creatures: u32,
+    pub noncreatures: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct ColorStats {
+    pub color: Color,
+    /// Plain mana symbols of this color in the mana costs of nonland cards,
+    /// see [crate::ManaCost::pips].
+    pub pips: u32,
+    /// Lands that can produce this color.
+    pub land_sources: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct TypeCount {
+    pub card_type: String,
+    pub count: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct RarityCount {
+    pub rarity: Option<Rarity>,
+    pub count: u32,
+}
+
+/// Which cards of a list a [DrawRequirement] counts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub enum CardCategory {
+    /// Items carrying the tag.
+    Tag(TagId),
+    /// Cards matching a search query, see [crate::CardQuery].
+    Query(String),
+    Cards(Vec<CardCoreId>),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct DrawRequirement {
+    pub name: String,
+    pub category: CardCategory,
+    pub at_least: u32,
+}
+
+/// "At least 2 lands and 1 ramp card by turn N". A card in the categories of
+/// several requirements counts toward one of them at a time, so a land
+/// tagged as ramp covers either but not both.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct DrawQuestion {
+    pub requirements: Vec<DrawRequirement>,
+    pub on_the_draw: bool,
+    /// London mulligans taken on opening hands that miss the requirements.
+    /// The hand after the last one is kept whatever it holds.
+    pub max_mulligans: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct DrawOdds {
+    pub deck_size: u32,
+    /// Copies in the list in the category of each requirement, in question
+    /// order. Cards in several categories are in each of these.
+    pub matching: Vec<u32>,
+    /// Turns 1 through [DrawOdds::TURNS].
+    pub turns: Vec<TurnOdds>,
+}
+
+impl DrawOdds {
+    pub const TURNS: u32 = 10;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct TurnOdds {
+    pub turn: u32,
+    /// Cards drawn by this turn, counting the opening hand.
+    pub cards_seen: u32,
+    /// Chance of meeting every requirement keeping the first seven.
+    pub probability: f64,
+    pub probability_with_mulligans: f64,
+}
+
+/// How goldfish games are played. Opening hands with fewer than `min_lands`
+/// or more than `max_lands` lands are mulliganed, London style, until
+/// `max_mulligans` is reached.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct GoldfishSettings {
+    /// At most [GoldfishSettings::MAX_RUNS].
+    pub runs: u32,
+    pub turns: u32,
+    /// Same seed, same games.
+    pub seed: u32,
+    pub on_the_draw: bool,
+    pub max_mulligans: u32,
+    pub min_lands: u32,
+    pub max_lands: u32,
+}
+
+impl GoldfishSettings {
+    pub const MAX_RUNS: u32 = 100_000;
+}
+
+impl Default for GoldfishSettings {
+    fn default() -> Self {
+        GoldfishSettings {
+            runs: 10_000,
+            turns: 10,
+            seed: 0,
+            on_the_draw: false,
+            max_mulligans: 2,
+            min_lands: 2,
+            max_lands: 5,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct GoldfishReport {
+    pub runs: u32,
+    /// Games by mulligans taken, starting at none.
+    pub mulligans: Vec<u32>,
+    pub turns: Vec<GoldfishTurn>,
+}
+
+/// Averages over every game of a turn. Colors are ignored, any land pays
+/// for any spell.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct GoldfishTurn {
+    pub turn: u32,
+    pub average_lands: f64,
+    /// Share of games with a land played on every turn so far.
+    pub land_drop_rate: f64,
+    /// Share of games casting a spell with mana value equal to the turn.
+    pub on_curve_rate: f64,
+    pub average_mana_spent: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct SampleHand {
+    /// Drawing again with this seed deals the same hand.
+    pub seed: u32,
+    /// One entry per card, an item with several copies drawn shows up once
+    /// for each.
+    pub hand: Vec<ListItemId>,
+    /// Cards put on the bottom for the mulligans taken.
+    pub bottomed: Vec<ListItemId>,
+}
+
+/// How many sources each color needs and what the lands of a list provide,
+/// after Frank Karsten's "How Many Sources Do You Need to Consistently Cast
+/// Your Spells?".
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct ManaBaseReport {
+    pub deck_size: u32,
+    pub land_count: u32,
+    /// Colors the deck casts or its lands produce, in WUBRG order.
+    pub colors: Vec<ColorSources>,
+    pub lands: Vec<LandSources>,
+    /// The deck's basic lands split over its colors to make up for what the
+    /// other lands don't cover.
+    pub basic_split: Vec<BasicLandCount>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct ColorSources {
+    pub color: Color,
+    /// Sources needed to cast the hardest spell of the color on curve.
+    pub required: u32,
+    /// Name of that spell.
+    pub hardest_card: Option<String>,
+    pub sources: u32,
+    pub under_supported: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub enum LandKind {
+    Basic,
+    Fetch,
+    /// Enters tapped unless you control two or fewer other lands.
+    FastLand,
+    /// Always enters tapped.
+    Tapped,
+    Dual,
+    Monocolored,
+    Colorless,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct LandSources {
+    pub card_core_id: CardCoreId,
+    pub name: String,
+    pub quantity: u32,
+    pub kind: LandKind,
+    pub colors: Vec<Color>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct BasicLandCount {
+    pub color: Color,
+    pub count: u32,
+}
@@ -0,0 +1,53 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{
+    CardCoreId, CardPrintingDetail, Folder, FolderId, ListId, ListItemId, ListRole, ProjectId,
+};
+
+/// Every list holding a card, grouped by project.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CardUsage {
+    pub card_core_id: CardCoreId,
+    /// Copies across all projects.
+    pub quantity: u32,
+    pub projects: Vec<ProjectCardUsage>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct ProjectCardUsage {
+    pub project_id: ProjectId,
+    pub project_name: String,
+    /// Folders from the root down to the one holding the project.
+    pub folder_path: Vec<Folder>,
+    pub quantity: u32,
+    pub lists: Vec<ListCardUsage>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct ListCardUsage {
+    pub list_id: ListId,
+    pub list_name: String,
+    pub list_item_id: ListItemId,
+    pub quantity: u32,
+    pub printing: Option<CardPrintingDetail>,
+}
+
+/// A list item holding a card, with the list and project it's in.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CardUsageRow {
+    pub list_item_id: ListItemId,
+    pub card_core_id: CardCoreId,
+    pub list_id: ListId,
+    pub list_name: String,
+    pub list_role: ListRole,
+    pub project_id: ProjectId,
+    pub project_name: String,
+    pub folder_id: FolderId,
+    pub quantity: i32,
+    pub printing: Option<CardPrintingDetail>,
+}
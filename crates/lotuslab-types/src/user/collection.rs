@@ -0,0 +1,103 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{
+    CardCondition, CardCoreId, CardPrintingId, CollectionEntryId, Finish, Patch, RepoError,
+};
+
+/// Copies of one printing the user physically owns. The collection is kept
+/// apart from lists, a list only says which cards a deck wants.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CollectionEntry {
+    pub id: CollectionEntryId,
+    /// The card of `card_printing_id`, kept on the entry for lookups by card.
+    pub card_core_id: CardCoreId,
+    /// `None` once a catalog update removed the printing without a
+    /// replacement. The copies still count for their card.
+    pub card_printing_id: Option<CardPrintingId>,
+    pub quantity: i32,
+    pub finish: Finish,
+    pub condition: CardCondition,
+    /// Language code of the printed cards, e.g. `"en"`.
+    pub language: String,
+    /// Price paid per copy, in cents.
+    pub purchase_price: Option<u32>,
+    /// `YYYY-MM-DD` date the copies were acquired.
+    pub acquired_on: Option<String>,
+    /// Where the copies are kept, e.g. a binder or box name.
+    pub location: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct NewCollectionEntry {
+    pub card_printing_id: CardPrintingId,
+    /// Defaults to 1.
+    pub quantity: Option<i32>,
+    #[serde(default)]
+    pub finish: Finish,
+    #[serde(default)]
+    pub condition: CardCondition,
+    /// Defaults to `"en"`.
+    pub language: Option<String>,
+    pub purchase_price: Option<u32>,
+    pub acquired_on: Option<String>,
+    pub location: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct UpdateCollectionEntry {
+    #[serde(default)]
+    pub card_printing_id: Patch<CardPrintingId>,
+    #[serde(default)]
+    pub quantity: Patch<i32>,
+    #[serde(default)]
+    pub finish: Patch<Finish>,
+    #[serde(default)]
+    pub condition: Patch<CardCondition>,
+    #[serde(default)]
+    pub language: Patch<String>,
+    #[serde(default)]
+    pub purchase_price: Patch<u32>,
+    #[serde(default)]
+    pub acquired_on: Patch<String>,
+    #[serde(default)]
+    pub location: Patch<String>,
+}
+
+// Repos
+#[async_trait::async_trait]
+pub trait CollectionReadRepo: Send + Sync {
+    async fn get_collection_entry(
+        &self,
+        id: &CollectionEntryId,
+    ) -> Result<CollectionEntry, RepoError>;
+    async fn list_collection_entries(&self) -> Result<Vec<CollectionEntry>, RepoError>;
+    /// Entries of the given cards, in any printing.
+    async fn collection_entries_for_cards(
+        &self,
+        ids: &[CardCoreId],
+    ) -> Result<Vec<CollectionEntry>, RepoError>;
+}
+
+#[async_trait::async_trait]
+pub trait CollectionWriteRepo: Send + Sync {
+    async fn create_collection_entry(
+        &self,
+        new: NewCollectionEntry,
+    ) -> Result<CollectionEntry, RepoError>;
+    /// Creates every entry or, when one of them fails, none of them.
+    async fn create_collection_entries(
+        &self,
+        new: Vec<NewCollectionEntry>,
+    ) -> Result<(), RepoError>;
+    async fn update_collection_entry(
+        &self,
+        id: &CollectionEntryId,
+        patch: UpdateCollectionEntry,
+    ) -> Result<CollectionEntry, RepoError>;
+    async fn delete_collection_entry(&self, id: &CollectionEntryId) -> Result<(), RepoError>;
+}
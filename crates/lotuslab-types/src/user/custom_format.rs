@@ -0,0 +1,35 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{CustomFormatId, FormatRules, RepoError};
+
+/// A format defined by the user, like a house banlist or a cube variant.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CustomFormat {
+    pub id: CustomFormatId,
+    /// The format's name is [FormatRules::name].
+    pub rules: FormatRules,
+}
+
+// Repos
+#[async_trait::async_trait]
+pub trait CustomFormatReadRepo: Send + Sync {
+    async fn get_custom_format(&self, id: &CustomFormatId) -> Result<CustomFormat, RepoError>;
+    async fn list_custom_formats(&self) -> Result<Vec<CustomFormat>, RepoError>;
+}
+
+#[async_trait::async_trait]
+pub trait CustomFormatWriteRepo: Send + Sync {
+    async fn create_custom_format(&self, rules: FormatRules) -> Result<CustomFormat, RepoError>;
+    /// Replaces the format's rules as a whole.
+    async fn update_custom_format(
+        &self,
+        id: &CustomFormatId,
+        rules: FormatRules,
+    ) -> Result<CustomFormat, RepoError>;
+    async fn delete_custom_format(&self, id: &CustomFormatId) -> Result<(), RepoError>;
+}
@@ -7,7 +7,7 @@ use specta::Type;
 
 use crate::{FolderId, Patch, Project, RepoError};
 
-#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
 pub struct Folder {
     pub id: FolderId,
     pub name: String,
@@ -38,6 +38,7 @@ pub struct FolderChildren {
 pub trait FolderReadRepo: Send + Sync {
     async fn get_folder_metadata(&self, id: &FolderId) -> Result<Folder, RepoError>;
     async fn get_folder_children(&self, id: &FolderId) -> Result<FolderChildren, RepoError>;
+    async fn list_folders(&self) -> Result<Vec<Folder>, RepoError>;
 }
 
 #[async_trait::async_trait]
@@ -0,0 +1,63 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{CardCoreId, CardPrintingDetail, ProjectId};
+
+/// Which cards of a project the collection covers and what the rest costs.
+///
+/// Owned copies go to other projects first, a copy sleeved in another deck
+/// can't also be in this one. Prices are in US cents.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct HaveNeedReport {
+    pub project_id: ProjectId,
+    /// Sorted by name.
+    pub cards: Vec<HaveNeedCard>,
+    pub wanted: u32,
+    pub have: u32,
+    pub missing: u32,
+    /// The price of every missing copy, leaving out cards without one.
+    pub cost_to_complete: u32,
+    /// Missing copies that have no price.
+    pub unpriced: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct HaveNeedCard {
+    pub card_core_id: CardCoreId,
+    pub name: String,
+    /// Copies across the project's main deck and sideboard.
+    pub wanted: u32,
+    /// Copies in the collection, in any printing.
+    pub owned: u32,
+    /// Owned copies other projects already use.
+    pub allocated: u32,
+    pub have: u32,
+    pub missing: u32,
+    /// The other projects using the card, sorted by name.
+    pub allocations: Vec<CardAllocation>,
+    /// The printing to buy, the project's own pick when it has a price and
+    /// the cheapest one otherwise.
+    pub buy_printing: Option<CardPrintingDetail>,
+    /// Price per copy of `buy_printing`.
+    pub unit_price: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct CardAllocation {
+    pub project_id: ProjectId,
+    pub project_name: String,
+    pub quantity: u32,
+}
+
+/// Text formats for the missing cards of a [HaveNeedReport].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub enum BuyListFormat {
+    /// `1 Sol Ring` per line.
+    PlainText,
+    /// TCGplayer's mass entry, `1 Sol Ring [C21]` per line.
+    TcgPlayer,
+}
@@ -7,17 +7,50 @@ use specta::Type;
 
 use crate::{ListId, Patch, ProjectId, RepoError};
 
+/// What a list is for in its project's deck. Names are free text, so this
+/// decides whether a list is part of the deck, not what it's called.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+pub enum ListRole {
+    #[default]
+    Main,
+    Sideboard,
+    /// Cards under consideration, never part of the deck.
+    Maybeboard,
+}
+
+impl ListRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListRole::Main => "main",
+            ListRole::Sideboard => "sideboard",
+            ListRole::Maybeboard => "maybeboard",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "main" => Some(ListRole::Main),
+            "sideboard" => Some(ListRole::Sideboard),
+            "maybeboard" => Some(ListRole::Maybeboard),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct List {
     pub id: ListId,
     pub name: String,
     pub project: ProjectId,
+    pub role: ListRole,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct NewList {
     pub name: String,
     pub project: ProjectId,
+    #[serde(default)]
+    pub role: ListRole,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -26,11 +59,14 @@ pub struct PatchList {
     pub name: Patch<String>,
     #[serde(default)]
     pub project: Patch<ProjectId>,
+    #[serde(default)]
+    pub role: Patch<ListRole>,
 }
 
 #[async_trait::async_trait]
 pub trait ListReadRepo: Send + Sync {
     async fn get_list(&self, id: &ListId) -> Result<List, RepoError>;
+    async fn lists_for_project(&self, project_id: &ProjectId) -> Result<Vec<List>, RepoError>;
     // TODO figure out the best way to get a formatted list for the frontend
 }
 
@@ -6,8 +6,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    CardCoreDetail, CardCoreId, CardPrintingDetail, CardPrintingId, ListId, ListItemId, Patch,
-    RepoError,
+    CardCoreDetail, CardCoreId, CardPrintingDetail, CardPrintingId, CardUsageRow, ListId,
+    ListItemId, Patch, RepoError, TagId,
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -16,6 +16,14 @@ pub struct ListItemSummary {
     pub list_id: ListId,
     pub card_core_id: CardCoreId,
     pub selected_printing: Option<CardPrintingId>,
+    pub quantity: i32,
+    pub notes: Option<String>,
+    pub custom_image_uri: Option<String>,
+    pub tags: Vec<TagId>,
+    /// The tags out of `tags` that tag rules added.
+    pub auto_tags: Vec<TagId>,
+    /// Tags a rule added and the user removed, rules leave them off.
+    pub dismissed_tags: Vec<TagId>,
     // TODO selected side
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -25,14 +33,22 @@ pub struct ListItemSummary {
 pub struct ListItemDetail {
     pub id: ListItemId,
     pub list_id: ListId,
-    pub card_card_id: CardCoreId,
+    pub card_core_id: CardCoreId,
     pub selected_printing: Option<CardPrintingId>,
+    pub quantity: i32,
+    pub notes: Option<String>,
+    pub custom_image_uri: Option<String>,
+    pub tags: Vec<TagId>,
+    /// The tags out of `tags` that tag rules added.
+    pub auto_tags: Vec<TagId>,
+    /// Tags a rule added and the user removed, rules leave them off.
+    pub dismissed_tags: Vec<TagId>,
     // TODO add relevant details
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 
     pub card_core_details: CardCoreDetail,
-    pub card_printing_details: CardPrintingDetail,
+    pub card_printing_details: Option<CardPrintingDetail>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -42,6 +58,7 @@ pub struct NewListItem {
     pub selected_printing: Option<CardPrintingId>,
     pub quantity: Option<i32>,
     pub notes: Option<String>,
+    pub tags: Vec<TagId>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -52,6 +69,12 @@ pub struct UpdateListItem {
     pub quantity: Patch<i32>,
     #[serde(default)]
     pub notes: Patch<String>,
+    #[serde(default)]
+    pub custom_image_uri: Patch<String>,
+    /// Auto tags left out are dismissed, dismissed tags put back become the
+    /// user's own.
+    #[serde(default)]
+    pub tags: Patch<Vec<TagId>>,
 }
 
 #[async_trait::async_trait]
@@ -62,15 +85,29 @@ pub trait ListItemReadRepo: Send + Sync {
         &self,
         list_id: &ListId,
     ) -> Result<Vec<ListItemSummary>, RepoError>;
+    /// Items whose card is missing from the catalog are left out.
+    async fn list_item_details_for_list(
+        &self,
+        list_id: &ListId,
+    ) -> Result<Vec<ListItemDetail>, RepoError>;
+    /// Every list item holding one of the cards, across all projects.
+    async fn card_usage_rows(
+        &self,
+        card_core_ids: &[CardCoreId],
+    ) -> Result<Vec<CardUsageRow>, RepoError>;
 }
 
 #[async_trait::async_trait]
 pub trait ListItemWriteRepo: Send + Sync {
     async fn create_list_item(&self, new: NewListItem) -> Result<ListItemDetail, RepoError>;
+    /// Creates every item or, when one of them fails, none of them.
+    async fn create_list_items(&self, new: Vec<NewListItem>) -> Result<(), RepoError>;
     async fn update_list_item(
         &self,
         id: &ListItemId,
         patch: UpdateListItem,
     ) -> Result<ListItemDetail, RepoError>;
     async fn delete_list_item(&self, id: &ListItemId) -> Result<(), RepoError>;
+    /// Replaces the item's auto tags, its other tags are left alone.
+    async fn set_auto_tags(&self, id: &ListItemId, auto_tags: Vec<TagId>) -> Result<(), RepoError>;
 }
@@ -2,14 +2,24 @@
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+pub mod card_usage;
+pub mod collection;
+pub mod custom_format;
 pub mod folder;
+pub mod have_need;
 pub mod list;
 pub mod list_item;
 pub mod project;
 pub mod tag;
+pub mod tag_rule;
 
+pub use self::card_usage::*;
+pub use self::collection::*;
+pub use self::custom_format::*;
 pub use self::folder::*;
+pub use self::have_need::*;
 pub use self::list::*;
 pub use self::list_item::*;
 pub use self::project::*;
 pub use self::tag::*;
+pub use self::tag_rule::*;
@@ -5,13 +5,15 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
-use crate::{FolderId, Patch, ProjectId, RepoError};
+use crate::{FolderId, ListItemId, Patch, ProjectId, RepoError};
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct Project {
     pub id: ProjectId,
     pub name: String,
     pub folder: FolderId,
+    /// Items from the project's lists that are the deck's commanders.
+    pub commanders: Vec<ListItemId>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -26,6 +28,8 @@ pub struct PatchProject {
     pub name: Patch<String>,
     #[serde(default)]
     pub folder: Patch<FolderId>,
+    #[serde(default)]
+    pub commanders: Patch<Vec<ListItemId>>,
 }
 
 // TODO figure out how to assert invariants for patches
@@ -3,23 +3,24 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use serde::{Deserialize, Serialize};
+use specta::Type;
 
 use crate::{Patch, RepoError, TagId};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct Tag {
     pub id: TagId,
     pub name: String,
     pub color: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct NewTag {
     pub name: String,
     pub color: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct UpdateTag {
     #[serde(default)]
     pub name: Patch<String>,
@@ -29,7 +30,9 @@ pub struct UpdateTag {
 
 #[async_trait::async_trait]
 pub trait TagReadRepo: Send + Sync {
-    async fn get_tag(&self, new: NewTag) -> Result<Tag, RepoError>;
+    async fn get_tag(&self, id: &TagId) -> Result<Tag, RepoError>;
+    async fn find_tag_by_name(&self, name: &str) -> Result<Tag, RepoError>;
+    async fn list_tags(&self) -> Result<Vec<Tag>, RepoError>;
 }
 
 #[async_trait::async_trait]
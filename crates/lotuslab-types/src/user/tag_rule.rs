@@ -0,0 +1,62 @@
+// SPDX-FileCopyrightText: 2025 Jesse Aubin <jesseb34r@jesseaubin.me>
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{Patch, RepoError, TagId, TagRuleId};
+
+/// Gives `tag` to every list item whose card matches `query`, a search in the
+/// [crate::CardQuery] language. Tags added this way are kept apart from the
+/// ones the user set, see [crate::ListItemDetail::auto_tags].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct TagRule {
+    pub id: TagRuleId,
+    pub query: String,
+    pub tag: TagId,
+    /// Disabled rules are kept but never run.
+    pub enabled: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct NewTagRule {
+    pub query: String,
+    pub tag: TagId,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct UpdateTagRule {
+    #[serde(default)]
+    pub query: Patch<String>,
+    #[serde(default)]
+    pub tag: Patch<TagId>,
+    #[serde(default)]
+    pub enabled: Patch<bool>,
+}
+
+/// What running the tag rules over a list changed.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct AutoTagReport {
+    pub items_checked: u32,
+    pub tags_added: u32,
+    pub tags_removed: u32,
+}
+
+// Repos
+#[async_trait::async_trait]
+pub trait TagRuleReadRepo: Send + Sync {
+    async fn get_tag_rule(&self, id: &TagRuleId) -> Result<TagRule, RepoError>;
+    async fn list_tag_rules(&self) -> Result<Vec<TagRule>, RepoError>;
+}
+
+#[async_trait::async_trait]
+pub trait TagRuleWriteRepo: Send + Sync {
+    async fn create_tag_rule(&self, new: NewTagRule) -> Result<TagRule, RepoError>;
+    async fn update_tag_rule(
+        &self,
+        id: &TagRuleId,
+        patch: UpdateTagRule,
+    ) -> Result<TagRule, RepoError>;
+    async fn delete_tag_rule(&self, id: &TagRuleId) -> Result<(), RepoError>;
+}